@@ -1,5 +1,7 @@
 //! Traits for passing arguments to SQL queries.
 
+use std::fmt::{self, Write};
+
 use crate::database::Database;
 use crate::encode::Encode;
 use crate::types::Type;
@@ -17,4 +19,22 @@ pub trait Arguments: Send + Sized + Default + 'static {
     where
         T: Type<Self::Database>,
         T: Encode<Self::Database>;
+
+    /// Write the bind parameter placeholder for the next value to be added to the arguments.
+    ///
+    /// This is `?` for most SQL flavors and `$N` for Postgres.
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        writer.write_char('?')
+    }
+}
+
+/// Arguments that can bind values to named bind parameters (`:name` or `@name` in the query).
+pub trait NamedArguments: Arguments {
+    /// Add the value of the named bind parameter `name`.
+    ///
+    /// Named and positional values cannot be mixed in the same arguments.
+    fn add_named<T>(&mut self, name: &str, value: T)
+    where
+        T: Type<Self::Database>,
+        T: Encode<Self::Database>;
 }
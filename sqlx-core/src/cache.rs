@@ -0,0 +1,189 @@
+//! A bounded cache of prepared statements, shared by the database drivers.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::url::Url;
+
+/// The number of statements a connection caches, unless it is set with the
+/// `statement_cache_capacity` parameter of the connection URL.
+const DEFAULT_CAPACITY: usize = 100;
+
+/// A cache of the prepared statements of a connection by their query, which evicts the least
+/// recently used statement when it is full.
+///
+/// Finding the least recently used statement is `O(log n)` in the number of cached statements.
+/// The cache does not close the statements it evicts; each driver does that in its own way.
+#[derive(Debug)]
+pub(crate) struct StatementCache<T> {
+    statements: HashMap<Box<str>, Entry<T>>,
+    capacity: usize,
+
+    // the queries of the cached statements by their `last_used`, the least recently used first
+    lru: BTreeMap<u64, Box<str>>,
+
+    // incremented on every use of a statement; the least recently used statement has the
+    // smallest `last_used`
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    statement: T,
+    last_used: u64,
+}
+
+impl<T> StatementCache<T> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            statements: HashMap::with_capacity(capacity.min(DEFAULT_CAPACITY)),
+            capacity,
+            lru: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    /// Creates a cache with the capacity set by the connection URL.
+    pub(crate) fn from_url(url: &Url) -> crate::Result<Self> {
+        Ok(Self::new(capacity(url)?))
+    }
+
+    /// Returns the cached statement for `query` and marks it as the most recently used.
+    pub(crate) fn get_mut(&mut self, query: &str) -> Option<&mut T> {
+        self.tick += 1;
+
+        let tick = self.tick;
+
+        let entry = self.statements.get_mut(query)?;
+
+        if let Some(query) = self.lru.remove(&entry.last_used) {
+            self.lru.insert(tick, query);
+        }
+
+        entry.last_used = tick;
+
+        Some(&mut entry.statement)
+    }
+
+    /// Inserts the statement for `query` as the most recently used; the least recently used
+    /// statement is removed and returned if the cache is full.
+    pub(crate) fn insert(&mut self, query: &str, statement: T) -> Option<T> {
+        let evicted = if self.is_full() && !self.statements.contains_key(query) {
+            self.remove_lru()
+        } else {
+            None
+        };
+
+        self.tick += 1;
+
+        let replaced = self.statements.insert(
+            query.into(),
+            Entry {
+                statement,
+                last_used: self.tick,
+            },
+        );
+
+        if let Some(replaced) = replaced {
+            self.lru.remove(&replaced.last_used);
+        }
+
+        self.lru.insert(self.tick, query.into());
+
+        evicted
+    }
+
+    /// Returns `true` if inserting a statement for a new query evicts another one.
+    pub(crate) fn is_full(&self) -> bool {
+        self.statements.len() >= self.capacity
+    }
+
+    /// Removes and returns the least recently used statement.
+    pub(crate) fn remove_lru(&mut self) -> Option<T> {
+        let last_used = *self.lru.keys().next()?;
+        let query = self.lru.remove(&last_used)?;
+
+        self.statements.remove(&query).map(|entry| entry.statement)
+    }
+
+    /// Removes and returns all statements.
+    pub(crate) fn clear(&mut self) -> Vec<T> {
+        self.lru.clear();
+
+        self.statements
+            .drain()
+            .map(|(_, entry)| entry.statement)
+            .collect()
+    }
+}
+
+/// The capacity of the statement cache, set by the `statement_cache_capacity` parameter of
+/// the connection URL.
+pub(crate) fn capacity(url: &Url) -> crate::Result<usize> {
+    match url.param("statement_cache_capacity") {
+        Some(capacity) => capacity
+            .parse()
+            .ok()
+            .filter(|&capacity| capacity > 0)
+            .ok_or_else(|| {
+                config_err!(
+                    "expected a number greater than 0 for `statement_cache_capacity`, got {:?}",
+                    capacity
+                )
+            }),
+
+        None => Ok(DEFAULT_CAPACITY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    #[test]
+    fn it_evicts_the_least_recently_used_statement() {
+        let mut cache = StatementCache::new(2);
+
+        assert_eq!(cache.insert("SELECT 1", 1), None);
+        assert_eq!(cache.insert("SELECT 2", 2), None);
+        assert!(cache.is_full());
+
+        // `SELECT 1` is now used more recently than `SELECT 2`
+        assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 1));
+
+        assert_eq!(cache.insert("SELECT 3", 3), Some(2));
+        assert_eq!(cache.get_mut("SELECT 2"), None);
+        assert_eq!(cache.statements.len(), 2);
+        assert_eq!(cache.lru.len(), 2);
+
+        // replacing a statement does not evict another one
+        assert_eq!(cache.insert("SELECT 3", 4), None);
+        assert_eq!(cache.get_mut("SELECT 3"), Some(&mut 4));
+
+        let mut cleared = cache.clear();
+        cleared.sort();
+
+        assert_eq!(cleared, [1, 4]);
+        assert!(cache.statements.is_empty());
+        assert!(cache.lru.is_empty());
+    }
+
+    #[test]
+    fn it_parses_the_capacity() {
+        let url = |url| Url::try_from(url).unwrap();
+
+        assert_eq!(
+            capacity(&url("postgres://localhost/db")).unwrap(),
+            DEFAULT_CAPACITY
+        );
+
+        assert_eq!(
+            capacity(&url("postgres://localhost/db?statement_cache_capacity=10")).unwrap(),
+            10
+        );
+
+        assert!(capacity(&url("postgres://localhost/db?statement_cache_capacity=0")).is_err());
+        assert!(capacity(&url("postgres://localhost/db?statement_cache_capacity=all")).is_err());
+    }
+}
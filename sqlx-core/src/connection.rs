@@ -3,6 +3,7 @@
 use std::convert::TryInto;
 
 use futures_core::future::BoxFuture;
+use futures_util::future;
 
 use crate::executor::Executor;
 use crate::pool::{Pool, PoolConnection};
@@ -52,6 +53,18 @@ where
 
     /// Checks if a connection to the database is still valid.
     fn ping(&mut self) -> BoxFuture<crate::Result<()>>;
+
+    /// Removes all prepared statements from the statement cache of this connection and closes
+    /// them.
+    ///
+    /// A connection caches the statements of the queries it runs with arguments, up to the
+    /// number set by the `statement_cache_capacity` parameter of the connection URL (`100` by
+    /// default); when the cache is full, the least recently used statement is closed.
+    ///
+    /// The default implementation does nothing, for connections that do not cache statements.
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        Box::pin(future::ok(()))
+    }
 }
 
 /// Represents a type that can directly establish a new connection.
@@ -61,6 +74,19 @@ pub trait Connect: Connection {
     where
         T: TryInto<Url, Error = url::ParseError>,
         Self: Sized;
+
+    /// Returns `true` if the database of the connection URL is destroyed when its last
+    /// connection is closed, e.g. a shared in-memory SQLite database. A [`Pool`] then keeps
+    /// a connection to it open, which it never reaps, until the pool is closed. That connection
+    /// is never handed out, so it is not counted in the size of the pool and the
+    /// `after_connect` callback is not run on it.
+    #[doc(hidden)]
+    fn needs_keep_alive(_url: &str) -> bool
+    where
+        Self: Sized,
+    {
+        false
+    }
 }
 
 #[allow(dead_code)]
@@ -18,6 +18,28 @@ macro_rules! decode_err {
     };
 }
 
+#[allow(unused_macros)]
+macro_rules! config_err {
+    ($s:literal, $($args:tt)*) => {
+        crate::Error::Configuration(format!($s, $($args)*).into())
+    };
+
+    ($s:literal) => {
+        crate::Error::Configuration($s.into())
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! arg_err {
+    ($s:literal, $($args:tt)*) => {
+        crate::Error::Argument(format!($s, $($args)*).into())
+    };
+
+    ($expr:expr) => {
+        crate::Error::Argument($expr.into())
+    };
+}
+
 /// A specialized `Result` type for SQLx.
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -31,6 +53,9 @@ pub enum Error {
     /// Connection URL was malformed.
     UrlParse(url::ParseError),
 
+    /// An option of the connection URL was invalid (e.g., an unknown value for a parameter).
+    Configuration(Box<dyn StdError + Send + Sync>),
+
     /// An error was returned by the database.
     Database(Box<dyn DatabaseError>),
 
@@ -47,6 +72,12 @@ pub enum Error {
     /// Column index was out of bounds (e.g., asking for column 4 in a 2-column row).
     ColumnIndexOutOfBounds { index: usize, len: usize },
 
+    /// An argument could not be used (e.g., a named parameter in the query has no value bound
+    /// to it, the list arguments (`..expr`) in a `VALUES` row of a `query!()` have different
+    /// lengths, a value could not be encoded, like a multidimensional Postgres array with
+    /// sub-arrays of different lengths, or a name passed to SQLite contains a NUL byte).
+    Argument(Box<str>),
+
     /// Unexpected or invalid data was encountered. This would indicate that we received
     /// data that we were not expecting or it was in a format we did not understand. This
     /// generally means either there is a programming error in a SQLx driver or
@@ -105,6 +136,7 @@ impl StdError for Error {
         match self {
             Error::Io(error) => Some(error),
             Error::UrlParse(error) => Some(error),
+            Error::Configuration(error) => Some(&**error),
             Error::PoolTimedOut(Some(error)) => Some(&**error),
             Error::Decode(error) => Some(&**error),
             Error::Tls(error) => Some(&**error),
@@ -124,6 +156,8 @@ impl Display for Error {
 
             Error::UrlParse(error) => write!(f, "{}", error),
 
+            Error::Configuration(error) => write!(f, "error with configuration: {}", error),
+
             Error::Decode(error) => write!(f, "{}", error),
 
             Error::Database(error) => Display::fmt(error, f),
@@ -140,6 +174,8 @@ impl Display for Error {
                 len, index
             ),
 
+            Error::Argument(ref err) => f.write_str(err),
+
             Error::Protocol(ref err) => f.write_str(err),
 
             Error::PoolTimedOut(Some(ref err)) => {
@@ -26,6 +26,9 @@ pub mod error;
 #[macro_use]
 mod io;
 
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+mod cache;
+
 pub mod connection;
 pub mod cursor;
 pub mod database;
@@ -45,12 +48,20 @@ pub mod decode;
 pub mod describe;
 
 pub mod encode;
+
+#[doc(hidden)]
+pub mod named;
+
 pub mod pool;
 pub mod query;
+pub mod query_builder;
 
 #[macro_use]
 pub mod query_as;
 
+#[macro_use]
+pub mod query_scalar;
+
 pub mod types;
 
 #[macro_use]
@@ -1,4 +1,4 @@
-use crate::arguments::Arguments;
+use crate::arguments::{Arguments, NamedArguments};
 use crate::encode::{Encode, IsNull};
 use crate::mysql::type_info::MySqlTypeInfo;
 use crate::mysql::MySql;
@@ -9,6 +9,41 @@ pub struct MySqlArguments {
     pub(crate) param_types: Vec<MySqlTypeInfo>,
     pub(crate) params: Vec<u8>,
     pub(crate) null_bitmap: Vec<u8>,
+
+    // Names of the bind parameters and the offsets of their values in `params`, if they
+    // are named
+    pub(crate) names: Vec<Box<str>>,
+    offsets: Vec<usize>,
+}
+
+impl MySqlArguments {
+    // Returns the named values in the order of `order`, the index of the value for each bind
+    // parameter of the query; a value may be repeated
+    pub(crate) fn reorder(&self, order: &[usize]) -> MySqlArguments {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.param_types.reserve(order.len());
+        arguments.params.reserve(self.params.len());
+        arguments.null_bitmap.resize((order.len() + 7) / 8, 0);
+
+        for (index, &value) in order.iter().enumerate() {
+            let start = self.offsets[value];
+            let end = self
+                .offsets
+                .get(value + 1)
+                .copied()
+                .unwrap_or_else(|| self.params.len());
+
+            arguments.param_types.push(self.param_types[value].clone());
+            arguments.params.extend_from_slice(&self.params[start..end]);
+
+            if self.null_bitmap[value / 8] & (1 << (value % 8)) as u8 != 0 {
+                arguments.null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
+            }
+        }
+
+        arguments
+    }
 }
 
 impl Arguments for MySqlArguments {
@@ -41,3 +76,15 @@ impl Arguments for MySqlArguments {
         }
     }
 }
+
+impl NamedArguments for MySqlArguments {
+    fn add_named<T>(&mut self, name: &str, value: T)
+    where
+        T: Type<Self::Database>,
+        T: Encode<Self::Database>,
+    {
+        self.names.push(name.into());
+        self.offsets.push(self.params.len());
+        self.add(value);
+    }
+}
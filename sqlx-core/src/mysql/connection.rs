@@ -1,11 +1,11 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
 use std::convert::TryInto;
 use std::ops::Range;
 
 use futures_core::future::BoxFuture;
 use sha1::Sha1;
 
+use crate::cache::StatementCache;
 use crate::connection::{Connect, Connection};
 use crate::executor::Executor;
 use crate::mysql::protocol::{
@@ -86,7 +86,7 @@ pub(super) const COLLATE_UTF8MB4_UNICODE_CI: u8 = 224;
 pub struct MySqlConnection {
     pub(super) stream: MySqlStream,
     pub(super) is_ready: bool,
-    pub(super) cache_statement: HashMap<Box<str>, u32>,
+    pub(super) cache_statement: StatementCache<u32>,
 
     // Work buffer for the value ranges of the current row
     // This is used as the backing memory for each Row's value indexes
@@ -276,6 +276,8 @@ async fn ping(stream: &mut MySqlStream) -> crate::Result<()> {
 impl MySqlConnection {
     pub(super) async fn new(url: std::result::Result<Url, url::ParseError>) -> crate::Result<Self> {
         let url = url?;
+        let cache_statement = StatementCache::from_url(&url)?;
+
         let mut stream = MySqlStream::new(&url).await?;
 
         establish(&mut stream, &url).await?;
@@ -284,7 +286,7 @@ impl MySqlConnection {
             stream,
             current_row_values: Vec::with_capacity(10),
             is_ready: true,
-            cache_statement: HashMap::new(),
+            cache_statement,
         };
 
         // After the connection is established, we initialize by configuring a few
@@ -342,4 +344,9 @@ impl Connection for MySqlConnection {
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(ping(&mut self.stream))
     }
+
+    #[inline]
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        Box::pin(MySqlConnection::clear_cached_statements(self))
+    }
 }
@@ -4,10 +4,11 @@ use crate::cursor::Cursor;
 use crate::describe::{Column, Describe};
 use crate::executor::{Execute, Executor, RefExecutor};
 use crate::mysql::protocol::{
-    self, ColumnDefinition, ComQuery, ComStmtExecute, ComStmtPrepare, ComStmtPrepareOk, FieldFlags,
-    Status,
+    self, ColumnDefinition, ComQuery, ComStmtClose, ComStmtExecute, ComStmtPrepare,
+    ComStmtPrepareOk, FieldFlags, Status,
 };
 use crate::mysql::{MySql, MySqlArguments, MySqlCursor, MySqlTypeInfo};
+use crate::named::{self, Placeholder};
 
 impl super::MySqlConnection {
     // Creates a prepared statement for the passed query string
@@ -38,15 +39,12 @@ impl super::MySqlConnection {
     }
 
     // Gets a cached prepared statement ID _or_ prepares the statement if not in the cache
-    // At the end we should have [cache_statement] and [cache_statement_columns] filled
     async fn get_or_prepare(&mut self, query: &str) -> crate::Result<u32> {
-        if let Some(&id) = self.cache_statement.get(query) {
+        if let Some(&mut id) = self.cache_statement.get_mut(query) {
             Ok(id)
         } else {
             let stmt = self.prepare(query).await?;
 
-            self.cache_statement.insert(query.into(), stmt.statement_id);
-
             // COM_STMT_PREPARE returns the input columns
             // We make no use of that data, so cycle through and drop them
             self.drop_column_defs(stmt.params as usize).await?;
@@ -55,10 +53,30 @@ impl super::MySqlConnection {
             // We just drop these as we get these when we execute the query
             self.drop_column_defs(stmt.columns as usize).await?;
 
+            if let Some(evicted) = self.cache_statement.insert(query, stmt.statement_id) {
+                self.close_statement(evicted).await?;
+            }
+
             Ok(stmt.statement_id)
         }
     }
 
+    async fn close_statement(&mut self, statement_id: u32) -> crate::Result<()> {
+        // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_close.html
+        // COM_STMT_CLOSE has no response
+        self.stream.send(ComStmtClose { statement_id }, true).await
+    }
+
+    pub(super) async fn clear_cached_statements(&mut self) -> crate::Result<()> {
+        self.stream.wait_until_ready().await?;
+
+        for statement_id in self.cache_statement.clear() {
+            self.close_statement(statement_id).await?;
+        }
+
+        Ok(())
+    }
+
     pub(crate) async fn run(
         &mut self,
         query: &str,
@@ -67,7 +85,25 @@ impl super::MySqlConnection {
         self.stream.wait_until_ready().await?;
         self.stream.is_ready = false;
 
-        if let Some(arguments) = arguments {
+        if let Some(mut arguments) = arguments {
+            // Named parameters are rewritten to `?` with their values in the order of the query
+            let rewritten = named::rewrite_bound(
+                query,
+                &arguments.names,
+                arguments.param_types.len(),
+                Placeholder::Positional {
+                    user_variables: true,
+                },
+            )?;
+
+            let query = if let Some((query, order)) = &rewritten {
+                arguments = arguments.reorder(order);
+
+                &**query
+            } else {
+                query
+            };
+
             let statement_id = self.get_or_prepare(query).await?;
 
             // https://dev.mysql.com/doc/dev/mysql-server/8.0.11/page_protocol_com_stmt_execute.html
@@ -165,6 +201,8 @@ impl super::MySqlConnection {
             self.stream.maybe_receive_eof().await?;
         }
 
+        self.close_statement(stmt.statement_id).await?;
+
         Ok(Describe {
             param_types: param_types.into_boxed_slice(),
             result_columns: result_columns.into_boxed_slice(),
@@ -32,4 +32,5 @@ pub type MySqlPool = crate::pool::Pool<MySqlConnection>;
 
 make_query_as!(MySqlQueryAs, MySql, MySqlRow);
 impl_map_row_for_row!(MySql, MySqlRow);
+impl_map_scalar_for_row!(MySql, MySqlRow);
 impl_from_row_for_tuples!(MySql, MySqlRow);
@@ -130,6 +130,8 @@ impl<'c> Row<'c> {
                     | TypeId::CHAR
                     | TypeId::TEXT
                     | TypeId::ENUM
+                    | TypeId::SET
+                    | TypeId::BIT
                     | TypeId::VAR_CHAR => {
                         let (len_size, len) = get_lenenc(&buffer[index..]);
 
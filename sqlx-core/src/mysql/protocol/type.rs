@@ -13,8 +13,9 @@ impl TypeId {
     pub const VAR_CHAR: TypeId = TypeId(253); // or VAR_BINARY
     pub const TEXT: TypeId = TypeId(252); // or BLOB
 
-    // Enum
+    // Enum, Set
     pub const ENUM: TypeId = TypeId(247);
+    pub const SET: TypeId = TypeId(248);
 
     // More Bytes
     pub const TINY_BLOB: TypeId = TypeId(249);
@@ -33,6 +34,9 @@ impl TypeId {
     pub const DOUBLE: TypeId = TypeId(5);
     pub const NEWDECIMAL: TypeId = TypeId(246);
 
+    // Bit field: BIT(n)
+    pub const BIT: TypeId = TypeId(16);
+
     // Date/Time: DATE, TIME, DATETIME, TIMESTAMP
     pub const DATE: TypeId = TypeId(10);
     pub const TIME: TypeId = TypeId(11);
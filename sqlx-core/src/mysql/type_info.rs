@@ -41,8 +41,17 @@ impl MySqlTypeInfo {
     }
 
     pub(crate) fn from_nullable_column_def(def: &ColumnDefinition) -> Self {
+        // ENUM and SET columns are sent as CHAR and told apart by their flags
+        let id = if def.flags.contains(FieldFlags::ENUM) {
+            TypeId::ENUM
+        } else if def.flags.contains(FieldFlags::SET) {
+            TypeId::SET
+        } else {
+            def.type_id
+        };
+
         Self {
-            id: def.type_id,
+            id,
             is_unsigned: def.flags.contains(FieldFlags::UNSIGNED),
             is_binary: def.flags.contains(FieldFlags::BINARY),
             char_set: def.char_set,
@@ -92,6 +101,11 @@ impl Display for MySqlTypeInfo {
             TypeId::VAR_CHAR => f.write_str("VARCHAR"),
             TypeId::TEXT => f.write_str("TEXT"),
 
+            TypeId::ENUM => f.write_str("ENUM"),
+            TypeId::SET => f.write_str("SET"),
+
+            TypeId::BIT => f.write_str("BIT"),
+
             TypeId::DATE => f.write_str("DATE"),
             TypeId::TIME => f.write_str("TIME"),
             TypeId::DATETIME => f.write_str("DATETIME"),
@@ -223,6 +237,65 @@ impl TypeInfo for MySqlTypeInfo {
                 true
             }
 
+            // Sets are considered compatible with other text/binary types
+            TypeId::SET
+                if match other.id {
+                    TypeId::VAR_CHAR
+                    | TypeId::TEXT
+                    | TypeId::CHAR
+                    | TypeId::TINY_BLOB
+                    | TypeId::MEDIUM_BLOB
+                    | TypeId::LONG_BLOB
+                    | TypeId::SET => true,
+
+                    _ => false,
+                } =>
+            {
+                true
+            }
+
+            TypeId::VAR_CHAR
+            | TypeId::TEXT
+            | TypeId::CHAR
+            | TypeId::TINY_BLOB
+            | TypeId::MEDIUM_BLOB
+            | TypeId::LONG_BLOB
+                if other.id == TypeId::SET =>
+            {
+                true
+            }
+
+            // BIT is compatible with BIGINT UNSIGNED and with binary strings
+            TypeId::BIT
+                if match other.id {
+                    TypeId::BIG_INT => other.is_unsigned,
+
+                    TypeId::VAR_CHAR
+                    | TypeId::TEXT
+                    | TypeId::CHAR
+                    | TypeId::TINY_BLOB
+                    | TypeId::MEDIUM_BLOB
+                    | TypeId::LONG_BLOB => other.is_binary,
+
+                    _ => false,
+                } =>
+            {
+                true
+            }
+
+            TypeId::BIG_INT if self.is_unsigned && other.id == TypeId::BIT => true,
+
+            TypeId::VAR_CHAR
+            | TypeId::TEXT
+            | TypeId::CHAR
+            | TypeId::TINY_BLOB
+            | TypeId::MEDIUM_BLOB
+            | TypeId::LONG_BLOB
+                if self.is_binary && other.id == TypeId::BIT =>
+            {
+                true
+            }
+
             // FLOAT is compatible with DOUBLE
             TypeId::FLOAT | TypeId::DOUBLE
                 if match other.id {
@@ -12,11 +12,18 @@
 //! | `u8`                                  | TINYINT UNSIGNED                                     |
 //! | `u16`                                 | SMALLINT UNSIGNED                                    |
 //! | `u32`                                 | INT UNSIGNED                                         |
-//! | `u64`                                 | BIGINT UNSIGNED                                      |
+//! | `u64`                                 | BIGINT UNSIGNED, BIT(N)                              |
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, `String`                      | VARCHAR, CHAR, TEXT                                  |
-//! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
+//! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB, BIT(N)                      |
+//! | `HashSet<String>`                     | SET                                                  |
+//!
+//! A `BIT(N)` value is decoded into a `u64` or, as a bit vector, into the big-endian bytes that
+//! hold its bits.
+//!
+//! The members of a `SET` are sent and received as a comma-separated string so a `SET` may also
+//! be used as a `String`.
 //!
 //! ### [`chrono`](https://crates.io/crates/chrono)
 //!
@@ -41,6 +48,18 @@
 //! | `time::Date`                          | DATE                                                 |
 //! | `time::Time`                          | TIME                                                 |
 //!
+//! ### [`uuid`](https://crates.io/crates/uuid)
+//!
+//! Requires the `uuid` Cargo feature flag.
+//!
+//! | Rust type                             | MySQL type(s)                                        |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `uuid::Uuid`                          | BINARY(16), CHAR(36)                                 |
+//! | `uuid::adapter::Hyphenated`           | CHAR(36)                                             |
+//!
+//! A `Uuid` is sent as the 16 bytes of a `BINARY(16)` and may be decoded from either its bytes
+//! or its hyphenated text in a `CHAR(36)`.
+//!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
 //! Requires the `bigdecimal` Cargo feature flag.
 //!
@@ -55,6 +74,21 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `json::JsonValue`             | JSON
 //!
+//! # Enumerations
+//!
+//! Rust enumerations may be used for `ENUM` columns through a derive for `Type`. The variants
+//! are sent and received as their (possibly renamed) names.
+//!
+//! ```text
+//! CREATE TABLE people (mood ENUM('sad', 'ok', 'happy') NOT NULL);
+//! ```
+//!
+//! ```rust,ignore
+//! #[derive(sqlx::Type)]
+//! #[sqlx(rename_all = "lowercase")]
+//! enum Mood { Sad, Ok, Happy }
+//! ```
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -65,6 +99,7 @@ mod bool;
 mod bytes;
 mod float;
 mod int;
+mod set;
 mod str;
 mod uint;
 
@@ -77,6 +112,9 @@ mod chrono;
 #[cfg(feature = "time")]
 mod time;
 
+#[cfg(feature = "uuid")]
+mod uuid;
+
 #[cfg(feature = "json")]
 mod json;
 
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+use std::hash::BuildHasher;
+
+use byteorder::LittleEndian;
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::mysql::io::BufMutExt;
+use crate::mysql::protocol::TypeId;
+use crate::mysql::type_info::MySqlTypeInfo;
+use crate::mysql::{MySql, MySqlValue};
+use crate::types::Type;
+
+// A SET value is sent as its members separated by commas; MySQL does not allow a comma
+// in the members of a SET so there is nothing to escape
+
+impl<S> Type<MySql> for HashSet<String, S> {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo {
+            id: TypeId::SET,
+            is_binary: false,
+            is_unsigned: false,
+            char_set: 224, // utf8mb4_unicode_ci
+        }
+    }
+}
+
+impl<S> Encode<MySql> for HashSet<String, S> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut value = String::new();
+
+        for member in self {
+            if !value.is_empty() {
+                value.push(',');
+            }
+
+            value.push_str(member);
+        }
+
+        buf.put_str_lenenc::<LittleEndian>(&value);
+    }
+}
+
+impl<'de, S> Decode<'de, MySql> for HashSet<String, S>
+where
+    S: BuildHasher + Default + 'de,
+{
+    fn decode(value: MySqlValue<'de>) -> crate::Result<Self> {
+        let value = <&'de str as Decode<MySql>>::decode(value)?;
+
+        if value.is_empty() {
+            return Ok(HashSet::default());
+        }
+
+        Ok(value.split(',').map(ToOwned::to_owned).collect())
+    }
+}
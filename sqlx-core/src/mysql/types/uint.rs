@@ -8,6 +8,7 @@ use crate::mysql::protocol::TypeId;
 use crate::mysql::type_info::MySqlTypeInfo;
 use crate::mysql::{MySql, MySqlData, MySqlValue};
 use crate::types::Type;
+use crate::value::RawValue;
 use crate::Error;
 
 impl Type<MySql> for u8 {
@@ -99,6 +100,10 @@ impl Encode<MySql> for u64 {
 
 impl<'de> Decode<'de, MySql> for u64 {
     fn decode(value: MySqlValue<'de>) -> crate::Result<Self> {
+        if value.type_info().map_or(false, |ty| ty.id == TypeId::BIT) {
+            return decode_bit(value);
+        }
+
         match value.try_get()? {
             MySqlData::Binary(mut buf) => buf.read_u64::<LittleEndian>().map_err(Into::into),
 
@@ -109,3 +114,21 @@ impl<'de> Decode<'de, MySql> for u64 {
         }
     }
 }
+
+// A BIT(n) value is sent as a big-endian string of (n + 7) / 8 bytes in both protocols
+fn decode_bit(value: MySqlValue<'_>) -> crate::Result<u64> {
+    let buf = match value.try_get()? {
+        MySqlData::Binary(buf) | MySqlData::Text(buf) => buf,
+    };
+
+    if buf.len() > 8 {
+        return Err(decode_err!(
+            "expected at most 8 bytes for BIT; received {}",
+            buf.len()
+        ));
+    }
+
+    Ok(buf
+        .iter()
+        .fold(0, |value, &byte| (value << 8) | u64::from(byte)))
+}
@@ -0,0 +1,64 @@
+use byteorder::LittleEndian;
+use uuid::adapter::Hyphenated;
+use uuid::Uuid;
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::mysql::io::BufMutExt;
+use crate::mysql::protocol::TypeId;
+use crate::mysql::type_info::MySqlTypeInfo;
+use crate::mysql::{MySql, MySqlValue};
+use crate::types::Type;
+
+impl Type<MySql> for Uuid {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo {
+            id: TypeId::CHAR,
+            is_binary: true,
+            is_unsigned: false,
+            char_set: 63, // binary
+        }
+    }
+}
+
+impl Encode<MySql> for Uuid {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.put_bytes_lenenc::<LittleEndian>(self.as_bytes());
+    }
+}
+
+impl<'de> Decode<'de, MySql> for Uuid {
+    fn decode(value: MySqlValue<'de>) -> crate::Result<Self> {
+        let buf = <&'de [u8] as Decode<MySql>>::decode(value)?;
+
+        // BINARY(16) holds the bytes of the UUID; anything else is expected to
+        // be the hyphenated text of a CHAR(36)
+        if buf.len() == 16 {
+            Uuid::from_slice(buf).map_err(crate::Error::decode)
+        } else {
+            std::str::from_utf8(buf)
+                .map_err(crate::Error::decode)
+                .and_then(|s| Uuid::parse_str(s).map_err(crate::Error::decode))
+        }
+    }
+}
+
+// A UUID in its hyphenated text form, for storing a UUID in a CHAR(36)
+
+impl Type<MySql> for Hyphenated {
+    fn type_info() -> MySqlTypeInfo {
+        <str as Type<MySql>>::type_info()
+    }
+}
+
+impl Encode<MySql> for Hyphenated {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.put_str_lenenc::<LittleEndian>(&self.to_string());
+    }
+}
+
+impl<'de> Decode<'de, MySql> for Hyphenated {
+    fn decode(value: MySqlValue<'de>) -> crate::Result<Self> {
+        <Uuid as Decode<MySql>>::decode(value).map(|uuid| uuid.to_hyphenated())
+    }
+}
@@ -0,0 +1,341 @@
+//! Named bind parameters (`:name` or `@name`) and their rewriting to the positional bind
+//! parameters of a database.
+
+use std::ops::Range;
+
+/// A named bind parameter in a query.
+pub struct Param<'a> {
+    /// The range of the parameter in the query, including its `:` or `@`.
+    pub range: Range<usize>,
+
+    /// The name of the parameter, without its `:` or `@`.
+    pub name: &'a str,
+}
+
+/// The syntax of the positional bind parameters of a database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placeholder {
+    /// `$N` (Postgres) where `N` is the 1-based index of the value; a name used more than
+    /// once in the query is bound once.
+    Numbered(char),
+
+    /// `?` (MySQL) which matches values in the order they appear in the query; a name used
+    /// more than once in the query is bound for every use.
+    ///
+    /// With `user_variables` (MySQL), an `@name` that no value is bound to is a user variable
+    /// and is left in the query.
+    Positional { user_variables: bool },
+}
+
+/// Finds the named bind parameters in a query.
+///
+/// Quoted strings and identifiers, comments, dollar-quoted strings, casts (`::type`) and
+/// the bounds of array slices (`arr[lower:upper]`) are skipped. Inside brackets, a `:` that
+/// follows `[`, an identifier, a number or `)` is the `:` of a slice.
+pub fn parse(query: &str) -> Vec<Param<'_>> {
+    let bytes = query.as_bytes();
+
+    let mut params = Vec::new();
+    let mut brackets = 0_usize;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ b'\'' | quote @ b'"' | quote @ b'`' => {
+                i = query[i + 1..]
+                    .find(quote as char)
+                    .map_or(query.len(), |end| i + 1 + end + 1);
+            }
+
+            b'-' if query[i..].starts_with("--") => {
+                i = query[i..].find('\n').map_or(query.len(), |end| i + end + 1);
+            }
+
+            b'/' if query[i..].starts_with("/*") => {
+                i = query[i + 2..]
+                    .find("*/")
+                    .map_or(query.len(), |end| i + 2 + end + 2);
+            }
+
+            // a dollar-quoted string, `$tag$ .. $tag$`
+            b'$' if i + 1 < bytes.len() && !bytes[i + 1].is_ascii_digit() => {
+                let tag_len = query[i + 1..]
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(query.len() - i - 1);
+
+                let tag_end = i + 1 + tag_len;
+
+                if bytes.get(tag_end) == Some(&b'$') {
+                    let tag = &query[i..=tag_end];
+
+                    i = query[tag_end + 1..]
+                        .find(tag)
+                        .map_or(query.len(), |end| tag_end + 1 + end + tag.len());
+                } else {
+                    i += 1;
+                }
+            }
+
+            // `::type` is a cast and `@@name` is a system variable (MySQL)
+            b':' | b'@' if bytes.get(i + 1) == Some(&bytes[i]) => {
+                i += 2;
+
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+            }
+
+            b'[' => {
+                brackets += 1;
+                i += 1;
+            }
+
+            b']' => {
+                brackets = brackets.saturating_sub(1);
+                i += 1;
+            }
+
+            // the `:` of an array slice (Postgres)
+            b':' if brackets > 0 && is_slice(&query[..i]) => i += 1,
+
+            b':' | b'@' if i + 1 < bytes.len() && is_ident_start(bytes[i + 1]) => {
+                let start = i;
+
+                i += 1;
+
+                while i < bytes.len() && is_ident(bytes[i]) {
+                    i += 1;
+                }
+
+                params.push(Param {
+                    range: start..i,
+                    name: &query[start + 1..i],
+                });
+            }
+
+            _ => i += 1,
+        }
+    }
+
+    params
+}
+
+/// Rewrites the named bind parameters of a query to positional bind parameters.
+///
+/// `names` are the names of the bound values, in the order they were bound. For
+/// [`Placeholder::Numbered`], the values are bound in that order. For
+/// [`Placeholder::Positional`], the returned indices are the index in `names` of the value
+/// for each placeholder of the rewritten query, in order.
+pub fn rewrite<N: AsRef<str>>(
+    query: &str,
+    names: &[N],
+    placeholder: Placeholder,
+) -> Result<(String, Vec<usize>), String> {
+    for (i, name) in names.iter().enumerate() {
+        if names[..i]
+            .iter()
+            .any(|other| other.as_ref() == name.as_ref())
+        {
+            return Err(format!(
+                "named parameter `{}` is bound more than once",
+                name.as_ref()
+            ));
+        }
+    }
+
+    let params = parse(query);
+
+    let mut rewritten = String::with_capacity(query.len());
+    let mut order = Vec::with_capacity(params.len());
+    let mut used = vec![false; names.len()];
+    let mut last = 0;
+
+    for param in &params {
+        let index = match names.iter().position(|name| name.as_ref() == param.name) {
+            Some(index) => index,
+
+            None if placeholder
+                == (Placeholder::Positional {
+                    user_variables: true,
+                })
+                && query[param.range.start..].starts_with('@') =>
+            {
+                continue;
+            }
+
+            None => {
+                return Err(format!(
+                    "no value is bound to named parameter `{}`",
+                    param.name
+                ));
+            }
+        };
+
+        rewritten.push_str(&query[last..param.range.start]);
+
+        match placeholder {
+            Placeholder::Numbered(prefix) => {
+                rewritten.push(prefix);
+                rewritten.push_str(&(index + 1).to_string());
+            }
+
+            Placeholder::Positional { .. } => rewritten.push('?'),
+        }
+
+        order.push(index);
+        used[index] = true;
+        last = param.range.end;
+    }
+
+    rewritten.push_str(&query[last..]);
+
+    if let Some(unused) = used.iter().position(|used| !used) {
+        return Err(format!(
+            "named parameter `{}` is not used in the query",
+            names[unused].as_ref()
+        ));
+    }
+
+    Ok((rewritten, order))
+}
+
+// Rewrites a query for the values bound to it if they are named, where `values` is the number
+// of values bound; `None` if the values are positional
+#[cfg(any(feature = "mysql", feature = "postgres"))]
+pub(crate) fn rewrite_bound(
+    query: &str,
+    names: &[Box<str>],
+    values: usize,
+    placeholder: Placeholder,
+) -> crate::Result<Option<(String, Vec<usize>)>> {
+    if names.is_empty() {
+        return Ok(None);
+    }
+
+    if names.len() != values {
+        return Err(arg_err!(
+            "named and positional values cannot be bound to the same query"
+        ));
+    }
+
+    rewrite(query, names, placeholder)
+        .map(Some)
+        .map_err(|err| arg_err!(err))
+}
+
+// Returns `true` if a `:` inside brackets after `before` separates the bounds of a slice
+fn is_slice(before: &str) -> bool {
+    match before.trim_end().as_bytes().last() {
+        Some(&b) => b == b'[' || b == b')' || is_ident(b),
+        None => false,
+    }
+}
+
+fn is_ident_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_'
+}
+
+fn is_ident(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_named_params() {
+        let query = "SELECT :a, @b_1, ':c', \"@d\", x::int4, @@version -- :e\n FROM t WHERE y = :a";
+        let names: Vec<_> = parse(query).into_iter().map(|param| param.name).collect();
+
+        assert_eq!(names, ["a", "b_1", "a"]);
+    }
+
+    #[test]
+    fn it_skips_dollar_quoted_strings() {
+        let query = "SELECT $tag$ :a $tag$, $1, :b";
+        let names: Vec<_> = parse(query).into_iter().map(|param| param.name).collect();
+
+        assert_eq!(names, ["b"]);
+    }
+
+    #[test]
+    fn it_skips_array_slices() {
+        let query = "SELECT arr[1:2], arr[:b], arr[a:b], arr[a : b], arr[f(a):b], arr[:c + 1] FROM t WHERE a = ANY(arr[:d])[2:3]";
+        let names: Vec<_> = parse(query).into_iter().map(|param| param.name).collect();
+
+        assert!(names.is_empty(), "{:?}", names);
+
+        let query = "SELECT arr[1 + :a] FROM t WHERE b = :b";
+        let names: Vec<_> = parse(query).into_iter().map(|param| param.name).collect();
+
+        assert_eq!(names, ["a", "b"]);
+    }
+
+    #[test]
+    fn it_leaves_unbound_user_variables() {
+        let query = "SELECT @total := @total + :a, @b";
+
+        let (rewritten, order) = rewrite(
+            query,
+            &["a", "b"],
+            Placeholder::Positional {
+                user_variables: true,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(rewritten, "SELECT @total := @total + ?, ?");
+        assert_eq!(order, [0, 1]);
+
+        assert!(rewrite(
+            query,
+            &["a", "b"],
+            Placeholder::Positional {
+                user_variables: false
+            }
+        )
+        .is_err());
+
+        assert!(rewrite(query, &["a", "b"], Placeholder::Numbered('$')).is_err());
+    }
+
+    #[test]
+    fn it_rewrites_to_numbered() {
+        let (query, order) = rewrite(
+            "SELECT * FROM t WHERE a = :a AND b = @b OR a > :a",
+            &["b", "a"],
+            Placeholder::Numbered('$'),
+        )
+        .unwrap();
+
+        assert_eq!(query, "SELECT * FROM t WHERE a = $2 AND b = $1 OR a > $2");
+        assert_eq!(order, [1, 0, 1]);
+    }
+
+    #[test]
+    fn it_rewrites_to_positional() {
+        let (query, order) = rewrite(
+            "SELECT * FROM t WHERE a = :a AND b = @b OR a > :a",
+            &["b", "a"],
+            Placeholder::Positional {
+                user_variables: false,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(query, "SELECT * FROM t WHERE a = ? AND b = ? OR a > ?");
+        assert_eq!(order, [1, 0, 1]);
+    }
+
+    #[test]
+    fn it_rejects_unbound_and_unused_names() {
+        let positional = Placeholder::Positional {
+            user_variables: false,
+        };
+
+        assert!(rewrite("SELECT :a", &["b"], positional).is_err());
+        assert!(rewrite("SELECT :a", &["a", "b"], positional).is_err());
+        assert!(rewrite("SELECT :a", &["a", "a"], positional).is_err());
+    }
+}
@@ -89,6 +89,11 @@ where
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(self.deref_mut().ping())
     }
+
+    #[inline]
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        Box::pin(self.deref_mut().clear_cached_statements())
+    }
 }
 
 /// Returns the connection to the [`Pool`][crate::pool::Pool] it was checked-out from.
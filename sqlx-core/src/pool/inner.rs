@@ -2,7 +2,7 @@ use std::cmp;
 use std::mem;
 use std::ptr;
 use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use crossbeam_queue::{ArrayQueue, SegQueue};
@@ -25,14 +25,18 @@ pub(crate) struct SharedPool<C> {
     waiters: SegQueue<Waker>,
     pub(super) size: AtomicU32,
     is_closed: AtomicBool,
-    options: Options,
+    options: Options<C>,
+    // a connection that is kept open, but never used, for as long as the pool is open if the
+    // database is destroyed when its last connection is closed; see `Connect::needs_keep_alive`
+    // as it is never handed out, `after_connect` is not run on it and it is not counted in `size`
+    keep_alive: Mutex<Option<C>>,
 }
 
 impl<C> SharedPool<C>
 where
     C: Connection,
 {
-    pub fn options(&self) -> &Options {
+    pub fn options(&self) -> &Options<C> {
         &self.options
     }
 
@@ -55,10 +59,18 @@ where
 
     pub(super) async fn close(&self) {
         self.is_closed.store(true, Ordering::Release);
-        while let Ok(_) = self.idle_conns.pop() {}
+        while let Ok(idle) = self.idle_conns.pop() {
+            let _ = Floating::from_idle(idle, self).close().await;
+        }
         while let Ok(waker) = self.waiters.pop() {
             waker.wake();
         }
+
+        let keep_alive = self.keep_alive.lock().unwrap().take();
+
+        if let Some(conn) = keep_alive {
+            let _ = conn.close().await;
+        }
     }
 
     #[inline]
@@ -132,7 +144,7 @@ impl<C> SharedPool<C>
 where
     C: Connect,
 {
-    pub(super) async fn new_arc(url: &str, options: Options) -> crate::Result<Arc<Self>> {
+    pub(super) async fn new_arc(url: &str, options: Options<C>) -> crate::Result<Arc<Self>> {
         let mut pool = Self {
             url: url.to_owned(),
             idle_conns: ArrayQueue::new(options.max_size as usize),
@@ -140,8 +152,19 @@ where
             size: AtomicU32::new(0),
             is_closed: AtomicBool::new(false),
             options,
+            keep_alive: Mutex::new(None),
         };
 
+        // opened first so the connections of the pool share its database; it is not one of the
+        // `max_size` connections of the pool
+        if C::needs_keep_alive(url) {
+            let conn = timeout(pool.options.connect_timeout, C::connect(url))
+                .await
+                .map_err(|e| crate::Error::PoolTimedOut(Some(Box::new(e))))??;
+
+            pool.keep_alive = Mutex::new(Some(conn));
+        }
+
         pool.init_min_connections().await?;
 
         let pool = Arc::new(pool);
@@ -215,8 +238,18 @@ where
 
         let timeout = super::deadline_as_timeout::<C::Database>(deadline)?;
 
+        let connect = async {
+            let mut raw = C::connect(&self.url).await?;
+
+            if let Some(after_connect) = &self.options.after_connect {
+                after_connect(&mut raw).await?;
+            }
+
+            Ok(raw)
+        };
+
         // result here is `Result<Result<C, Error>, TimeoutError>`
-        match crate::runtime::timeout(timeout, C::connect(&self.url)).await {
+        match crate::runtime::timeout(timeout, connect).await {
             // successfully established connection
             Ok(Ok(raw)) => Ok(Some(Floating::new_live(raw, guard))),
 
@@ -241,14 +274,14 @@ where
 
 // NOTE: Function names here are bizzare. Helpful help would be appreciated.
 
-fn is_beyond_lifetime<C>(live: &Live<C>, options: &Options) -> bool {
+fn is_beyond_lifetime<C>(live: &Live<C>, options: &Options<C>) -> bool {
     // check if connection was within max lifetime (or not set)
     options
         .max_lifetime
         .map_or(false, |max| live.created.elapsed() > max)
 }
 
-fn is_beyond_idle<C>(idle: &Idle<C>, options: &Options) -> bool {
+fn is_beyond_idle<C>(idle: &Idle<C>, options: &Options<C>) -> bool {
     // if connection wasn't idle too long (or not set)
     options
         .idle_timeout
@@ -257,7 +290,7 @@ fn is_beyond_idle<C>(idle: &Idle<C>, options: &Options) -> bool {
 
 async fn check_conn<'s: 'p, 'p, C>(
     mut conn: Floating<'s, Idle<C>>,
-    options: &'p Options,
+    options: &'p Options<C>,
 ) -> Option<Floating<'s, Live<C>>>
 where
     C: Connection,
@@ -39,7 +39,7 @@ where
         Self::builder().build(url).await
     }
 
-    async fn with_options(url: &str, options: Options) -> crate::Result<Self> {
+    async fn with_options(url: &str, options: Options<C>) -> crate::Result<Self> {
         let inner = SharedPool::<C>::new_arc(url, options).await?;
 
         Ok(Pool(inner))
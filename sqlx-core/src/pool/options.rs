@@ -1,13 +1,19 @@
-use std::{marker::PhantomData, time::Duration};
+use std::fmt;
+use std::time::Duration;
+
+use futures_core::future::BoxFuture;
 
 use super::Pool;
 use crate::connection::Connect;
 use crate::database::Database;
 
+// A callback run on each new connection of a pool
+type AfterConnect<C> =
+    dyn for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<()>> + Send + Sync + 'static;
+
 /// Builder for [Pool].
 pub struct Builder<C> {
-    phantom: PhantomData<C>,
-    options: Options,
+    options: Options<C>,
 }
 
 impl<C> Builder<C>
@@ -19,7 +25,6 @@ where
     /// See the source of this method for current defaults.
     pub(crate) fn new() -> Self {
         Self {
-            phantom: PhantomData,
             options: Options {
                 // pool a maximum of 10 connections to the same database
                 max_size: 10,
@@ -35,6 +40,7 @@ where
                 idle_timeout: None,
                 // If true, test the health of a connection on acquire
                 test_on_acquire: true,
+                after_connect: None,
             },
         }
     }
@@ -107,6 +113,35 @@ where
         self
     }
 
+    /// Set a callback that is run on every new connection before it is used, e.g. to set
+    /// session options or register functions.
+    ///
+    /// If the callback returns an error, the connection is closed and the error is returned
+    /// from [`Pool::acquire`].
+    ///
+    /// ```rust,ignore
+    /// let pool = SqlitePool::builder()
+    ///     .after_connect(|conn| {
+    ///         Box::pin(async move {
+    ///             conn.create_function("add_one", 1, true, |args| {
+    ///                 Ok(args.try_get::<i64>(0)? + 1)
+    ///             })
+    ///             .await
+    ///         })
+    ///     })
+    ///     .build("sqlite://data.db")
+    ///     .await?;
+    /// ```
+    pub fn after_connect<F>(mut self, callback: F) -> Self
+    where
+        F: for<'c> Fn(&'c mut C) -> BoxFuture<'c, crate::Result<()>> + Send + Sync + 'static,
+    {
+        let callback: Box<AfterConnect<C>> = Box::new(callback);
+
+        self.options.after_connect = Some(callback);
+        self
+    }
+
     /// Spin up the connection pool.
     ///
     /// If [`min_size`] was set to a non-zero value, that many connections will be immediately
@@ -131,12 +166,26 @@ where
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct Options {
+pub(crate) struct Options<C> {
     pub max_size: u32,
     pub connect_timeout: Duration,
     pub min_size: u32,
     pub max_lifetime: Option<Duration>,
     pub idle_timeout: Option<Duration>,
     pub test_on_acquire: bool,
+    pub after_connect: Option<Box<AfterConnect<C>>>,
+}
+
+impl<C> fmt::Debug for Options<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Options")
+            .field("max_size", &self.max_size)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("min_size", &self.min_size)
+            .field("max_lifetime", &self.max_lifetime)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("test_on_acquire", &self.test_on_acquire)
+            .field("after_connect", &self.after_connect.is_some())
+            .finish()
+    }
 }
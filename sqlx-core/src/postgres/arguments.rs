@@ -1,6 +1,8 @@
+use std::fmt::{self, Write};
+
 use byteorder::{ByteOrder, NetworkEndian};
 
-use crate::arguments::Arguments;
+use crate::arguments::{Arguments, NamedArguments};
 use crate::encode::{Encode, IsNull};
 use crate::io::BufMut;
 use crate::postgres::{PgRawBuffer, PgTypeInfo, Postgres};
@@ -13,6 +15,9 @@ pub struct PgArguments {
 
     // Write buffer for serializing bind values
     pub(super) buffer: PgRawBuffer,
+
+    // Names of the bind parameters, if they are named
+    pub(super) names: Vec<Box<str>>,
 }
 
 impl Arguments for PgArguments {
@@ -48,4 +53,18 @@ impl Arguments for PgArguments {
         // Write-back the len to the beginning of this frame (not including the len of len)
         NetworkEndian::write_i32(&mut self.buffer[pos..], len as i32);
     }
+
+    fn format_placeholder<W: Write>(&self, writer: &mut W) -> fmt::Result {
+        write!(writer, "${}", self.types.len() + 1)
+    }
+}
+
+impl NamedArguments for PgArguments {
+    fn add_named<T>(&mut self, name: &str, value: T)
+    where
+        T: Type<Self::Database> + Encode<Self::Database>,
+    {
+        self.names.push(name.into());
+        self.add(value);
+    }
 }
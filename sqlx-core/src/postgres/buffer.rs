@@ -1,7 +1,7 @@
 use crate::postgres::type_info::SharedStr;
 use crate::postgres::PgConnection;
 use byteorder::{ByteOrder, NetworkEndian};
-use core::ops::{Deref, DerefMut};
+use core::ops::{Deref, DerefMut, Range};
 
 #[derive(Debug, Default, PartialEq)]
 pub struct PgRawBuffer {
@@ -13,6 +13,10 @@ pub struct PgRawBuffer {
     // This is done for Records and Arrays as the OID is needed well before we are in an async
     // function and can just ask postgres
     type_holes: Vec<(usize, SharedStr)>,
+
+    // An `Encode` impl that cannot encode its value (e.g. a ragged multidimensional array)
+    // records an error here which is returned before the query is sent
+    error: Option<Box<str>>,
 }
 
 impl PgRawBuffer {
@@ -25,6 +29,35 @@ impl PgRawBuffer {
         self.type_holes.push((offset, type_name.clone()));
     }
 
+    // Records an error in encoding a value, returned as `Error::Argument`; only the first error
+    // is kept
+    pub(crate) fn set_error(&mut self, error: impl Into<Box<str>>) {
+        if self.error.is_none() {
+            self.error = Some(error.into());
+        }
+    }
+
+    pub(crate) fn take_error(&mut self) -> Option<crate::Error> {
+        self.error.take().map(crate::Error::Argument)
+    }
+
+    // Removes a range of bytes from the buffer, along with any type holes within it
+    // Type holes after the range are moved back to match
+    pub(crate) fn remove_range(&mut self, range: Range<usize>) {
+        let len = range.end - range.start;
+
+        self.inner.drain(range.clone());
+
+        self.type_holes
+            .retain(|(offset, _)| *offset < range.start || *offset >= range.end);
+
+        for (offset, _) in &mut self.type_holes {
+            if *offset >= range.end {
+                *offset -= len;
+            }
+        }
+    }
+
     // Patch all remembered type holes
     // This should only go out and ask postgres if we have not seen the type name yet
     pub(crate) async fn patch_type_holes(
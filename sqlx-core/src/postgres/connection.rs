@@ -6,6 +6,7 @@ use std::sync::Arc;
 use futures_core::future::BoxFuture;
 use futures_util::TryFutureExt;
 
+use crate::cache::StatementCache;
 use crate::connection::{Connect, Connection};
 use crate::executor::Executor;
 
@@ -85,7 +86,7 @@ pub struct PgConnection {
     pub(super) is_ready: bool,
 
     // cache query -> statement ID
-    pub(super) cache_statement_id: HashMap<Box<str>, StatementId>,
+    pub(super) cache_statement_id: StatementCache<StatementId>,
 
     // cache statement ID -> statement description
     pub(super) cache_statement: HashMap<StatementId, Arc<Statement>>,
@@ -96,6 +97,9 @@ pub struct PgConnection {
     // cache type OID -> type name
     pub(super) cache_type_name: HashMap<u32, SharedStr>,
 
+    // cache domain type OID -> base type OID
+    pub(super) cache_type_base: HashMap<u32, u32>,
+
     // Work buffer for the value ranges of the current row
     // This is used as the backing memory for each Row's value indexes
     pub(super) current_row_values: Vec<Option<(u32, u32)>>,
@@ -256,6 +260,8 @@ async fn terminate(mut stream: PgStream) -> crate::Result<()> {
 impl PgConnection {
     pub(super) async fn new(url: std::result::Result<Url, url::ParseError>) -> crate::Result<Self> {
         let url = url?;
+        let cache_statement_id = StatementCache::from_url(&url)?;
+
         let mut stream = PgStream::new(&url).await?;
 
         tls::request_if_needed(&mut stream, &url).await?;
@@ -268,7 +274,8 @@ impl PgConnection {
             is_ready: true,
             cache_type_oid: HashMap::new(),
             cache_type_name: HashMap::new(),
-            cache_statement_id: HashMap::with_capacity(10),
+            cache_type_base: HashMap::new(),
+            cache_statement_id,
             cache_statement: HashMap::with_capacity(10),
             process_id: key_data.process_id,
             secret_key: key_data.secret_key,
@@ -294,4 +301,8 @@ impl Connection for PgConnection {
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         Box::pin(Executor::execute(self, "SELECT 1").map_ok(|_| ()))
     }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        Box::pin(PgConnection::clear_cached_statements(self))
+    }
 }
@@ -9,9 +9,11 @@ use crate::arguments::Arguments;
 use crate::cursor::Cursor;
 use crate::describe::{Column, Describe};
 use crate::executor::{Execute, Executor, RefExecutor};
+use crate::named::{self, Placeholder};
+use crate::postgres::explain::{explain_nullability, is_explainable, Nullability};
 use crate::postgres::protocol::{
     self, CommandComplete, Message, ParameterDescription, ReadyForQuery, RowDescription,
-    StatementId, TypeFormat, TypeId,
+    StatementId, TransactionStatus, TypeFormat, TypeId,
 };
 use crate::postgres::row::Column as StatementColumn;
 use crate::postgres::row::Statement;
@@ -33,7 +35,7 @@ impl PgConnection {
         query: &str,
         args: &PgArguments,
     ) -> crate::Result<StatementId> {
-        if let Some(&id) = self.cache_statement_id.get(query) {
+        if let Some(&mut id) = self.cache_statement_id.get_mut(query) {
             Ok(id)
         } else {
             let id = StatementId(self.next_statement_id);
@@ -53,6 +55,18 @@ impl PgConnection {
                 });
             }
 
+            // The least recently used statement is closed to make room for this one
+            let closed = if self.cache_statement_id.is_full() {
+                self.cache_statement_id.remove_lru()
+            } else {
+                None
+            };
+
+            if let Some(closed) = closed {
+                self.cache_statement.remove(&closed);
+                self.stream.write(protocol::Close::Statement(closed));
+            }
+
             self.stream.write(protocol::Parse {
                 statement: id,
                 param_types: &*types,
@@ -68,11 +82,14 @@ impl PgConnection {
             self.stream.flush().await?;
             self.is_ready = false;
 
-            // wait for `ParseComplete`
-            match self.stream.receive().await? {
-                Message::ParseComplete => {}
-                message => {
-                    return Err(protocol_err!("run: unexpected message: {:?}", message).into());
+            // wait for `CloseComplete` if a statement was closed, then `ParseComplete`
+            loop {
+                match self.stream.receive().await? {
+                    Message::CloseComplete if closed.is_some() => {}
+                    Message::ParseComplete => break,
+                    message => {
+                        return Err(protocol_err!("run: unexpected message: {:?}", message).into());
+                    }
                 }
             }
 
@@ -83,13 +100,54 @@ impl PgConnection {
             let statement = self.expect_row_desc(pd).await?;
 
             // cache statement ID and statement description
-            self.cache_statement_id.insert(query.into(), id);
+            self.cache_statement_id.insert(query, id);
             self.cache_statement.insert(id, Arc::new(statement));
 
             Ok(id)
         }
     }
 
+    pub(super) async fn clear_cached_statements(&mut self) -> crate::Result<()> {
+        let statements = self.cache_statement_id.clear();
+
+        if statements.is_empty() {
+            return Ok(());
+        }
+
+        for id in statements {
+            self.cache_statement.remove(&id);
+            self.stream.write(protocol::Close::Statement(id));
+        }
+
+        self.write_sync();
+
+        self.wait_until_ready().await?;
+        self.stream.flush().await?;
+        self.is_ready = false;
+
+        // a `CloseComplete` for each statement, then `ReadyForQuery`
+        loop {
+            match self.stream.receive().await? {
+                Message::CloseComplete => {}
+
+                Message::ReadyForQuery => {
+                    self.is_ready = true;
+                    break;
+                }
+
+                message => {
+                    return Err(protocol_err!(
+                        "clear_cached_statements: unexpected message: {:?}",
+                        message
+                    )
+                    .into());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     async fn parse_parameter_description(
         &mut self,
         pd: ParameterDescription,
@@ -254,6 +312,20 @@ impl PgConnection {
         arguments: Option<PgArguments>,
     ) -> crate::Result<Option<StatementId>> {
         let statement = if let Some(mut arguments) = arguments {
+            // Named parameters are rewritten to `$N` for the order the values were bound in
+            let rewritten = named::rewrite_bound(
+                query,
+                &arguments.names,
+                arguments.types.len(),
+                Placeholder::Numbered('$'),
+            )?;
+
+            let query = rewritten.as_ref().map_or(query, |(query, _)| &**query);
+
+            if let Some(error) = arguments.buffer.take_error() {
+                return Err(error);
+            }
+
             // Check the statement cache for a statement ID that matches the given query
             // If it doesn't exist, we generate a new statement ID and write out [Parse] to the
             // connection command buffer
@@ -296,31 +368,137 @@ impl PgConnection {
         let statement = &self.cache_statement[&statement_id];
         let columns = statement.columns.to_vec();
 
+        let param_types = statement
+            .params
+            .iter()
+            .map(|info| Some(info.clone()))
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+
+        let mut result_columns = self.map_result_columns(columns).await?;
+
+        // only some statements can be explained (e.g. not `SHOW` or `FETCH`)
+        if !result_columns.is_empty() && is_explainable(query) {
+            self.explain_result_columns(statement_id, param_types.len(), &mut result_columns)
+                .await?;
+        }
+
         Ok(Describe {
-            param_types: statement
-                .params
-                .iter()
-                .map(|info| Some(info.clone()))
-                .collect::<Vec<_>>()
-                .into_boxed_slice(),
-            result_columns: self.map_result_columns(columns).await?.into_boxed_slice(),
+            param_types,
+            result_columns: result_columns.into_boxed_slice(),
         })
     }
 
+    // Refine the nullability of the result columns of a statement from its query plan
+    async fn explain_result_columns(
+        &mut self,
+        statement: StatementId,
+        params: usize,
+        columns: &mut [Column<Postgres>],
+    ) -> crate::Result<()> {
+        let mut explain = format!(
+            "EXPLAIN (VERBOSE, FORMAT JSON) EXECUTE __sqlx_statement_{}",
+            statement.0
+        );
+
+        // the values of the parameters are not known so `NULL` is given for each
+        if params > 0 {
+            explain += "(";
+            explain += &vec!["NULL"; params].join(", ");
+            explain += ")";
+        }
+
+        let plan = match self.stream.transaction_status {
+            TransactionStatus::Idle => self.explain(&explain).await,
+
+            // a failed EXPLAIN must not abort the transaction of the caller
+            TransactionStatus::Transaction => {
+                self.execute("SAVEPOINT _sqlx_explain").await?;
+
+                let plan = self.explain(&explain).await;
+
+                if plan.is_err() {
+                    self.execute("ROLLBACK TO SAVEPOINT _sqlx_explain").await?;
+                }
+
+                self.execute("RELEASE SAVEPOINT _sqlx_explain").await?;
+
+                plan
+            }
+
+            // no statement can run until the failed transaction is ended
+            TransactionStatus::Error => return Ok(()),
+        };
+
+        let plan = match plan {
+            Ok(Some(plan)) => plan,
+            Ok(None) => return Ok(()),
+
+            Err(crate::Error::Database(error)) => {
+                log::debug!("cannot explain statement: {}", error);
+
+                return Ok(());
+            }
+
+            Err(error) => return Err(error),
+        };
+
+        let nullability = explain_nullability(&plan)?;
+
+        // the plan may output more columns than the statement but never fewer
+        if nullability.len() < columns.len() {
+            return Ok(());
+        }
+
+        for (column, nullability) in columns.iter_mut().zip(nullability) {
+            match nullability {
+                Nullability::Nullable => column.non_null = Some(false),
+                Nullability::NonNull if column.non_null.is_none() => column.non_null = Some(true),
+
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn explain(&mut self, explain: &str) -> crate::Result<Option<String>> {
+        match self.fetch(explain).next().await? {
+            Some(row) => Ok(Some(row.try_get_unchecked::<String, _>(0)?)),
+            None => Ok(None),
+        }
+    }
+
     pub(crate) async fn get_type_id_by_name(&mut self, name: &str) -> crate::Result<u32> {
         if let Some(oid) = self.cache_type_oid.get(name) {
             return Ok(*oid);
         }
 
-        // language=SQL
-        let (oid,): (u32,) = query_as(
-            "
+        let (oid,): (u32,) = if name.ends_with("[]") {
+            // arrays are named after their element type (`mood[]`); the array type itself is
+            // implicitly created by Postgres so we ask for it through the element type
+            let element_name = &name[..name.len() - 2];
+
+            // language=SQL
+            query_as(
+                "
+SELECT typarray FROM pg_catalog.pg_type WHERE typname ILIKE $1 AND typarray <> 0
+                    ",
+            )
+            .bind(element_name)
+            .fetch_one(&mut *self)
+            .await?
+        } else {
+            // language=SQL
+            query_as(
+                "
 SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
-                ",
-        )
-        .bind(name)
-        .fetch_one(&mut *self)
-        .await?;
+                    ",
+            )
+            .bind(name)
+            .fetch_one(&mut *self)
+            .await?
+        };
 
         let shared = SharedStr::from(name.to_owned());
 
@@ -335,6 +513,9 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
         oid: u32,
         fetch_type_info: bool,
     ) -> crate::Result<PgTypeInfo> {
+        // a domain is sent and received as its base type
+        let oid = self.cache_type_base.get(&oid).copied().unwrap_or(oid);
+
         if let Some(name) = try_resolve_type_name(oid) {
             return Ok(PgTypeInfo::new(TypeId(oid), name));
         }
@@ -343,27 +524,56 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
             return Ok(PgTypeInfo::new(TypeId(oid), name));
         }
 
-        let name = if fetch_type_info {
+        let (oid, name) = if fetch_type_info {
+            // arrays of user-defined types are named after their element type (`MOOD[]`)
+            // to match the names used for the built-in array types
+
+            // domains (and arrays of domains) are reported as their base type
+
             // language=SQL
-            let (name,): (String,) = query_as(
+            let (base_oid, name): (u32, String) = query_as(
                 "
-    SELECT UPPER(typname) FROM pg_catalog.pg_type WHERE oid = $1
+    SELECT
+        CASE
+            WHEN element.typtype = 'd' THEN base_element.typarray
+            WHEN ty.typtype = 'd' THEN ty.typbasetype
+            ELSE ty.oid
+        END,
+        UPPER(CASE
+            WHEN element.typtype = 'd' THEN base_element.typname || '[]'
+            WHEN element.oid IS NOT NULL THEN element.typname || '[]'
+            WHEN ty.typtype = 'd' THEN base.typname
+            ELSE ty.typname
+        END)
+    FROM pg_catalog.pg_type ty
+    LEFT JOIN pg_catalog.pg_type element ON element.typarray = ty.oid
+    LEFT JOIN pg_catalog.pg_type base_element ON base_element.oid = element.typbasetype
+    LEFT JOIN pg_catalog.pg_type base ON base.oid = ty.typbasetype
+    WHERE ty.oid = $1
                     ",
             )
             .bind(oid)
             .fetch_one(&mut *self)
             .await?;
 
+            if base_oid != oid {
+                self.cache_type_base.insert(oid, base_oid);
+            }
+
+            if let Some(name) = try_resolve_type_name(base_oid) {
+                return Ok(PgTypeInfo::new(TypeId(base_oid), name));
+            }
+
             // Emplace the new type name <-> OID association in the cache
             let shared = SharedStr::from(name);
 
-            self.cache_type_oid.insert(shared.clone(), oid);
-            self.cache_type_name.insert(oid, shared.clone());
+            self.cache_type_oid.insert(shared.clone(), base_oid);
+            self.cache_type_name.insert(base_oid, shared.clone());
 
-            shared
+            (base_oid, shared)
         } else {
             // NOTE: The name isn't too important for the decode lifecycle of TEXT
-            SharedStr::Static("")
+            (oid, SharedStr::Static(""))
         };
 
         Ok(PgTypeInfo::new(TypeId(oid), name))
@@ -0,0 +1,421 @@
+//! Nullability of the output columns of a statement as inferred from its query plan.
+//!
+//! `RowDescription` only tells us where a column comes from if it is a column of a table and
+//! the catalog then tells us if that column is `NOT NULL`. This is wrong for a column from the
+//! nullable side of an outer join and unknown for any expression. The query plan given by
+//! `EXPLAIN (VERBOSE, FORMAT JSON)` lists the expressions of the output columns and the joins
+//! they go through which fills in some of those gaps.
+
+use std::collections::HashSet;
+
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq)]
+pub(super) enum Nullability {
+    // The expression of the column can never be NULL
+    NonNull,
+
+    // The column comes from the nullable side of an outer join
+    Nullable,
+
+    // Nothing more is known about the column
+    Unknown,
+}
+
+#[derive(Deserialize)]
+struct Explain {
+    #[serde(rename = "Plan")]
+    plan: Plan,
+}
+
+#[derive(Deserialize)]
+struct Plan {
+    #[serde(rename = "Node Type")]
+    node_type: String,
+
+    #[serde(rename = "Join Type")]
+    join_type: Option<String>,
+
+    #[serde(rename = "Parent Relationship")]
+    parent_relationship: Option<String>,
+
+    #[serde(rename = "Output")]
+    output: Option<Vec<String>>,
+
+    #[serde(rename = "Plans")]
+    plans: Option<Vec<Plan>>,
+}
+
+/// Infers the nullability of each output column from the output of
+/// `EXPLAIN (VERBOSE, FORMAT JSON)`.
+///
+/// The plan may output more columns than the statement (such as the keys of an `ORDER BY`)
+/// which are listed after the columns of the statement.
+pub(super) fn explain_nullability(explain: &str) -> crate::Result<Vec<Nullability>> {
+    let explain: Vec<Explain> = match serde_json::from_str(explain) {
+        Ok(explain) => explain,
+        Err(err) => return Err(protocol_err!("unexpected output from EXPLAIN: {}", err).into()),
+    };
+
+    let plan = match explain.first() {
+        Some(explain) => &explain.plan,
+        None => return Ok(Vec::new()),
+    };
+
+    let mut nullable = HashSet::new();
+
+    visit_outer_joins(plan, &mut nullable);
+
+    // the output of a set operation, subquery or CTE is deparsed as the expression of its
+    // first branch (e.g. `(1)` for `SELECT 1 UNION SELECT NULL`), which says nothing about
+    // the other branches
+    let is_derived = has_derived_output(plan);
+
+    Ok(plan
+        .output
+        .iter()
+        .flatten()
+        .map(|output| {
+            if nullable.contains(output.as_str()) {
+                Nullability::Nullable
+            } else if !is_derived && is_non_null(output) {
+                Nullability::NonNull
+            } else {
+                Nullability::Unknown
+            }
+        })
+        .collect())
+}
+
+// Collects the outputs of every plan on the nullable side of an outer join
+fn visit_outer_joins<'a>(plan: &'a Plan, nullable: &mut HashSet<&'a str>) {
+    for child in plan.plans.iter().flatten() {
+        let is_nullable_side = match (
+            plan.join_type.as_deref(),
+            child.parent_relationship.as_deref(),
+        ) {
+            (Some("Full"), _) => true,
+            (Some("Left"), Some("Inner")) | (Some("Right"), Some("Outer")) => true,
+
+            _ => false,
+        };
+
+        if is_nullable_side {
+            nullable.extend(child.output.iter().flatten().map(String::as_str));
+        }
+
+        visit_outer_joins(child, nullable);
+    }
+}
+
+// Whether a plan combines or reads the rows of other statements, which are then output as if
+// they were the rows of one of them
+fn has_derived_output(plan: &Plan) -> bool {
+    match &*plan.node_type {
+        "Append" | "Merge Append" | "SetOp" | "Recursive Union" | "Subquery Scan" | "CTE Scan"
+        | "WorkTable Scan" => true,
+
+        _ => plan.plans.iter().flatten().any(has_derived_output),
+    }
+}
+
+/// Whether `EXPLAIN` can be run on a statement, by its first keyword.
+pub(super) fn is_explainable(query: &str) -> bool {
+    let mut query = query;
+
+    // skip whitespace, comments and the parentheses around a statement
+    loop {
+        query = query.trim_start_matches(|c: char| c.is_whitespace() || c == '(');
+
+        if query.starts_with("--") {
+            query = query.find('\n').map_or("", |end| &query[end..]);
+        } else if query.starts_with("/*") {
+            query = query.find("*/").map_or("", |end| &query[(end + 2)..]);
+        } else {
+            break;
+        }
+    }
+
+    let keyword = query
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .unwrap_or_default();
+
+    ["SELECT", "INSERT", "UPDATE", "DELETE", "VALUES", "WITH"]
+        .iter()
+        .any(|explainable| explainable.eq_ignore_ascii_case(keyword))
+}
+
+// Whether an expression, as deparsed by Postgres, can never be NULL
+fn is_non_null(expr: &str) -> bool {
+    let expr = strip_parens(expr.trim());
+
+    if let Some(expr) = strip_cast(expr) {
+        return is_non_null(expr);
+    }
+
+    // `count(..)` is never NULL, not even over no rows or as a window function
+    if let Some((_, rest)) = call(expr, "count") {
+        return rest.is_empty() || rest.starts_with(" OVER ") || rest.starts_with(" FILTER ");
+    }
+
+    if let Some((args, rest)) = call(expr, "COALESCE") {
+        return rest.is_empty() && split_top_level(args, ',').into_iter().any(is_non_null);
+    }
+
+    is_literal(expr)
+}
+
+fn is_literal(expr: &str) -> bool {
+    if expr == "true" || expr == "false" {
+        return true;
+    }
+
+    let digits = expr.trim_start_matches('-');
+
+    if digits.starts_with(|c: char| c.is_ascii_digit()) {
+        return digits.parse::<f64>().is_ok();
+    }
+
+    // a string constant, which is `'...'` with quotes doubled or `E'...'` with escapes
+    let string = expr.trim_start_matches('E');
+
+    string.starts_with('\'') && top_level(string).is_empty() && string.ends_with('\'')
+}
+
+// Strips the parentheses around a whole expression
+fn strip_parens(mut expr: &str) -> &str {
+    while expr.starts_with('(') && closing_paren(expr) == Some(expr.len() - 1) {
+        expr = expr[1..expr.len() - 1].trim();
+    }
+
+    expr
+}
+
+// Strips a cast (`expr::type`) from an expression
+fn strip_cast(expr: &str) -> Option<&str> {
+    let top_level = top_level(expr);
+
+    let cast = top_level
+        .windows(2)
+        .rev()
+        .find(|w| w[0] == (w[1].0 - 1, ':') && w[1].1 == ':')?[0]
+        .0;
+
+    // anything but a type name after the cast is an operator applied to the cast
+    let is_type = top_level
+        .iter()
+        .filter(|&&(i, _)| i > cast + 1)
+        .all(|&(_, c)| c.is_alphanumeric() || " _.\"[]".contains(c));
+
+    if is_type {
+        Some(expr[..cast].trim_end())
+    } else {
+        None
+    }
+}
+
+// Splits a call of the function `name` into its arguments and whatever follows the call
+fn call<'a>(expr: &'a str, name: &str) -> Option<(&'a str, &'a str)> {
+    if !expr.starts_with(name) || !expr[name.len()..].starts_with('(') {
+        return None;
+    }
+
+    let close = name.len() + closing_paren(&expr[name.len()..])?;
+
+    Some((&expr[(name.len() + 1)..close], &expr[(close + 1)..]))
+}
+
+// The index of the parenthesis closing the one that `expr` starts with
+fn closing_paren(expr: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+
+    for (i, c) in expr.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+
+            (None, ')') => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+
+            (None, _) => {}
+        }
+    }
+
+    None
+}
+
+fn split_top_level(expr: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+
+    for (i, c) in top_level(expr) {
+        if c == separator {
+            parts.push(&expr[start..i]);
+            start = i + c.len_utf8();
+        }
+    }
+
+    parts.push(&expr[start..]);
+    parts
+}
+
+// The characters of an expression that are not within parentheses, brackets or quotes
+fn top_level(expr: &str) -> Vec<(usize, char)> {
+    let mut chars = Vec::new();
+    let mut depth = 0_usize;
+    let mut quote = None;
+
+    for (i, c) in expr.char_indices() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), _) => {}
+
+            (None, '\'') | (None, '"') => quote = Some(c),
+            (None, '(') | (None, '[') => depth += 1,
+            (None, ')') | (None, ']') => depth = depth.saturating_sub(1),
+
+            (None, _) if depth == 0 => chars.push((i, c)),
+            (None, _) => {}
+        }
+    }
+
+    chars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{explain_nullability, is_explainable, is_non_null, Nullability};
+
+    #[test]
+    fn it_infers_non_null_expressions() {
+        assert!(is_non_null("1"));
+        assert!(is_non_null("(1)"));
+        assert!(is_non_null("1.5"));
+        assert!(is_non_null("true"));
+        assert!(is_non_null("'x'::text"));
+        assert!(is_non_null("'it''s'::character varying(10)"));
+        assert!(is_non_null("'-1'::integer"));
+        assert!(is_non_null("count(*)"));
+        assert!(is_non_null("count(a.id) OVER (?)"));
+        assert!(is_non_null("(count(*))::integer"));
+        assert!(is_non_null("COALESCE(b.score, '0'::double precision)"));
+        assert!(is_non_null("COALESCE(b.name, c.name, 'none'::text)"));
+
+        assert!(!is_non_null("NULL::integer"));
+        assert!(!is_non_null("a.name"));
+        assert!(!is_non_null("max(a.id)"));
+        assert!(!is_non_null("(count(*) + a.x)"));
+        assert!(!is_non_null("COALESCE(b.name, c.name)"));
+        assert!(!is_non_null("COALESCE(b.name, 'x'::text) || c.name"));
+        assert!(!is_non_null("'x'::text || a.name"));
+    }
+
+    #[test]
+    fn it_infers_outer_join_nullability() {
+        let explain = r#"[{"Plan": {
+            "Node Type": "Hash Join",
+            "Join Type": "Left",
+            "Output": ["a.id", "b.name", "count(*) OVER (?)", "NULL::integer", "a.name"],
+            "Plans": [
+                {
+                    "Node Type": "Seq Scan",
+                    "Parent Relationship": "Outer",
+                    "Output": ["a.id", "a.name"]
+                },
+                {
+                    "Node Type": "Hash",
+                    "Parent Relationship": "Inner",
+                    "Output": ["b.name", "b.id"],
+                    "Plans": [{
+                        "Node Type": "Seq Scan",
+                        "Parent Relationship": "Outer",
+                        "Output": ["b.name", "b.id"]
+                    }]
+                }
+            ]
+        }}]"#;
+
+        assert_eq!(
+            explain_nullability(explain).unwrap(),
+            vec![
+                Nullability::Unknown,
+                Nullability::Nullable,
+                Nullability::NonNull,
+                Nullability::Unknown,
+                Nullability::Unknown,
+            ]
+        );
+    }
+
+    #[test]
+    fn it_does_not_infer_the_output_of_set_operations() {
+        // SELECT 1 AS x UNION SELECT NULL::int
+        let explain = r#"[{"Plan": {
+            "Node Type": "Unique",
+            "Output": ["(1)"],
+            "Plans": [{
+                "Node Type": "Sort",
+                "Parent Relationship": "Outer",
+                "Output": ["(1)"],
+                "Plans": [{
+                    "Node Type": "Append",
+                    "Parent Relationship": "Outer",
+                    "Plans": [
+                        {"Node Type": "Result", "Parent Relationship": "Member", "Output": ["1"]},
+                        {"Node Type": "Result", "Parent Relationship": "Member", "Output": ["NULL::integer"]}
+                    ]
+                }]
+            }]
+        }}]"#;
+
+        assert_eq!(
+            explain_nullability(explain).unwrap(),
+            vec![Nullability::Unknown]
+        );
+
+        // WITH t AS MATERIALIZED (SELECT 1 AS x) SELECT x, 1 FROM t
+        let explain = r#"[{"Plan": {
+            "Node Type": "CTE Scan",
+            "Output": ["t.x", "1"],
+            "Plans": [{
+                "Node Type": "Result",
+                "Parent Relationship": "InitPlan",
+                "Output": ["1"]
+            }]
+        }}]"#;
+
+        assert_eq!(
+            explain_nullability(explain).unwrap(),
+            vec![Nullability::Unknown, Nullability::Unknown]
+        );
+    }
+
+    #[test]
+    fn it_explains_only_some_statements() {
+        assert!(is_explainable("SELECT 1"));
+        assert!(is_explainable("  select 1"));
+        assert!(is_explainable("(SELECT 1) UNION (SELECT 2)"));
+        assert!(is_explainable(
+            "-- comment\nWITH t AS (SELECT 1) SELECT * FROM t"
+        ));
+        assert!(is_explainable(
+            "/* comment */ INSERT INTO t VALUES (1) RETURNING id"
+        ));
+        assert!(is_explainable("VALUES (1), (2)"));
+
+        assert!(!is_explainable("SHOW search_path"));
+        assert!(!is_explainable("FETCH ALL FROM c"));
+        assert!(!is_explainable("EXPLAIN SELECT 1"));
+        assert!(!is_explainable("-- SELECT"));
+    }
+}
@@ -18,6 +18,7 @@ mod cursor;
 mod database;
 mod error;
 mod executor;
+mod explain;
 mod listen;
 mod protocol;
 mod row;
@@ -34,4 +35,5 @@ pub type PgPool = crate::pool::Pool<PgConnection>;
 
 make_query_as!(PgQueryAs, Postgres, PgRow);
 impl_map_row_for_row!(Postgres, PgRow);
+impl_map_scalar_for_row!(Postgres, PgRow);
 impl_from_row_for_tuples!(Postgres, PgRow);
@@ -0,0 +1,49 @@
+use crate::io::BufMut;
+use crate::postgres::protocol::{StatementId, Write};
+use byteorder::{ByteOrder, NetworkEndian};
+
+pub enum Close<'a> {
+    Statement(StatementId),
+    Portal(&'a str),
+}
+
+impl Write for Close<'_> {
+    fn write(&self, buf: &mut Vec<u8>) {
+        buf.push(b'C');
+
+        let pos = buf.len();
+        buf.put_i32::<NetworkEndian>(0); // skip over len
+
+        match self {
+            Close::Statement(id) => {
+                buf.push(b'S');
+                id.write(buf);
+            }
+
+            Close::Portal(name) => {
+                buf.push(b'P');
+                buf.put_str_nul(name);
+            }
+        };
+
+        // Write-back the len to the beginning of this frame
+        let len = buf.len() - pos;
+        NetworkEndian::write_i32(&mut buf[pos..], len as i32);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Close, Write};
+    use crate::postgres::protocol::StatementId;
+
+    #[test]
+    fn it_writes_close_statement() {
+        let mut buf = Vec::new();
+        let m = Close::Statement(StatementId(1));
+
+        m.write(&mut buf);
+
+        assert_eq!(buf, b"C\x00\x00\x00\x18S__sqlx_statement_1\x00");
+    }
+}
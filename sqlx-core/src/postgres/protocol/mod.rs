@@ -10,6 +10,7 @@ pub use type_id::TypeId;
 
 // REQUESTS
 mod bind;
+mod close;
 mod describe;
 mod execute;
 mod parse;
@@ -24,6 +25,7 @@ mod sync;
 mod terminate;
 
 pub(crate) use bind::Bind;
+pub(crate) use close::Close;
 pub(crate) use describe::Describe;
 pub(crate) use execute::Execute;
 pub(crate) use parse::Parse;
@@ -59,7 +61,7 @@ pub(crate) use data_row::DataRow;
 pub(crate) use message::Message;
 pub(crate) use notification_response::NotificationResponse;
 pub(crate) use parameter_description::ParameterDescription;
-pub(crate) use ready_for_query::ReadyForQuery;
+pub(crate) use ready_for_query::{ReadyForQuery, TransactionStatus};
 pub(crate) use response::{Response, Severity};
 pub(crate) use row_description::{Field, RowDescription};
 
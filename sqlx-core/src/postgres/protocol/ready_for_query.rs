@@ -1,6 +1,6 @@
 use crate::postgres::database::Postgres;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum TransactionStatus {
     /// Not in a transaction block.
@@ -16,7 +16,7 @@ pub enum TransactionStatus {
 /// `ReadyForQuery` is sent whenever the database is ready for a new query cycle.
 #[derive(Debug)]
 pub struct ReadyForQuery {
-    status: TransactionStatus,
+    pub(crate) status: TransactionStatus,
 }
 
 impl ReadyForQuery {
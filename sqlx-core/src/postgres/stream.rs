@@ -5,7 +5,9 @@ use byteorder::NetworkEndian;
 use futures_channel::mpsc::UnboundedSender;
 
 use crate::io::{Buf, BufStream, MaybeTlsStream};
-use crate::postgres::protocol::{Message, NotificationResponse, Response, Write};
+use crate::postgres::protocol::{
+    Message, NotificationResponse, ReadyForQuery, Response, TransactionStatus, Write,
+};
 use crate::postgres::PgError;
 
 use crate::url::Url;
@@ -19,6 +21,9 @@ pub struct PgStream {
     // Is referenced by our buffered stream
     // Is initialized to ReadyForQuery/0 at the start
     pub(super) message: (Message, u32),
+
+    // Transaction status of the most recently received ReadyForQuery
+    pub(super) transaction_status: TransactionStatus,
 }
 
 impl PgStream {
@@ -49,6 +54,7 @@ impl PgStream {
             notifications: None,
             stream: BufStream::new(stream),
             message: (Message::ReadyForQuery, 0),
+            transaction_status: TransactionStatus::Idle,
         })
     }
 
@@ -124,6 +130,10 @@ impl PgStream {
                     }
                 }
 
+                Message::ReadyForQuery => {
+                    self.transaction_status = ReadyForQuery::read(self.stream.buffer())?.status;
+                }
+
                 _ => {}
             }
 
@@ -26,6 +26,9 @@ impl PgTypeInfo {
     ///
     /// The OID for the type will be fetched from Postgres on bind or decode of
     /// a value of this type. The fetched OID will be cached per-connection.
+    ///
+    /// An array of a user-defined type may be named by suffixing the name of its element
+    /// type with `[]` (e.g., `mood[]`).
     pub const fn with_name(name: &'static str) -> Self {
         Self {
             id: None,
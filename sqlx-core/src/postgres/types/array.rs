@@ -1,19 +1,19 @@
 //! Encoding and decoding of Postgres arrays.
 
-use crate::database::Database;
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::types::raw::{PgArrayDecoder, PgArrayEncoder};
-use crate::postgres::{PgRawBuffer, PgValue, Postgres};
+use crate::postgres::{PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 
 impl<T> Encode<Postgres> for [T]
 where
     T: Encode<Postgres>,
-    T: Type<Postgres>,
+    T: PgHasArrayType,
 {
     fn encode(&self, buf: &mut PgRawBuffer) {
-        let mut encoder = PgArrayEncoder::new(buf);
+        let mut encoder =
+            PgArrayEncoder::nested(buf, &T::array_element_type_info(), T::DIMENSIONS + 1);
 
         for item in self {
             encoder.encode(item);
@@ -26,7 +26,7 @@ where
 impl<T> Encode<Postgres> for Vec<T>
 where
     T: Encode<Postgres>,
-    T: Type<Postgres>,
+    T: PgHasArrayType,
 {
     fn encode(&self, buf: &mut PgRawBuffer) {
         self.as_slice().encode(buf)
@@ -37,32 +37,107 @@ impl<'de, T> Decode<'de, Postgres> for Vec<T>
 where
     T: 'de,
     T: for<'arr> Decode<'arr, Postgres>,
-    [T]: Type<Postgres>,
-    T: Type<Postgres>,
+    T: PgHasArrayType,
 {
     fn decode(value: PgValue<'de>) -> crate::Result<Self> {
-        PgArrayDecoder::<T>::new(value)?.collect()
+        let mut decoder = PgArrayDecoder::<T>::new(value)?;
+        let dimensions = decoder.dimensions();
+
+        if !dimensions.is_empty() && dimensions.len() != T::DIMENSIONS + 1 {
+            return Err(decode_err!(
+                "encountered an array of {} dimensions; expected {} dimensions (use `PgArray` for arrays of any dimensions)",
+                dimensions.len(),
+                T::DIMENSIONS + 1
+            ));
+        }
+
+        if let Some(dimension) = dimensions.iter().find(|d| d.lower_bound != 1) {
+            return Err(decode_err!(
+                "encountered an array with a lower bound of {}; only arrays starting at one are supported (use `PgArray` for arrays with other bounds)",
+                dimension.lower_bound
+            ));
+        }
+
+        if T::DIMENSIONS == 0 {
+            decoder.collect()
+        } else {
+            std::iter::from_fn(|| decoder.decode_sub_array().transpose()).collect()
+        }
+    }
+}
+
+/// Provides the SQL type information of a Postgres array whose elements are of this type.
+///
+/// Postgres arrays are typed by their element type; implementing this trait makes `[T]`,
+/// `Vec<T>` and `Vec<Option<T>>` usable wherever `T` is. `#[derive(sqlx::Type)]` implements
+/// this for user-defined composites.
+///
+/// `Vec<T>` implements this trait as well so that `Vec<Vec<T>>` is a two-dimensional array.
+pub trait PgHasArrayType: Type<Postgres> {
+    fn array_type_info() -> PgTypeInfo;
+
+    // The number of array dimensions of this type; an array of `Vec<T>` is sent as one
+    // array of a dimension more than `Vec<T>`
+    #[doc(hidden)]
+    const DIMENSIONS: usize = 0;
+
+    // The type of the elements of an array of this type once flattened
+    #[doc(hidden)]
+    fn array_element_type_info() -> PgTypeInfo {
+        Self::type_info()
+    }
+}
+
+impl<T> PgHasArrayType for Option<T>
+where
+    T: PgHasArrayType,
+{
+    #[inline]
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    const DIMENSIONS: usize = T::DIMENSIONS;
+
+    #[inline]
+    fn array_element_type_info() -> PgTypeInfo {
+        T::array_element_type_info()
+    }
+}
+
+impl<T> PgHasArrayType for Vec<T>
+where
+    T: PgHasArrayType,
+{
+    #[inline]
+    fn array_type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+
+    const DIMENSIONS: usize = T::DIMENSIONS + 1;
+
+    #[inline]
+    fn array_element_type_info() -> PgTypeInfo {
+        T::array_element_type_info()
     }
 }
 
-impl<T, DB> Type<DB> for Vec<Option<T>>
+impl<T> Type<Postgres> for [T]
 where
-    DB: Database,
-    [T]: Type<DB>,
+    T: PgHasArrayType,
 {
     #[inline]
-    fn type_info() -> DB::TypeInfo {
-        <[T] as Type<DB>>::type_info()
+    fn type_info() -> PgTypeInfo {
+        T::array_type_info()
     }
 }
 
-impl<T, DB> Type<DB> for [Option<T>]
+impl<T> Type<Postgres> for Vec<T>
 where
-    DB: Database,
-    [T]: Type<DB>,
+    T: PgHasArrayType,
 {
     #[inline]
-    fn type_info() -> DB::TypeInfo {
-        <[T] as Type<DB>>::type_info()
+    fn type_info() -> PgTypeInfo {
+        T::array_type_info()
     }
 }
@@ -7,6 +7,7 @@ use num_bigint::{BigInt, Sign};
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 
@@ -18,19 +19,13 @@ impl Type<Postgres> for BigDecimal {
     }
 }
 
-impl Type<Postgres> for [BigDecimal] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for BigDecimal {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_NUMERIC, "NUMERIC[]")
         // <[PgNumeric] as Type<Postgres>>::type_info()
     }
 }
 
-impl Type<Postgres> for Vec<BigDecimal> {
-    fn type_info() -> PgTypeInfo {
-        <[BigDecimal] as Type<Postgres>>::type_info()
-    }
-}
-
 impl TryFrom<BigDecimal> for PgNumeric {
     type Error = std::num::TryFromIntError;
 
@@ -1,6 +1,7 @@
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 
@@ -10,16 +11,11 @@ impl Type<Postgres> for bool {
     }
 }
 
-impl Type<Postgres> for [bool] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for bool {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_BOOL, "BOOL[]")
     }
 }
-impl Type<Postgres> for Vec<bool> {
-    fn type_info() -> PgTypeInfo {
-        <[bool] as Type<Postgres>>::type_info()
-    }
-}
 
 impl Encode<Postgres> for bool {
     fn encode(&self, buf: &mut PgRawBuffer) {
@@ -1,6 +1,7 @@
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 
@@ -10,21 +11,21 @@ impl Type<Postgres> for [u8] {
     }
 }
 
-impl Type<Postgres> for [&'_ [u8]] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for &'_ [u8] {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_BYTEA, "BYTEA[]")
     }
 }
 
-impl Type<Postgres> for Vec<&'_ [u8]> {
+impl Type<Postgres> for Vec<u8> {
     fn type_info() -> PgTypeInfo {
-        <&'_ [u8] as Type<Postgres>>::type_info()
+        <[u8] as Type<Postgres>>::type_info()
     }
 }
 
-impl Type<Postgres> for Vec<u8> {
-    fn type_info() -> PgTypeInfo {
-        <[u8] as Type<Postgres>>::type_info()
+impl PgHasArrayType for Vec<u8> {
+    fn array_type_info() -> PgTypeInfo {
+        <&[u8] as PgHasArrayType>::array_type_info()
     }
 }
 
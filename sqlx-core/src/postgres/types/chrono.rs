@@ -7,6 +7,7 @@ use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Tim
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 use crate::Error;
@@ -38,57 +39,30 @@ where
     }
 }
 
-impl Type<Postgres> for [NaiveTime] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for NaiveTime {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_TIME, "TIME[]")
     }
 }
 
-impl Type<Postgres> for [NaiveDate] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for NaiveDate {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_DATE, "DATE[]")
     }
 }
 
-impl Type<Postgres> for [NaiveDateTime] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for NaiveDateTime {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_TIMESTAMP, "TIMESTAMP[]")
     }
 }
 
-impl<Tz> Type<Postgres> for [DateTime<Tz>]
+impl<Tz> PgHasArrayType for DateTime<Tz>
 where
     Tz: TimeZone,
 {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::new(TypeId::ARRAY_TIMESTAMPTZ, "TIMESTAMP[]")
-    }
-}
-
-impl Type<Postgres> for Vec<NaiveTime> {
-    fn type_info() -> PgTypeInfo {
-        <[NaiveTime] as Type<Postgres>>::type_info()
-    }
-}
-
-impl Type<Postgres> for Vec<NaiveDate> {
-    fn type_info() -> PgTypeInfo {
-        <[NaiveDate] as Type<Postgres>>::type_info()
-    }
-}
-
-impl Type<Postgres> for Vec<NaiveDateTime> {
-    fn type_info() -> PgTypeInfo {
-        <[NaiveDateTime] as Type<Postgres>>::type_info()
-    }
-}
-
-impl<Tz> Type<Postgres> for Vec<DateTime<Tz>>
-where
-    Tz: TimeZone,
-{
-    fn type_info() -> PgTypeInfo {
-        <[NaiveDateTime] as Type<Postgres>>::type_info()
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::new(TypeId::ARRAY_TIMESTAMPTZ, "TIMESTAMPTZ[]")
     }
 }
 
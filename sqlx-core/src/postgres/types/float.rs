@@ -6,6 +6,7 @@ use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::error::Error;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 
@@ -15,16 +16,11 @@ impl Type<Postgres> for f32 {
     }
 }
 
-impl Type<Postgres> for [f32] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for f32 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_FLOAT4, "FLOAT4[]")
     }
 }
-impl Type<Postgres> for Vec<f32> {
-    fn type_info() -> PgTypeInfo {
-        <[f32] as Type<Postgres>>::type_info()
-    }
-}
 
 impl Encode<Postgres> for f32 {
     fn encode(&self, buf: &mut PgRawBuffer) {
@@ -51,16 +47,11 @@ impl Type<Postgres> for f64 {
     }
 }
 
-impl Type<Postgres> for [f64] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for f64 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_FLOAT8, "FLOAT8[]")
     }
 }
-impl Type<Postgres> for Vec<f64> {
-    fn type_info() -> PgTypeInfo {
-        <[f64] as Type<Postgres>>::type_info()
-    }
-}
 
 impl Encode<Postgres> for f64 {
     fn encode(&self, buf: &mut PgRawBuffer) {
@@ -5,6 +5,7 @@ use byteorder::{NetworkEndian, ReadBytesExt};
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 use crate::Error;
@@ -15,18 +16,12 @@ impl Type<Postgres> for i8 {
     }
 }
 
-impl Type<Postgres> for [i8] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i8 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_CHAR, "CHAR[]")
     }
 }
 
-impl Type<Postgres> for Vec<i8> {
-    fn type_info() -> PgTypeInfo {
-        <[i8] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<Postgres> for i8 {
     fn encode(&self, buf: &mut PgRawBuffer) {
         buf.extend_from_slice(&self.to_be_bytes());
@@ -48,18 +43,12 @@ impl Type<Postgres> for i16 {
     }
 }
 
-impl Type<Postgres> for [i16] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i16 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_INT2, "INT2[]")
     }
 }
 
-impl Type<Postgres> for Vec<i16> {
-    fn type_info() -> PgTypeInfo {
-        <[i16] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<Postgres> for i16 {
     fn encode(&self, buf: &mut PgRawBuffer) {
         buf.extend_from_slice(&self.to_be_bytes());
@@ -81,18 +70,12 @@ impl Type<Postgres> for i32 {
     }
 }
 
-impl Type<Postgres> for [i32] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i32 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_INT4, "INT4[]")
     }
 }
 
-impl Type<Postgres> for Vec<i32> {
-    fn type_info() -> PgTypeInfo {
-        <[i32] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<Postgres> for i32 {
     fn encode(&self, buf: &mut PgRawBuffer) {
         buf.extend_from_slice(&self.to_be_bytes());
@@ -114,18 +97,12 @@ impl Type<Postgres> for u32 {
     }
 }
 
-impl Type<Postgres> for [u32] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for u32 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_OID, "OID[]")
     }
 }
 
-impl Type<Postgres> for Vec<u32> {
-    fn type_info() -> PgTypeInfo {
-        <[u32] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<Postgres> for u32 {
     fn encode(&self, buf: &mut PgRawBuffer) {
         buf.extend_from_slice(&self.to_be_bytes());
@@ -147,16 +124,11 @@ impl Type<Postgres> for i64 {
     }
 }
 
-impl Type<Postgres> for [i64] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for i64 {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_INT8, "INT8[]")
     }
 }
-impl Type<Postgres> for Vec<i64> {
-    fn type_info() -> PgTypeInfo {
-        <[i64] as Type<Postgres>>::type_info()
-    }
-}
 
 impl Encode<Postgres> for i64 {
     fn encode(&self, buf: &mut PgRawBuffer) {
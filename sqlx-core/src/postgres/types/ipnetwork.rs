@@ -5,6 +5,7 @@ use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::value::PgValue;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, Postgres};
 use crate::types::Type;
@@ -30,18 +31,12 @@ impl Type<Postgres> for IpNetwork {
     }
 }
 
-impl Type<Postgres> for [IpNetwork] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for IpNetwork {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_INET, "INET[]")
     }
 }
 
-impl Type<Postgres> for Vec<IpNetwork> {
-    fn type_info() -> PgTypeInfo {
-        <[IpNetwork] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<Postgres> for IpNetwork {
     fn encode(&self, buf: &mut PgRawBuffer) {
         match self {
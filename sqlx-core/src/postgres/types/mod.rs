@@ -91,12 +91,53 @@
 //! }
 //! ```
 //!
+//! Fields of a composite may themselves be arrays, other composites or `Option<T>` for
+//! nullable fields. Arrays of a composite are supported as `Vec<T>` or `&[T]`; the array
+//! type (`inventory_item[]`) is looked up by name when first bound.
+//!
+//! ```rust,ignore
+//! #[derive(sqlx::Type)]
+//! #[sqlx(rename = "inventory_shelf")]
+//! struct InventoryShelf {
+//!     label: String,
+//!     tags: Vec<String>,
+//!     featured: Option<InventoryItem>,
+//!     items: Vec<InventoryItem>,
+//! }
+//! ```
+//!
 //! Anonymous composite types are represented as tuples. Note that anonymous composites may only
 //! be returned and not sent to Postgres (this is a limitation of postgres).
 //!
 //! # Arrays
 //!
-//! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`.
+//! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`
+//! and [`PgHasArrayType`].
+//!
+//! Multidimensional arrays are supported as nested vectors (`Vec<Vec<T>>`), which must be
+//! rectangular, or as a [`PgArray<T>`] that holds the elements in a flat list along with the
+//! length and lower bound of each dimension. Only a [`PgArray<T>`] may be decoded from an
+//! array that does not start at `1`.
+//!
+//! The derives for user-defined enumerations and composites also implement
+//! [`PgHasArrayType`]. The OID of the array type is not known ahead of time so it is resolved
+//! by the element type name the first time it is bound and cached on the connection. This
+//! allows binding a list to compare against with `= ANY($1)`.
+//!
+//! ```rust,ignore
+//! sqlx::query("SELECT * FROM people WHERE mood = ANY($1)")
+//!     .bind(vec![Mood::Ok, Mood::Happy])
+//! ```
+//!
+//! A `#[sqlx(transparent)]` wrapper uses the array type of the type it wraps. As a column
+//! of a [domain](https://www.postgresql.org/docs/current/domains.html) is sent and received as
+//! its base type, a transparent wrapper over the base type can be used for domains and
+//! arrays of domains.
+//!
+//! The array type of a transparent wrapper or an integer enumeration (`#[repr(..)]`) is only
+//! implemented when the wrapped type or `repr` has one. When deriving for a type that has
+//! none (e.g., `#[repr(u8)]`) with the `postgres` feature enabled, opt out with
+//! `#[sqlx(no_pg_array)]`.
 //!
 //! # [Enumerations](https://www.postgresql.org/docs/current/datatype-enum.html)
 //!
@@ -140,6 +181,9 @@ mod int;
 mod record;
 mod str;
 
+pub use array::PgHasArrayType;
+pub use raw::{PgArray, PgArrayDimension};
+
 // internal types used by other types to encode or decode related formats
 #[doc(hidden)]
 pub mod raw;
@@ -1,62 +1,258 @@
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::io::{Buf, BufMut};
-use crate::postgres::types::raw::sequence::PgSequenceDecoder;
-use crate::postgres::{PgData, PgRawBuffer, PgValue, Postgres};
+use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
+use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
-use byteorder::BE;
+use byteorder::{ByteOrder, BE};
 use std::marker::PhantomData;
 
 // https://git.postgresql.org/gitweb/?p=postgresql.git;a=blob;f=src/include/utils/array.h;h=7f7e744cb12bc872f628f90dad99dfdf074eb314;hb=master#l6
 // https://git.postgresql.org/gitweb/?p=postgresql.git;a=blob;f=src/backend/utils/adt/arrayfuncs.c;h=7a4a5aaa86dc1c8cffa2d899c89511dc317d485b;hb=master#l1547
 
-pub(crate) struct PgArrayEncoder<'enc, T> {
-    count: usize,
-    len_start_index: usize,
-    buf: &'enc mut PgRawBuffer,
-    phantom: PhantomData<T>,
+/// An array of any number of dimensions.
+///
+/// Postgres arrays are rectangular and may start at any index in each dimension. A `PgArray`
+/// holds its elements as a flat list in row-major order (the last dimension varies fastest),
+/// along with the length and lower bound of each dimension.
+///
+/// ```rust,ignore
+/// // SELECT '[0:1][1:2]={{1,2},{3,4}}'::int4[]
+/// let array: PgArray<i32> = row.get(0);
+///
+/// assert_eq!(array.dimensions()[0], PgArrayDimension { len: 2, lower_bound: 0 });
+/// assert_eq!(array.get(&[1, 2]), Some(&4));
+/// ```
+///
+/// An empty array has no dimensions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PgArray<T> {
+    dimensions: Vec<PgArrayDimension>,
+    elements: Vec<T>,
+}
+
+/// The length and lower bound of one dimension of a [`PgArray`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PgArrayDimension {
+    pub len: usize,
+    pub lower_bound: i32,
 }
 
-impl<'enc, T> PgArrayEncoder<'enc, T>
+impl<T> PgArray<T> {
+    /// Creates a one-dimensional array starting at `1`.
+    pub fn new(elements: Vec<T>) -> Self {
+        let dimensions = if elements.is_empty() {
+            Vec::new()
+        } else {
+            vec![PgArrayDimension {
+                len: elements.len(),
+                lower_bound: 1,
+            }]
+        };
+
+        Self {
+            dimensions,
+            elements,
+        }
+    }
+
+    /// Creates an array from its dimensions and its elements in row-major order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of elements is not the product of the length of each dimension.
+    /// See [`try_with_dimensions`](PgArray::try_with_dimensions) for a version that returns an
+    /// error instead.
+    pub fn with_dimensions(dimensions: Vec<PgArrayDimension>, elements: Vec<T>) -> Self {
+        match Self::try_with_dimensions(dimensions, elements) {
+            Ok(array) => array,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Creates an array from its dimensions and its elements in row-major order, or returns
+    /// an error if the number of elements is not the product of the length of each dimension.
+    pub fn try_with_dimensions(
+        dimensions: Vec<PgArrayDimension>,
+        elements: Vec<T>,
+    ) -> crate::Result<Self> {
+        if count_elements(&dimensions) != Some(elements.len()) {
+            return Err(arg_err!(
+                "array dimensions {:?} do not describe the {} elements given",
+                dimensions,
+                elements.len()
+            ));
+        }
+
+        Ok(Self {
+            dimensions,
+            elements,
+        })
+    }
+
+    pub fn dimensions(&self) -> &[PgArrayDimension] {
+        &self.dimensions
+    }
+
+    pub fn elements(&self) -> &[T] {
+        &self.elements
+    }
+
+    pub fn into_elements(self) -> Vec<T> {
+        self.elements
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the element at the given subscripts, one per dimension, as they would be
+    /// written in SQL (e.g. `[1, 2]` for `array[1][2]`).
+    pub fn get(&self, subscripts: &[i32]) -> Option<&T> {
+        if subscripts.len() != self.dimensions.len() || self.dimensions.is_empty() {
+            return None;
+        }
+
+        let mut index = 0;
+
+        for (subscript, dimension) in subscripts.iter().zip(&self.dimensions) {
+            let offset = i64::from(*subscript) - i64::from(dimension.lower_bound);
+
+            if offset < 0 || offset >= dimension.len as i64 {
+                return None;
+            }
+
+            index = index * dimension.len + offset as usize;
+        }
+
+        self.elements.get(index)
+    }
+}
+
+impl<T> Type<Postgres> for PgArray<T>
+where
+    T: PgHasArrayType,
+{
+    #[inline]
+    fn type_info() -> PgTypeInfo {
+        T::array_type_info()
+    }
+}
+
+impl<T> Encode<Postgres> for PgArray<T>
+where
+    T: Encode<Postgres>,
+    T: PgHasArrayType,
+{
+    fn encode(&self, buf: &mut PgRawBuffer) {
+        let mut encoder = PgArrayEncoder::new(buf, &T::type_info(), &self.dimensions);
+
+        for item in &self.elements {
+            encoder.encode(item);
+        }
+    }
+}
+
+impl<'de, T> Decode<'de, Postgres> for PgArray<T>
 where
-    T: Encode<Postgres> + Type<Postgres>,
+    T: 'de,
+    T: for<'arr> Decode<'arr, Postgres>,
+    T: Type<Postgres>,
 {
-    pub(crate) fn new(buf: &'enc mut PgRawBuffer) -> Self {
-        let ty = <T as Type<Postgres>>::type_info();
+    fn decode(value: PgValue<'de>) -> crate::Result<Self> {
+        let mut decoder = PgArrayDecoder::<T>::new(value)?;
+        let dimensions = decoder.dimensions().to_vec();
+        let elements = decoder.by_ref().collect::<crate::Result<Vec<T>>>()?;
+
+        Ok(Self {
+            dimensions,
+            elements,
+        })
+    }
+}
+
+pub(crate) struct PgArrayEncoder<'enc> {
+    buf: &'enc mut PgRawBuffer,
+    dimensions_start_index: usize,
+    count: usize,
 
+    // for an array of more than one dimension built from nested arrays (`Vec<Vec<T>>`), each
+    // item is encoded as an array of its own and then has its header removed
+    is_nested: bool,
+    sub_dimensions: Option<Vec<i32>>,
+}
+
+impl<'enc> PgArrayEncoder<'enc> {
+    // Starts an array whose dimensions are known ahead of time
+    pub(crate) fn new(
+        buf: &'enc mut PgRawBuffer,
+        element: &PgTypeInfo,
+        dimensions: &[PgArrayDimension],
+    ) -> Self {
         // ndim
-        buf.put_i32::<BE>(1);
+        buf.put_i32::<BE>(dimensions.len() as i32);
 
         // dataoffset
         buf.put_i32::<BE>(0);
 
         // [elemtype] element type OID
-        if let Some(oid) = ty.id {
+        if let Some(oid) = element.id {
             // write oid
             buf.extend(&oid.0.to_be_bytes());
         } else {
             // write hole for this oid
-            buf.push_type_hole(&ty.name);
+            buf.push_type_hole(&element.name);
         }
 
-        let len_start_index = buf.len();
+        let dimensions_start_index = buf.len();
 
-        // dimensions
-        buf.put_i32::<BE>(0);
+        for dimension in dimensions {
+            // dimensions
+            buf.put_i32::<BE>(dimension.len as i32);
 
-        // lower_bnds
-        buf.put_i32::<BE>(1);
+            // lower_bnds
+            buf.put_i32::<BE>(dimension.lower_bound);
+        }
 
         Self {
-            count: 0,
-            len_start_index,
             buf,
-
-            phantom: PhantomData,
+            dimensions_start_index,
+            count: 0,
+            is_nested: false,
+            sub_dimensions: None,
         }
     }
 
-    pub(crate) fn encode(&mut self, item: T) {
+    // Starts an array of `ndim` dimensions that is counted as it is encoded; for more than one
+    // dimension each item is expected to be an array of `ndim - 1` dimensions
+    pub(crate) fn nested(buf: &'enc mut PgRawBuffer, element: &PgTypeInfo, ndim: usize) -> Self {
+        let dimensions = vec![
+            PgArrayDimension {
+                len: 0,
+                lower_bound: 1,
+            };
+            ndim
+        ];
+
+        let mut encoder = Self::new(buf, element, &dimensions);
+        encoder.is_nested = ndim > 1;
+        encoder
+    }
+
+    pub(crate) fn encode<T>(&mut self, item: T)
+    where
+        T: Encode<Postgres>,
+    {
+        if self.is_nested {
+            self.encode_sub_array(item);
+            return;
+        }
+
         // Allocate space for the length of the encoded elemement up front
         let el_len_index = self.buf.len();
         self.buf.put_i32::<BE>(0);
@@ -79,47 +275,104 @@ where
         self.count += 1;
     }
 
+    fn encode_sub_array<T>(&mut self, item: T)
+    where
+        T: Encode<Postgres>,
+    {
+        let start = self.buf.len();
+
+        if let IsNull::Yes = Encode::<Postgres>::encode_nullable(&item, self.buf) {
+            self.buf
+                .set_error("multidimensional arrays cannot have NULL sub-arrays");
+
+            return;
+        }
+
+        // the header of the sub-array is its number of dimensions, the data offset, the
+        // element type and then the length and lower bound of each dimension
+        let ndim = BE::read_i32(&self.buf[start..]) as usize;
+        let header_len = 12 + ndim * 8;
+
+        let dimensions: Vec<i32> = (0..ndim)
+            .map(|i| BE::read_i32(&self.buf[(start + 12 + i * 8)..]))
+            .collect();
+
+        match self.sub_dimensions {
+            // the query returns an error instead of being sent (see `PgRawBuffer::set_error`)
+            Some(ref expected) if *expected != dimensions => self.buf.set_error(format!(
+                "multidimensional arrays must have sub-arrays with matching dimensions; \
+                 expected {:?} but found {:?}",
+                expected, dimensions
+            )),
+
+            Some(_) => {}
+
+            None => self.sub_dimensions = Some(dimensions),
+        }
+
+        // the elements of the sub-array follow on as elements of this array
+        self.buf.remove_range(start..(start + header_len));
+
+        self.count += 1;
+    }
+
+    // Writes the length of each dimension for an array started with `nested`
     pub(crate) fn finish(&mut self) {
         const I32_SIZE: usize = std::mem::size_of::<i32>();
 
+        let mut index = self.dimensions_start_index;
+
         let size_bytes = (self.count as i32).to_be_bytes();
+        self.buf[index..index + I32_SIZE].copy_from_slice(&size_bytes);
 
-        self.buf[self.len_start_index..self.len_start_index + I32_SIZE]
-            .copy_from_slice(&size_bytes);
+        // the sub-arrays of an empty array are unknown and are left at a length of 0
+        if let Some(ref sub_dimensions) = self.sub_dimensions {
+            for len in sub_dimensions {
+                index += I32_SIZE * 2;
+                self.buf[index..index + I32_SIZE].copy_from_slice(&len.to_be_bytes());
+            }
+        }
     }
 }
 
 pub(crate) struct PgArrayDecoder<'de, T> {
-    inner: PgSequenceDecoder<'de>,
+    dimensions: Vec<PgArrayDimension>,
+    elements: PgArrayElements<'de>,
+    remaining: usize,
     phantom: PhantomData<T>,
 }
 
+enum PgArrayElements<'de> {
+    Binary { element_oid: u32, buf: &'de [u8] },
+    Text(std::vec::IntoIter<Option<String>>),
+}
+
 impl<'de, T> PgArrayDecoder<'de, T>
 where
     T: for<'arr> Decode<'arr, Postgres>,
     T: Type<Postgres>,
 {
     pub(crate) fn new(value: PgValue<'de>) -> crate::Result<Self> {
-        let mut data = value.try_get()?;
-
-        let element_oid = match data {
-            PgData::Binary(ref mut buf) => {
+        let (dimensions, elements) = match value.try_get()? {
+            PgData::Binary(mut buf) => {
                 // number of dimensions of the array
                 let ndim = buf.get_i32::<BE>()?;
 
                 if ndim == 0 {
                     // ndim of 0 is an empty array
                     return Ok(Self {
-                        inner: PgSequenceDecoder::new(PgData::Binary(&[]), None),
+                        dimensions: Vec::new(),
+                        elements: PgArrayElements::Binary {
+                            element_oid: 0,
+                            buf: &[],
+                        },
+                        remaining: 0,
                         phantom: PhantomData,
                     });
                 }
 
-                if ndim != 1 {
-                    return Err(decode_err!(
-                        "encountered an array of {} dimensions; only one-dimensional arrays are supported",
-                        ndim
-                    ));
+                if ndim < 0 {
+                    return Err(decode_err!("encountered an array of {} dimensions", ndim));
                 }
 
                 // offset to stored data
@@ -129,33 +382,141 @@ where
                 // element type OID
                 let element_oid = buf.get_u32::<BE>()?;
 
-                // length of each array axis
-                let _dimensions = buf.get_i32::<BE>()?;
+                let mut dimensions = Vec::with_capacity(ndim as usize);
+
+                for _ in 0..ndim {
+                    // length of each array axis
+                    let len = buf.get_i32::<BE>()?;
 
-                // lower boundary of each dimension
-                let lower_bnds = buf.get_i32::<BE>()?;
+                    if len < 0 {
+                        return Err(decode_err!(
+                            "encountered an array dimension of length {}",
+                            len
+                        ));
+                    }
 
-                if lower_bnds != 1 {
-                    return Err(decode_err!(
-                        "encountered an array with a lower bound of {} in the first dimension; only arrays starting at one are supported",
-                        lower_bnds
-                    ));
+                    // lower boundary of each dimension
+                    let lower_bound = buf.get_i32::<BE>()?;
+
+                    dimensions.push(PgArrayDimension {
+                        len: len as usize,
+                        lower_bound,
+                    });
                 }
 
-                Some(element_oid)
+                (dimensions, PgArrayElements::Binary { element_oid, buf })
             }
 
-            PgData::Text(_) => None,
+            PgData::Text(s) => {
+                let (dimensions, elements) = parse_text(s)?;
+
+                (dimensions, PgArrayElements::Text(elements.into_iter()))
+            }
         };
 
+        let remaining = count_elements(&dimensions).ok_or_else(|| {
+            decode_err!(
+                "array dimensions {:?} describe too many elements",
+                dimensions
+            )
+        })?;
+
         Ok(Self {
-            inner: PgSequenceDecoder::new(data, element_oid),
+            remaining,
+            dimensions,
+            elements,
             phantom: PhantomData,
         })
     }
 
+    pub(crate) fn dimensions(&self) -> &[PgArrayDimension] {
+        &self.dimensions
+    }
+
     fn decode(&mut self) -> crate::Result<Option<T>> {
-        self.inner.decode()
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        self.remaining -= 1;
+
+        let value = match self.elements {
+            PgArrayElements::Binary {
+                element_oid,
+                ref mut buf,
+            } => match next_binary_element(buf)? {
+                // NOTE: We don't validate the element type because the outer type
+                //       like `text[]` would have already ensured we are dealing with a Vec<String>
+                Some(el) => {
+                    T::decode(PgValue::bytes(PgTypeInfo::new(TypeId(element_oid), ""), el))?
+                }
+
+                None => T::decode(PgValue::null())?,
+            },
+
+            PgArrayElements::Text(ref mut elements) => match elements.next() {
+                Some(Some(el)) => T::decode(PgValue::from_str(&el))?,
+                Some(None) => T::decode(PgValue::null())?,
+
+                None => return Ok(None),
+            },
+        };
+
+        Ok(Some(value))
+    }
+
+    // Decodes the next sub-array along the first dimension as a `T` of one fewer dimension
+    pub(crate) fn decode_sub_array(&mut self) -> crate::Result<Option<T>> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        let sub_dimensions = &self.dimensions[1..];
+
+        // the count of every suffix of the dimensions was checked by `new()`
+        let len = count_elements(sub_dimensions).unwrap_or(0);
+
+        self.remaining -= len;
+
+        let value = match self.elements {
+            PgArrayElements::Binary {
+                element_oid,
+                ref mut buf,
+            } => {
+                let start = *buf;
+
+                for _ in 0..len {
+                    next_binary_element(buf)?;
+                }
+
+                let data = &start[..(start.len() - buf.len())];
+
+                let mut sub = Vec::with_capacity(12 + sub_dimensions.len() * 8 + data.len());
+
+                sub.put_i32::<BE>(sub_dimensions.len() as i32);
+                sub.put_i32::<BE>(0);
+                sub.put_u32::<BE>(element_oid);
+
+                for dimension in sub_dimensions {
+                    sub.put_i32::<BE>(dimension.len as i32);
+                    sub.put_i32::<BE>(dimension.lower_bound);
+                }
+
+                sub.extend_from_slice(data);
+
+                T::decode(PgValue::bytes(T::type_info(), &sub))?
+            }
+
+            PgArrayElements::Text(ref mut elements) => {
+                let mut sub = String::new();
+
+                write_text(&mut sub, sub_dimensions, elements);
+
+                T::decode(PgValue::from_str(&sub))?
+            }
+        };
+
+        Ok(Some(value))
     }
 }
 
@@ -172,18 +533,263 @@ where
     }
 }
 
+// The number of elements of an array of the given dimensions; `None` if it (or the number of
+// elements of any of its sub-arrays) overflows
+fn count_elements(dimensions: &[PgArrayDimension]) -> Option<usize> {
+    if dimensions.is_empty() {
+        Some(0)
+    } else {
+        dimensions
+            .iter()
+            .rev()
+            .try_fold(1_usize, |count, dimension| count.checked_mul(dimension.len))
+    }
+}
+
+// Splits off the next element of a binary array; `None` is a NULL element
+fn next_binary_element<'de>(buf: &mut &'de [u8]) -> crate::Result<Option<&'de [u8]>> {
+    let len = buf.get_i32::<BE>()?;
+
+    // a length of -1 is a NULL element
+    if len == -1 {
+        return Ok(None);
+    }
+
+    if len < 0 {
+        return Err(decode_err!(
+            "encountered an array element of length {}",
+            len
+        ));
+    }
+
+    if len as usize > buf.len() {
+        return Err(decode_err!(
+            "array element of {} bytes overruns the remaining {} bytes",
+            len,
+            buf.len()
+        ));
+    }
+
+    Ok(Some(buf.get_bytes(len as usize)?))
+}
+
+// Parses the TEXT form of an array, e.g. `[0:1][1:2]={{1,2},{3,NULL}}`, into its dimensions
+// and a flat list of elements
+fn parse_text(mut s: &str) -> crate::Result<(Vec<PgArrayDimension>, Vec<Option<String>>)> {
+    let mut lower_bounds = Vec::new();
+
+    // arrays that do not start at 1 are prefixed with the bounds of each dimension
+    if s.starts_with('[') {
+        let end = s
+            .find('=')
+            .ok_or_else(|| decode_err!("expected `=` after array bounds in {:?}", s))?;
+
+        for bounds in s[..end].split(']').filter(|bounds| !bounds.is_empty()) {
+            let lower = bounds
+                .trim_start_matches('[')
+                .split(':')
+                .next()
+                .and_then(|lower| lower.parse::<i32>().ok())
+                .ok_or_else(|| decode_err!("invalid array bounds {:?}", bounds))?;
+
+            lower_bounds.push(lower);
+        }
+
+        s = &s[(end + 1)..];
+    }
+
+    let mut lens: Vec<Option<usize>> = Vec::new();
+    let mut counts: Vec<usize> = Vec::new();
+    let mut elements = Vec::new();
+    let mut element_depth = None;
+    let mut chars = s.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' => {
+                counts.push(0);
+
+                if lens.len() < counts.len() {
+                    lens.push(None);
+                }
+
+                continue;
+            }
+
+            '}' => {
+                let depth = counts.len();
+                let count = counts
+                    .pop()
+                    .ok_or_else(|| decode_err!("unbalanced braces in array {:?}", s))?;
+
+                match lens[depth - 1] {
+                    Some(len) if len != count => {
+                        return Err(decode_err!(
+                            "multidimensional arrays must have sub-arrays with matching dimensions: {:?}",
+                            s
+                        ));
+                    }
+
+                    _ => lens[depth - 1] = Some(count),
+                }
+
+                if let Some(count) = counts.last_mut() {
+                    *count += 1;
+                }
+
+                continue;
+            }
+
+            ',' => continue,
+            _ if ch.is_whitespace() => continue,
+
+            '"' => {
+                let mut value = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('\\') => value.extend(chars.next()),
+                        Some('"') => break,
+                        Some(ch) => value.push(ch),
+
+                        None => return Err(decode_err!("unterminated quote in array {:?}", s)),
+                    }
+                }
+
+                elements.push(Some(value));
+            }
+
+            _ => {
+                let mut value = String::new();
+                let mut ch = Some(ch);
+
+                while let Some(c) = ch {
+                    if c == '\\' {
+                        value.extend(chars.next());
+                    } else {
+                        value.push(c);
+                    }
+
+                    ch = match chars.peek() {
+                        Some(',') | Some('}') | None => None,
+                        Some(_) => chars.next(),
+                    };
+                }
+
+                let value = value.trim_end();
+
+                // Yes, in arrays the text encoding of a NULL is just NULL
+                elements.push(if value.eq_ignore_ascii_case("NULL") {
+                    None
+                } else {
+                    Some(value.to_owned())
+                });
+            }
+        }
+
+        // every element must be at the same depth
+        match element_depth {
+            Some(depth) if depth != counts.len() => {
+                return Err(decode_err!(
+                    "multidimensional arrays must have sub-arrays with matching dimensions: {:?}",
+                    s
+                ));
+            }
+
+            _ => element_depth = Some(counts.len()),
+        }
+
+        if let Some(count) = counts.last_mut() {
+            *count += 1;
+        }
+    }
+
+    if !counts.is_empty() {
+        return Err(decode_err!("unbalanced braces in array {:?}", s));
+    }
+
+    if elements.is_empty() {
+        // the empty array `{}` has no dimensions
+        return Ok((Vec::new(), elements));
+    }
+
+    if !lower_bounds.is_empty() && lower_bounds.len() != lens.len() {
+        return Err(decode_err!(
+            "expected bounds for {} dimensions in array {:?}",
+            lens.len(),
+            s
+        ));
+    }
+
+    let dimensions = lens
+        .into_iter()
+        .enumerate()
+        .map(|(i, len)| PgArrayDimension {
+            len: len.unwrap_or(0),
+            lower_bound: lower_bounds.get(i).copied().unwrap_or(1),
+        })
+        .collect();
+
+    Ok((dimensions, elements))
+}
+
+// Writes out the TEXT form of an array of the given dimensions from a flat list of elements
+fn write_text(
+    buf: &mut String,
+    dimensions: &[PgArrayDimension],
+    elements: &mut impl Iterator<Item = Option<String>>,
+) {
+    buf.push('{');
+
+    for i in 0..dimensions[0].len {
+        if i > 0 {
+            buf.push(',');
+        }
+
+        if dimensions.len() > 1 {
+            write_text(buf, &dimensions[1..], elements);
+            continue;
+        }
+
+        match elements.next() {
+            Some(Some(value)) => {
+                buf.push('"');
+
+                for ch in value.chars() {
+                    if ch == '"' || ch == '\\' {
+                        buf.push('\\');
+                    }
+
+                    buf.push(ch);
+                }
+
+                buf.push('"');
+            }
+
+            _ => buf.push_str("NULL"),
+        }
+    }
+
+    buf.push('}');
+}
+
 #[cfg(test)]
 mod tests {
-    use super::PgArrayDecoder;
-    use super::PgArrayEncoder;
-    use crate::postgres::{PgRawBuffer, PgValue};
+    use super::{PgArray, PgArrayDecoder, PgArrayDimension, PgArrayEncoder};
+    use crate::decode::Decode;
+    use crate::encode::Encode;
+    use crate::postgres::types::PgHasArrayType;
+    use crate::postgres::{PgRawBuffer, PgValue, Postgres};
 
     const BUF_BINARY_I32: &[u8] = b"\x00\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x17\x00\x00\x00\x04\x00\x00\x00\x01\x00\x00\x00\x04\x00\x00\x00\x01\x00\x00\x00\x04\x00\x00\x00\x02\x00\x00\x00\x04\x00\x00\x00\x03\x00\x00\x00\x04\x00\x00\x00\x04";
 
+    // select '{{1,2},{3,4}}'::int4[]
+    const BUF_BINARY_I32_2D: &[u8] = b"\x00\x00\x00\x02\x00\x00\x00\x00\x00\x00\x00\x17\x00\x00\x00\x02\x00\x00\x00\x01\x00\x00\x00\x02\x00\x00\x00\x01\x00\x00\x00\x04\x00\x00\x00\x01\x00\x00\x00\x04\x00\x00\x00\x02\x00\x00\x00\x04\x00\x00\x00\x03\x00\x00\x00\x04\x00\x00\x00\x04";
+
     #[test]
     fn it_encodes_i32() {
         let mut buf = PgRawBuffer::default();
-        let mut encoder = PgArrayEncoder::new(&mut buf);
+        let mut encoder = PgArrayEncoder::nested(&mut buf, &i32::array_element_type_info(), 1);
 
         for val in &[1_i32, 2, 3, 4] {
             encoder.encode(*val);
@@ -194,6 +800,24 @@ mod tests {
         assert_eq!(&**buf, BUF_BINARY_I32);
     }
 
+    #[test]
+    fn it_encodes_nested_i32() {
+        let mut buf = PgRawBuffer::default();
+
+        vec![vec![1_i32, 2], vec![3, 4]].encode(&mut buf);
+
+        assert_eq!(&**buf, BUF_BINARY_I32_2D);
+    }
+
+    #[test]
+    fn it_rejects_ragged_nested_i32() {
+        let mut buf = PgRawBuffer::default();
+
+        vec![vec![1_i32, 2], vec![3]].encode(&mut buf);
+
+        assert!(matches!(buf.take_error(), Some(crate::Error::Argument(_))));
+    }
+
     #[test]
     fn it_decodes_text_i32() -> crate::Result<()> {
         let s = "{1,152,-12412}";
@@ -219,6 +843,48 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn it_decodes_text_nested_str() -> crate::Result<()> {
+        let s = r#"{{a,"b,\"c"},{NULL,"NULL"}}"#;
+        let value: Vec<Vec<Option<String>>> = Decode::<Postgres>::decode(PgValue::from_str(s))?;
+
+        assert_eq!(
+            value,
+            vec![
+                vec![Some("a".to_owned()), Some("b,\"c".to_owned())],
+                vec![None, Some("NULL".to_owned())]
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_decodes_text_bounds() -> crate::Result<()> {
+        let s = "[0:1][-1:0]={{1,2},{3,4}}";
+        let array: PgArray<i32> = Decode::<Postgres>::decode(PgValue::from_str(s))?;
+
+        assert_eq!(
+            array.dimensions(),
+            &[
+                PgArrayDimension {
+                    len: 2,
+                    lower_bound: 0
+                },
+                PgArrayDimension {
+                    len: 2,
+                    lower_bound: -1
+                },
+            ]
+        );
+
+        assert_eq!(array.elements(), &[1, 2, 3, 4]);
+        assert_eq!(array.get(&[1, -1]), Some(&3));
+        assert_eq!(array.get(&[2, -1]), None);
+
+        Ok(())
+    }
+
     #[test]
     fn it_decodes_binary_nulls() -> crate::Result<()> {
         let mut decoder = PgArrayDecoder::<Option<bool>>::new(PgValue::from_bytes(
@@ -251,4 +917,63 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn it_rejects_negative_binary_lengths() -> crate::Result<()> {
+        // a dimension of length -1
+        let mut buf = BUF_BINARY_I32.to_vec();
+        buf[12..16].copy_from_slice(&(-1_i32).to_be_bytes());
+
+        assert!(matches!(
+            PgArrayDecoder::<i32>::new(PgValue::from_bytes(&buf)),
+            Err(crate::Error::Decode(_))
+        ));
+
+        // an element of length -2
+        let mut buf = BUF_BINARY_I32.to_vec();
+        buf[20..24].copy_from_slice(&(-2_i32).to_be_bytes());
+
+        let mut decoder = PgArrayDecoder::<i32>::new(PgValue::from_bytes(&buf))?;
+
+        assert!(matches!(decoder.decode(), Err(crate::Error::Decode(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn it_checks_the_dimensions() {
+        let dimension = |len| PgArrayDimension {
+            len,
+            lower_bound: 1,
+        };
+
+        assert!(PgArray::try_with_dimensions(vec![dimension(2), dimension(2)], vec![1; 4]).is_ok());
+
+        assert!(matches!(
+            PgArray::try_with_dimensions(vec![dimension(2), dimension(2)], vec![1; 3]),
+            Err(crate::Error::Argument(_))
+        ));
+
+        assert!(PgArray::try_with_dimensions(
+            vec![dimension(std::usize::MAX), dimension(2)],
+            vec![1; 0]
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn it_decodes_binary_nested_i32() -> crate::Result<()> {
+        let value: Vec<Vec<i32>> =
+            Decode::<Postgres>::decode(PgValue::from_bytes(BUF_BINARY_I32_2D))?;
+
+        assert_eq!(value, vec![vec![1, 2], vec![3, 4]]);
+
+        let array: PgArray<i32> =
+            Decode::<Postgres>::decode(PgValue::from_bytes(BUF_BINARY_I32_2D))?;
+
+        assert_eq!(array.dimensions().len(), 2);
+        assert_eq!(array.elements(), &[1, 2, 3, 4]);
+
+        Ok(())
+    }
 }
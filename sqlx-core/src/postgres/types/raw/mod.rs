@@ -5,6 +5,9 @@ mod sequence;
 
 pub(crate) use array::{PgArrayDecoder, PgArrayEncoder};
 
+// Re-exported from `postgres::types`
+pub use array::{PgArray, PgArrayDimension};
+
 // Used in integration tests
 pub use numeric::{PgNumeric, PgNumericSign};
 
@@ -1,6 +1,7 @@
 use crate::decode::Decode;
 use crate::io::Buf;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::try_resolve_type_name;
 use crate::postgres::{PgData, PgTypeInfo, PgValue, Postgres};
 use crate::types::{Type, TypeInfo};
 use byteorder::BigEndian;
@@ -9,12 +10,16 @@ pub(crate) struct PgSequenceDecoder<'de> {
     data: PgData<'de>,
     len: usize,
     is_text_record: bool,
+    // set once the last element of a TEXT sequence has been consumed; an empty remainder
+    // is not enough as a record with a trailing NULL field ends in a bare `,`
+    is_text_done: bool,
     element_oid: Option<u32>,
 }
 
 impl<'de> PgSequenceDecoder<'de> {
     pub(crate) fn new(mut data: PgData<'de>, element_oid: Option<u32>) -> Self {
         let mut is_text_record = false;
+        let mut is_text_done = false;
 
         match data {
             PgData::Binary(_) => {
@@ -26,11 +31,13 @@ impl<'de> PgSequenceDecoder<'de> {
                 is_text_record = s.as_bytes()[0] == b'(';
                 // remove the outer ( ... ) or { ... }
                 *s = &s[1..(s.len() - 1)];
+                is_text_done = s.is_empty();
             }
         }
 
         Self {
             is_text_record,
+            is_text_done,
             element_oid,
             data,
             len: 0,
@@ -61,9 +68,18 @@ impl<'de> PgSequenceDecoder<'de> {
                     // mixed sequences can contain values of many different types
                     // the OID of the type is encoded next to each value
                     let element_oid = buf.get_u32::<BigEndian>()?;
-                    let expected_ty = PgTypeInfo::new(TypeId(element_oid), "");
-
-                    if !expected_ty.compatible(&T::type_info()) {
+                    let element_name = try_resolve_type_name(element_oid);
+                    let expected_ty =
+                        PgTypeInfo::new(TypeId(element_oid), element_name.unwrap_or(""));
+                    let ty = T::type_info();
+
+                    // NOTE: User-defined types (composites, enums, domains) are declared by name
+                    //       and the OID of a nested field cannot be resolved to a name without
+                    //       asking Postgres. The enclosing type has already been matched by name
+                    //       so we only validate fields where at least one side is known by OID.
+                    let is_user_defined = ty.id.is_none() && element_name.is_none();
+
+                    if !is_user_defined && !expected_ty.compatible(&ty) {
                         return Err(crate::Error::mismatched_types::<Postgres, T>(expected_ty));
                     }
 
@@ -88,7 +104,7 @@ impl<'de> PgSequenceDecoder<'de> {
             }
 
             PgData::Text(ref mut s) => {
-                if s.is_empty() {
+                if self.is_text_done {
                     return Ok(None);
                 }
 
@@ -150,7 +166,10 @@ impl<'de> PgSequenceDecoder<'de> {
                 // NOTE: We pass `0` as the type ID because we don't have a reasonable value
                 //       we could use. In TEXT mode, sequences aren't typed.
 
-                let value = T::decode(if end == Some(0) {
+                // an empty, unquoted element is a NULL
+                let is_empty = end.unwrap_or_else(|| s.len()) == 0;
+
+                let value = T::decode(if is_empty {
                     PgValue::null()
                 } else if !self.is_text_record && value == "NULL" {
                     // Yes, in arrays the text encoding of a NULL is just NULL
@@ -159,11 +178,12 @@ impl<'de> PgSequenceDecoder<'de> {
                     PgValue::from_str(&*value)
                 })?;
 
-                *s = if let Some(end) = end {
-                    &s[end + 1..]
+                if let Some(end) = end {
+                    *s = &s[end + 1..];
                 } else {
-                    ""
-                };
+                    *s = "";
+                    self.is_text_done = true;
+                }
 
                 self.len += 1;
 
@@ -198,6 +218,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn it_decodes_text_trailing_null() -> crate::Result<()> {
+        // select ('a',null,null);
+        let data = "(a,,)";
+        let mut decoder = PgSequenceDecoder::from(data);
+
+        assert_eq!(decoder.decode::<String>()?, Some("a".to_owned()));
+        assert_eq!(decoder.decode::<Option<i32>>()?, Some(None));
+        assert_eq!(decoder.decode::<Option<i32>>()?, Some(None));
+        assert_eq!(decoder.decode::<Option<i32>>()?, None);
+
+        Ok(())
+    }
+
     #[test]
     fn it_decodes_text_nested_sequence() -> crate::Result<()> {
         // select ((1,array[false,true]),array[(1,4),(5,2)]);
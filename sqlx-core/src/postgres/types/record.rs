@@ -2,6 +2,7 @@ use crate::decode::Decode;
 use crate::postgres::protocol::TypeId;
 use crate::postgres::type_info::PgTypeInfo;
 use crate::postgres::types::raw::PgRecordDecoder;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::value::PgValue;
 use crate::postgres::Postgres;
 use crate::types::Type;
@@ -15,21 +16,13 @@ macro_rules! impl_pg_record_for_tuple {
             }
         }
 
-        impl<$($T,)+> Type<Postgres> for [($($T,)+)] {
+        impl<$($T,)+> PgHasArrayType for ($($T,)+) {
             #[inline]
-            fn type_info() -> PgTypeInfo {
+            fn array_type_info() -> PgTypeInfo {
                 PgTypeInfo::new(TypeId::ARRAY_RECORD, "RECORD[]")
             }
         }
 
-
-        impl<$($T,)+> Type<Postgres> for Vec<($($T,)+)> {
-            #[inline]
-            fn type_info() -> PgTypeInfo {
-                <[($($T,)+)] as Type<Postgres>>::type_info()
-            }
-        }
-
         impl<'de, $($T,)+> Decode<'de, Postgres> for ($($T,)+)
         where
             $($T: 'de,)+
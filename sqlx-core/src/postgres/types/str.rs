@@ -3,6 +3,7 @@ use std::str::from_utf8;
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 use crate::Error;
@@ -13,33 +14,21 @@ impl Type<Postgres> for str {
     }
 }
 
-impl Type<Postgres> for [&'_ str] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for &'_ str {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_TEXT, "TEXT[]")
     }
 }
 
-impl Type<Postgres> for Vec<&'_ str> {
-    fn type_info() -> PgTypeInfo {
-        <[&'_ str] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Type<Postgres> for String {
     fn type_info() -> PgTypeInfo {
         <str as Type<Postgres>>::type_info()
     }
 }
 
-impl Type<Postgres> for [String] {
-    fn type_info() -> PgTypeInfo {
-        <[&'_ str] as Type<Postgres>>::type_info()
-    }
-}
-
-impl Type<Postgres> for Vec<String> {
-    fn type_info() -> PgTypeInfo {
-        <Vec<&'_ str> as Type<Postgres>>::type_info()
+impl PgHasArrayType for String {
+    fn array_type_info() -> PgTypeInfo {
+        <&str as PgHasArrayType>::array_type_info()
     }
 }
 
@@ -9,6 +9,7 @@ use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::io::Buf;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::{PgData, PgRawBuffer, PgTypeInfo, PgValue, Postgres};
 use crate::types::Type;
 
@@ -38,50 +39,26 @@ impl Type<Postgres> for OffsetDateTime {
     }
 }
 
-impl Type<Postgres> for [Time] {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::new(TypeId::ARRAY_TIME, "TIME[]")
-    }
-}
-
-impl Type<Postgres> for [Date] {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::new(TypeId::ARRAY_DATE, "DATE[]")
-    }
-}
-
-impl Type<Postgres> for [PrimitiveDateTime] {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::new(TypeId::ARRAY_TIMESTAMP, "TIMESTAMP[]")
-    }
-}
-
-impl Type<Postgres> for [OffsetDateTime] {
-    fn type_info() -> PgTypeInfo {
-        PgTypeInfo::new(TypeId::ARRAY_TIMESTAMPTZ, "TIMESTAMPTZ[]")
-    }
-}
-
-impl Type<Postgres> for Vec<Time> {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for Time {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_TIME, "TIME[]")
     }
 }
 
-impl Type<Postgres> for Vec<Date> {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for Date {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_DATE, "DATE[]")
     }
 }
 
-impl Type<Postgres> for Vec<PrimitiveDateTime> {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for PrimitiveDateTime {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_TIMESTAMP, "TIMESTAMP[]")
     }
 }
 
-impl Type<Postgres> for Vec<OffsetDateTime> {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for OffsetDateTime {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_TIMESTAMPTZ, "TIMESTAMPTZ[]")
     }
 }
@@ -5,6 +5,7 @@ use uuid::Uuid;
 use crate::decode::Decode;
 use crate::encode::Encode;
 use crate::postgres::protocol::TypeId;
+use crate::postgres::types::PgHasArrayType;
 use crate::postgres::value::{PgData, PgValue};
 use crate::postgres::{PgRawBuffer, PgTypeInfo, Postgres};
 use crate::types::Type;
@@ -15,18 +16,12 @@ impl Type<Postgres> for Uuid {
     }
 }
 
-impl Type<Postgres> for [Uuid] {
-    fn type_info() -> PgTypeInfo {
+impl PgHasArrayType for Uuid {
+    fn array_type_info() -> PgTypeInfo {
         PgTypeInfo::new(TypeId::ARRAY_UUID, "UUID[]")
     }
 }
 
-impl Type<Postgres> for Vec<Uuid> {
-    fn type_info() -> PgTypeInfo {
-        <[Uuid] as Type<Postgres>>::type_info()
-    }
-}
-
 impl Encode<Postgres> for Uuid {
     fn encode(&self, buf: &mut PgRawBuffer) {
         buf.extend_from_slice(self.as_bytes());
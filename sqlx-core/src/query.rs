@@ -5,7 +5,7 @@ use futures_core::Stream;
 use futures_util::future::ready;
 use futures_util::TryFutureExt;
 
-use crate::arguments::Arguments;
+use crate::arguments::{Arguments, NamedArguments};
 use crate::cursor::{Cursor, HasCursor};
 use crate::database::Database;
 use crate::encode::Encode;
@@ -79,6 +79,21 @@ where
         self
     }
 
+    /// Bind a value to the named parameter `name`, written as `:name` or `@name` in the query.
+    ///
+    /// Before the query is executed, the named parameters are rewritten to the bind parameters
+    /// of the database. A name may be used more than once in the query but every name in the
+    /// query must be bound exactly once and named and positional values cannot be mixed.
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        DB::Arguments: NamedArguments,
+        T: Type<DB>,
+        T: Encode<DB>,
+    {
+        self.arguments.add_named(name, value);
+        self
+    }
+
     #[doc(hidden)]
     pub fn bind_all(self, arguments: DB::Arguments) -> Query<'q, DB> {
         Query {
@@ -209,6 +224,206 @@ where
     }
 }
 
+/// SQL query with bind parameters that owns its query string.
+///
+/// Returned by `query!()` and friends when an argument is a list (`..expr`) which is expanded
+/// to a bind parameter for each element when the query is built. Has the same methods as
+/// [Query] except for [Query::bind] and [Query::fetch].
+#[must_use = "query must be executed to affect database"]
+pub struct OwnedQuery<DB>
+where
+    DB: Database,
+{
+    query: String,
+    arguments: DB::Arguments,
+
+    // an error in binding the arguments that is returned when the query is executed
+    error: Option<crate::Error>,
+}
+
+/// SQL query that owns its query string and will map its results to owned Rust types.
+///
+/// Returned by [OwnedQuery::try_map]. Has the same methods as [Map].
+#[must_use = "query must be executed to affect database"]
+pub struct OwnedMap<DB, F>
+where
+    DB: Database,
+{
+    query: OwnedQuery<DB>,
+    mapper: F,
+}
+
+impl<DB> OwnedQuery<DB>
+where
+    DB: Database,
+{
+    #[doc(hidden)]
+    pub fn new(query: String, arguments: DB::Arguments) -> Self {
+        OwnedQuery {
+            query,
+            arguments,
+            error: None,
+        }
+    }
+
+    // Checks that the list arguments of a `VALUES` row have the same length; if they do not,
+    // an error is returned when the query is executed
+    #[doc(hidden)]
+    pub fn check_row_lengths(mut self, lengths: &[usize]) -> Self {
+        if self.error.is_none() && lengths.iter().any(|&len| len != lengths[0]) {
+            self.error = Some(arg_err!(
+                "the list arguments in a `VALUES` row must have the same length; \
+                 found lengths {:?}",
+                lengths
+            ));
+        }
+
+        self
+    }
+
+    pub async fn execute<E>(self, mut executor: E) -> crate::Result<u64>
+    where
+        E: Executor<Database = DB>,
+    {
+        let OwnedQuery {
+            query,
+            arguments,
+            error,
+        } = self;
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        executor
+            .execute(Query {
+                query: &query,
+                arguments,
+                database: PhantomData,
+            })
+            .await
+    }
+
+    /// Map each row in the result to another type.
+    ///
+    /// See [Query::try_map].
+    pub fn try_map<F>(self, mapper: F) -> OwnedMap<DB, F>
+    where
+        F: TryMapRow<DB>,
+    {
+        OwnedMap {
+            query: self,
+            mapper,
+        }
+    }
+}
+
+impl<DB, F> OwnedMap<DB, F>
+where
+    DB: Database,
+    F: TryMapRow<DB>,
+{
+    /// Execute the query and get a [Stream] of the results, returning our mapped type.
+    pub fn fetch<'e, E>(
+        self,
+        executor: E,
+    ) -> impl Stream<Item = crate::Result<F::Output>> + Unpin + 'e
+    where
+        E: RefExecutor<'e, Database = DB> + 'e,
+        F: 'e,
+        F::Output: 'e,
+    {
+        let OwnedMap { query, mut mapper } = self;
+
+        Box::pin(try_stream! {
+            let OwnedQuery { query, arguments, error } = query;
+
+            if let Some(error) = error {
+                Err(error)?;
+            }
+
+            let mut cursor = executor.fetch_by_ref(Query {
+                query: &query,
+                arguments,
+                database: PhantomData,
+            });
+
+            while let Some(next) = cursor.next().await? {
+                let mapped = mapper.try_map_row(next)?;
+                yield mapped;
+            }
+        })
+    }
+
+    /// Get the first row in the result
+    pub async fn fetch_optional<'e, E>(self, executor: E) -> crate::Result<Option<F::Output>>
+    where
+        E: RefExecutor<'e, Database = DB>,
+    {
+        let OwnedMap { query, mut mapper } = self;
+        let OwnedQuery {
+            query,
+            arguments,
+            error,
+        } = query;
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let mut cursor = executor.fetch_by_ref(Query {
+            query: &query,
+            arguments,
+            database: PhantomData,
+        });
+
+        let val = cursor.next().await?;
+        val.map(|row| mapper.try_map_row(row)).transpose()
+    }
+
+    pub async fn fetch_one<'e, E>(self, executor: E) -> crate::Result<F::Output>
+    where
+        E: RefExecutor<'e, Database = DB>,
+    {
+        self.fetch_optional(executor)
+            .and_then(|row| match row {
+                Some(row) => ready(Ok(row)),
+                None => ready(Err(crate::Error::RowNotFound)),
+            })
+            .await
+    }
+
+    pub async fn fetch_all<'e, E>(self, executor: E) -> crate::Result<Vec<F::Output>>
+    where
+        E: RefExecutor<'e, Database = DB>,
+    {
+        let OwnedMap { query, mut mapper } = self;
+        let OwnedQuery {
+            query,
+            arguments,
+            error,
+        } = query;
+
+        if let Some(error) = error {
+            return Err(error);
+        }
+
+        let mut cursor = executor.fetch_by_ref(Query {
+            query: &query,
+            arguments,
+            database: PhantomData,
+        });
+
+        let mut out = vec![];
+
+        while let Some(row) = cursor.next().await? {
+            out.push(mapper.try_map_row(row)?);
+        }
+
+        Ok(out)
+    }
+}
+
 // A (hopefully) temporary workaround for an internal compiler error (ICE) involving higher-ranked
 // trait bounds (HRTBs), associated types and closures.
 //
@@ -1,6 +1,6 @@
 use core::marker::PhantomData;
 
-use crate::arguments::Arguments;
+use crate::arguments::{Arguments, NamedArguments};
 use crate::database::Database;
 use crate::encode::Encode;
 use crate::executor::Execute;
@@ -15,7 +15,7 @@ where
     DB: Database,
 {
     query: &'q str,
-    arguments: <DB as Database>::Arguments,
+    pub(crate) arguments: <DB as Database>::Arguments,
     database: PhantomData<DB>,
     output: PhantomData<O>,
 }
@@ -34,6 +34,20 @@ where
         self.arguments.add(value);
         self
     }
+
+    /// Bind a value to the named parameter `name`, written as `:name` or `@name` in the query.
+    ///
+    /// See [`Query::bind_named`](crate::query::Query::bind_named).
+    #[inline]
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        DB::Arguments: NamedArguments,
+        T: Type<DB>,
+        T: Encode<DB>,
+    {
+        self.arguments.add_named(name, value);
+        self
+    }
 }
 
 impl<'q, DB, O: Send> Execute<'q, DB> for QueryAs<'q, DB, O>
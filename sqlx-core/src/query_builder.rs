@@ -0,0 +1,277 @@
+//! Runtime query builder for SQL that is not known until runtime.
+
+use std::fmt::{Display, Write};
+
+use crate::arguments::Arguments;
+use crate::database::Database;
+use crate::encode::Encode;
+use crate::query::{query, Query};
+use crate::query_as::{query_as, QueryAs};
+use crate::query_scalar::{query_scalar, QueryScalar};
+use crate::types::Type;
+
+/// A builder for SQL that is assembled at runtime, e.g. a search with optional filters.
+///
+/// SQL fragments are pushed as-is, while values are pushed as bind parameters with the
+/// placeholder syntax of the database (`?` for most SQL flavors, `$N` for Postgres) so they
+/// never need to be escaped or counted by hand.
+///
+/// ```rust,ignore
+/// let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE true");
+///
+/// if let Some(name) = name {
+///     builder.push(" AND name = ").push_bind(name);
+/// }
+///
+/// if let Some(min_age) = min_age {
+///     builder.push(" AND age >= ").push_bind(min_age);
+/// }
+///
+/// let users = builder.build().fetch_all(&mut conn).await?;
+/// ```
+pub struct QueryBuilder<DB>
+where
+    DB: Database,
+{
+    query: String,
+    init_len: usize,
+    arguments: Option<DB::Arguments>,
+}
+
+impl<DB> QueryBuilder<DB>
+where
+    DB: Database,
+{
+    /// Start a new query with the given initial SQL.
+    pub fn new(init: impl Into<String>) -> Self {
+        let query = init.into();
+
+        QueryBuilder {
+            init_len: query.len(),
+            query,
+            arguments: Some(Default::default()),
+        }
+    }
+
+    /// Append a SQL fragment to the query.
+    ///
+    /// The fragment is inserted verbatim; never push untrusted input with this method,
+    /// use [`push_bind`](QueryBuilder::push_bind) instead.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        self.sanity_check();
+
+        write!(self.query, "{}", sql).expect("error formatting `sql`");
+
+        self
+    }
+
+    /// Append a bind parameter placeholder to the query and bind the value to it.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: Type<DB>,
+        T: Encode<DB>,
+    {
+        self.sanity_check();
+
+        let arguments = self
+            .arguments
+            .as_mut()
+            .expect("BUG: arguments taken already");
+
+        arguments
+            .format_placeholder(&mut self.query)
+            .expect("error formatting placeholder");
+
+        arguments.add(value);
+
+        self
+    }
+
+    /// Start a list of items separated by `separator`, e.g. the columns of a `SELECT` or the
+    /// values of an `IN (...)` list.
+    ///
+    /// The returned [`Separated`] pushes the separator before every item but the first.
+    pub fn separated<Sep>(&mut self, separator: Sep) -> Separated<'_, DB, Sep>
+    where
+        Sep: Display,
+    {
+        self.sanity_check();
+
+        Separated {
+            builder: self,
+            separator,
+            push_separator: false,
+        }
+    }
+
+    /// Append a `VALUES` clause with one parenthesized tuple for each item of `tuples`.
+    ///
+    /// `push_tuple` is called for each item with a [`Separated`] that pushes the values of the
+    /// tuple separated by commas:
+    ///
+    /// ```rust,ignore
+    /// let mut builder = QueryBuilder::<Sqlite>::new("INSERT INTO users (id, name) ");
+    ///
+    /// builder.push_values(users, |mut b, user| {
+    ///     b.push_bind(user.id).push_bind(user.name);
+    /// });
+    ///
+    /// builder.build().execute(&mut conn).await?;
+    /// ```
+    ///
+    /// If `tuples` is empty, `VALUES` is pushed with no tuples after it which is a syntax error
+    /// on every database.
+    pub fn push_values<I, F>(&mut self, tuples: I, mut push_tuple: F) -> &mut Self
+    where
+        I: IntoIterator,
+        F: FnMut(Separated<'_, DB, &'static str>, I::Item),
+    {
+        self.sanity_check();
+
+        self.push("VALUES ");
+
+        let mut separated = self.separated(", ");
+
+        for tuple in tuples {
+            separated.push("(");
+
+            push_tuple(separated.builder.separated(", "), tuple);
+
+            separated.push_unseparated(")");
+        }
+
+        self
+    }
+
+    /// Produce a [`Query`] for the built SQL and bind parameters.
+    ///
+    /// The query borrows the SQL from the builder; call [`reset`](QueryBuilder::reset) before
+    /// building another query with the same builder.
+    ///
+    /// # Panics
+    /// If the builder was already built and not reset since.
+    pub fn build(&mut self) -> Query<'_, DB> {
+        self.sanity_check();
+
+        let mut query = query(&self.query);
+        query.arguments = self.arguments.take().expect("BUG: arguments taken already");
+
+        query
+    }
+
+    /// Produce a [`QueryAs`] for the built SQL and bind parameters that maps each row
+    /// to `O` using [`FromRow`](crate::row::FromRow).
+    ///
+    /// # Panics
+    /// If the builder was already built and not reset since.
+    pub fn build_query_as<O>(&mut self) -> QueryAs<'_, DB, O> {
+        self.sanity_check();
+
+        let mut query = query_as(&self.query);
+        query.arguments = self.arguments.take().expect("BUG: arguments taken already");
+
+        query
+    }
+
+    /// Produce a [`QueryScalar`] for the built SQL and bind parameters that maps each row
+    /// to the value of its first column.
+    ///
+    /// # Panics
+    /// If the builder was already built and not reset since.
+    pub fn build_query_scalar<O>(&mut self) -> QueryScalar<'_, DB, O> {
+        self.sanity_check();
+
+        let mut query = query_scalar(&self.query);
+        query.query.arguments = self.arguments.take().expect("BUG: arguments taken already");
+
+        query
+    }
+
+    /// Truncate the SQL back to what was passed to [`new`](QueryBuilder::new) and clear the
+    /// bind parameters, so the builder can be used again.
+    pub fn reset(&mut self) -> &mut Self {
+        self.query.truncate(self.init_len);
+        self.arguments = Some(Default::default());
+
+        self
+    }
+
+    /// Get the SQL built so far.
+    pub fn sql(&self) -> &str {
+        &self.query
+    }
+
+    /// Take the SQL built so far, discarding the bind parameters.
+    pub fn into_sql(self) -> String {
+        self.query
+    }
+
+    fn sanity_check(&self) {
+        assert!(
+            self.arguments.is_some(),
+            "QueryBuilder must be reset before reuse after `.build()`"
+        );
+    }
+}
+
+/// A list of items separated by a separator. Returned by [`QueryBuilder::separated`].
+pub struct Separated<'qb, DB, Sep>
+where
+    DB: Database,
+{
+    builder: &'qb mut QueryBuilder<DB>,
+    separator: Sep,
+    push_separator: bool,
+}
+
+impl<'qb, DB, Sep> Separated<'qb, DB, Sep>
+where
+    DB: Database,
+    Sep: Display,
+{
+    /// Push the separator (unless this is the first item) and then a SQL fragment.
+    pub fn push(&mut self, sql: impl Display) -> &mut Self {
+        if self.push_separator {
+            self.builder.push(&self.separator);
+        }
+
+        self.builder.push(sql);
+        self.push_separator = true;
+
+        self
+    }
+
+    /// Push a SQL fragment without a separator before it.
+    pub fn push_unseparated(&mut self, sql: impl Display) -> &mut Self {
+        self.builder.push(sql);
+
+        self
+    }
+
+    /// Push the separator (unless this is the first item) and then a bind parameter.
+    pub fn push_bind<T>(&mut self, value: T) -> &mut Self
+    where
+        T: Type<DB>,
+        T: Encode<DB>,
+    {
+        if self.push_separator {
+            self.builder.push(&self.separator);
+        }
+
+        self.builder.push_bind(value);
+        self.push_separator = true;
+
+        self
+    }
+
+    /// Push a bind parameter without a separator before it.
+    pub fn push_bind_unseparated<T>(&mut self, value: T) -> &mut Self
+    where
+        T: Type<DB>,
+        T: Encode<DB>,
+    {
+        self.builder.push_bind(value);
+
+        self
+    }
+}
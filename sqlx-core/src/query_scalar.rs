@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use futures_core::Stream;
+
+use crate::arguments::NamedArguments;
+use crate::database::Database;
+use crate::encode::Encode;
+use crate::executor::{Execute, RefExecutor};
+use crate::query::{query, Map, Query, TryMapRow};
+use crate::types::Type;
+
+/// Raw SQL query with bind parameters, mapped to the value of the first column of each row.
+/// Returned by [`query_scalar`](fn.query_scalar.html).
+#[must_use = "query must be executed to affect database"]
+pub struct QueryScalar<'q, DB, O>
+where
+    DB: Database,
+{
+    pub(crate) query: Query<'q, DB>,
+    output: PhantomData<O>,
+}
+
+/// Maps a row to the value of its first column. Used by [`QueryScalar`].
+///
+/// This is implemented for each database as decoding a value is not generic over the row type.
+pub struct MapScalar<O>(PhantomData<O>);
+
+impl<'q, DB, O> QueryScalar<'q, DB, O>
+where
+    DB: Database,
+{
+    /// Bind a value for use with this SQL query.
+    #[inline]
+    pub fn bind<T>(mut self, value: T) -> Self
+    where
+        T: Type<DB>,
+        T: Encode<DB>,
+    {
+        self.query = self.query.bind(value);
+        self
+    }
+
+    /// Bind a value to the named parameter `name`, written as `:name` or `@name` in the query.
+    ///
+    /// See [`Query::bind_named`].
+    #[inline]
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        DB::Arguments: NamedArguments,
+        T: Type<DB>,
+        T: Encode<DB>,
+    {
+        self.query = self.query.bind_named(name, value);
+        self
+    }
+
+    /// Map each row in the result to the value of its first column.
+    #[inline]
+    pub fn into_map(self) -> Map<'q, DB, MapScalar<O>>
+    where
+        MapScalar<O>: TryMapRow<DB>,
+    {
+        self.query.try_map(MapScalar(PhantomData))
+    }
+}
+
+impl<'q, DB, O> QueryScalar<'q, DB, O>
+where
+    DB: Database,
+    Query<'q, DB>: Execute<'q, DB>,
+    MapScalar<O>: TryMapRow<DB, Output = O>,
+{
+    /// Execute the query and get a [Stream] of the first column of each row.
+    pub fn fetch<'e: 'q, E>(self, executor: E) -> impl Stream<Item = crate::Result<O>> + Unpin + 'e
+    where
+        'q: 'e,
+        E: RefExecutor<'e, Database = DB> + 'e,
+        O: 'e,
+    {
+        self.into_map().fetch(executor)
+    }
+
+    /// Get the first column of the first row in the result.
+    pub async fn fetch_optional<'e, E>(self, executor: E) -> crate::Result<Option<O>>
+    where
+        E: RefExecutor<'e, Database = DB>,
+        'q: 'e,
+    {
+        self.into_map().fetch_optional(executor).await
+    }
+
+    /// Get the first column of the first row in the result or [`RowNotFound`] if there is no row.
+    ///
+    /// [`RowNotFound`]: crate::Error::RowNotFound
+    pub async fn fetch_one<'e, E>(self, executor: E) -> crate::Result<O>
+    where
+        E: RefExecutor<'e, Database = DB>,
+        'q: 'e,
+    {
+        self.into_map().fetch_one(executor).await
+    }
+
+    /// Get the first column of every row in the result.
+    pub async fn fetch_all<'e, E>(self, executor: E) -> crate::Result<Vec<O>>
+    where
+        E: RefExecutor<'e, Database = DB>,
+        'q: 'e,
+    {
+        self.into_map().fetch_all(executor).await
+    }
+}
+
+/// Construct a raw SQL query that is mapped to the value of the first column of each row.
+///
+/// The value is decoded as `O` with the same checks as [`Row::try_get`]; this is
+/// particularly useful for queries like `SELECT count(*) FROM ...`.
+///
+/// Returns [`QueryScalar`].
+///
+/// [`Row::try_get`]: crate::row::Row::try_get
+pub fn query_scalar<DB, O>(sql: &str) -> QueryScalar<DB, O>
+where
+    DB: Database,
+{
+    QueryScalar {
+        query: query(sql),
+        output: PhantomData,
+    }
+}
+
+#[allow(unused_macros)]
+macro_rules! impl_map_scalar_for_row {
+    ($DB:ident, $R:ident) => {
+        impl<O> crate::query::TryMapRow<$DB> for crate::query_scalar::MapScalar<O>
+        where
+            O: Unpin + crate::types::Type<$DB> + for<'c> crate::decode::Decode<'c, $DB>,
+        {
+            type Output = O;
+
+            fn try_map_row(&mut self, row: $R) -> crate::Result<O> {
+                crate::row::Row::try_get(&row, 0)
+            }
+        }
+    };
+}
@@ -211,10 +211,13 @@ pub trait HasRow<'c> {
 ///
 /// In order to use [`query_as`] the output type must implement `FromRow`.
 ///
+/// This trait is implemented for tuples of up to 16 elements which are decoded from the
+/// columns of the row in order, e.g. `query_as::<_, (i64, String)>(..)`.
+///
 /// # Deriving
 /// This trait can be automatically derived by SQLx for any struct. The generated implementation
 /// will consist of a sequence of calls to [`Row::try_get`] using the name from each
-/// struct field.
+/// struct field, or the position of each field for a tuple struct.
 ///
 /// ```rust,ignore
 /// #[derive(sqlx::FromRow)]
@@ -224,6 +227,32 @@ pub trait HasRow<'c> {
 /// }
 /// ```
 ///
+/// The name of the column can be changed for all fields with `#[sqlx(rename_all = "..")]` on
+/// the struct (one of `lowercase`, `snake_case`, `UPPERCASE`, `SCREAMING_SNAKE_CASE`,
+/// `kebab-case`, `camelCase` or `PascalCase`) and the following attributes can be used on
+/// a field:
+///
+///  * `#[sqlx(rename = "..")]`: reads the column of the given name.
+///  * `#[sqlx(default)]`: uses [`Default::default`] if there is no such column.
+///  * `#[sqlx(flatten)]`: builds the field from the same row with its own `FromRow`.
+///  * `#[sqlx(skip)]`: does not read the field but always uses [`Default::default`].
+///  * `#[sqlx(try_from = "i64")]`: decodes the column as the given type and converts that
+///    with [`TryFrom`](std::convert::TryFrom).
+///
+/// ```rust,ignore
+/// #[derive(sqlx::FromRow)]
+/// #[sqlx(rename_all = "camelCase")]
+/// struct User {
+///     user_id: i32,
+///
+///     #[sqlx(try_from = "i64")]
+///     post_count: u32,
+///
+///     #[sqlx(flatten)]
+///     address: Address,
+/// }
+/// ```
+///
 /// [`query_as`]: crate::query_as
 /// [`Row::try_get`]: crate::row::Row::try_get
 pub trait FromRow<'c, R>
@@ -331,6 +360,118 @@ macro_rules! impl_from_row_for_tuples {
             (7) -> T8;
             (8) -> T9;
         );
+
+        impl_from_row_for_tuple!($db, $r;
+            (0) -> T1;
+            (1) -> T2;
+            (2) -> T3;
+            (3) -> T4;
+            (4) -> T5;
+            (5) -> T6;
+            (6) -> T7;
+            (7) -> T8;
+            (8) -> T9;
+            (9) -> T10;
+        );
+
+        impl_from_row_for_tuple!($db, $r;
+            (0) -> T1;
+            (1) -> T2;
+            (2) -> T3;
+            (3) -> T4;
+            (4) -> T5;
+            (5) -> T6;
+            (6) -> T7;
+            (7) -> T8;
+            (8) -> T9;
+            (9) -> T10;
+            (10) -> T11;
+        );
+
+        impl_from_row_for_tuple!($db, $r;
+            (0) -> T1;
+            (1) -> T2;
+            (2) -> T3;
+            (3) -> T4;
+            (4) -> T5;
+            (5) -> T6;
+            (6) -> T7;
+            (7) -> T8;
+            (8) -> T9;
+            (9) -> T10;
+            (10) -> T11;
+            (11) -> T12;
+        );
+
+        impl_from_row_for_tuple!($db, $r;
+            (0) -> T1;
+            (1) -> T2;
+            (2) -> T3;
+            (3) -> T4;
+            (4) -> T5;
+            (5) -> T6;
+            (6) -> T7;
+            (7) -> T8;
+            (8) -> T9;
+            (9) -> T10;
+            (10) -> T11;
+            (11) -> T12;
+            (12) -> T13;
+        );
+
+        impl_from_row_for_tuple!($db, $r;
+            (0) -> T1;
+            (1) -> T2;
+            (2) -> T3;
+            (3) -> T4;
+            (4) -> T5;
+            (5) -> T6;
+            (6) -> T7;
+            (7) -> T8;
+            (8) -> T9;
+            (9) -> T10;
+            (10) -> T11;
+            (11) -> T12;
+            (12) -> T13;
+            (13) -> T14;
+        );
+
+        impl_from_row_for_tuple!($db, $r;
+            (0) -> T1;
+            (1) -> T2;
+            (2) -> T3;
+            (3) -> T4;
+            (4) -> T5;
+            (5) -> T6;
+            (6) -> T7;
+            (7) -> T8;
+            (8) -> T9;
+            (9) -> T10;
+            (10) -> T11;
+            (11) -> T12;
+            (12) -> T13;
+            (13) -> T14;
+            (14) -> T15;
+        );
+
+        impl_from_row_for_tuple!($db, $r;
+            (0) -> T1;
+            (1) -> T2;
+            (2) -> T3;
+            (3) -> T4;
+            (4) -> T5;
+            (5) -> T6;
+            (6) -> T7;
+            (7) -> T8;
+            (8) -> T9;
+            (9) -> T10;
+            (10) -> T11;
+            (11) -> T12;
+            (12) -> T13;
+            (13) -> T14;
+            (14) -> T15;
+            (15) -> T16;
+        );
     };
 }
 
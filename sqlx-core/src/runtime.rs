@@ -11,7 +11,7 @@ pub(crate) use async_std::{
     fs,
     future::timeout,
     io::prelude::ReadExt as AsyncReadExt,
-    io::{Read as AsyncRead, Write as AsyncWrite},
+    io::{Read as AsyncRead, Seek as AsyncSeek, Write as AsyncWrite},
     net::TcpStream,
     task::sleep,
     task::spawn,
@@ -23,7 +23,7 @@ pub(crate) use async_std::os::unix::net::UnixStream;
 #[cfg(feature = "runtime-tokio")]
 pub(crate) use tokio::{
     fs,
-    io::{AsyncRead, AsyncReadExt, AsyncWrite},
+    io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncWrite},
     net::TcpStream,
     task::spawn,
     time::delay_for as sleep,
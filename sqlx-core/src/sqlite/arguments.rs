@@ -5,10 +5,12 @@ use std::os::raw::{c_char, c_int};
 
 use libsqlite3_sys::{
     sqlite3_bind_blob, sqlite3_bind_double, sqlite3_bind_int, sqlite3_bind_int64,
-    sqlite3_bind_null, sqlite3_bind_text, SQLITE_OK, SQLITE_TRANSIENT,
+    sqlite3_bind_null, sqlite3_bind_text, sqlite3_context, sqlite3_result_blob,
+    sqlite3_result_double, sqlite3_result_int, sqlite3_result_int64, sqlite3_result_null,
+    sqlite3_result_text, SQLITE_OK, SQLITE_TRANSIENT,
 };
 
-use crate::arguments::Arguments;
+use crate::arguments::{Arguments, NamedArguments};
 use crate::encode::{Encode, IsNull};
 use crate::sqlite::statement::Statement;
 use crate::sqlite::Sqlite;
@@ -36,9 +38,31 @@ pub enum SqliteArgumentValue {
 pub struct SqliteArguments {
     index: usize,
     values: Vec<SqliteArgumentValue>,
+
+    // Names of the bind parameters, if they are named
+    names: Vec<Box<str>>,
 }
 
 impl SqliteArguments {
+    pub(crate) fn is_named(&self) -> bool {
+        !self.names.is_empty()
+    }
+
+    // Returns the value bound to the named parameter `name` (without its prefix)
+    pub(crate) fn named(&self, name: &str) -> crate::Result<&SqliteArgumentValue> {
+        if self.names.len() != self.values.len() {
+            return Err(arg_err!(
+                "named and positional values cannot be bound to the same query"
+            ));
+        }
+
+        self.names
+            .iter()
+            .position(|bound| &**bound == name)
+            .map(|index| &self.values[index])
+            .ok_or_else(|| arg_err!("no value is bound to named parameter `{}`", name))
+    }
+
     pub(crate) fn next(&mut self) -> Option<SqliteArgumentValue> {
         if self.index >= self.values.len() {
             return None;
@@ -69,6 +93,16 @@ impl Arguments for SqliteArguments {
     }
 }
 
+impl NamedArguments for SqliteArguments {
+    fn add_named<T>(&mut self, name: &str, value: T)
+    where
+        T: Encode<Self::Database> + Type<Self::Database>,
+    {
+        self.names.push(name.into());
+        self.add(value);
+    }
+}
+
 impl SqliteArgumentValue {
     pub(super) fn bind(&self, statement: &mut Statement, index: usize) -> crate::Result<()> {
         let handle = unsafe {
@@ -127,4 +161,34 @@ impl SqliteArgumentValue {
 
         Ok(())
     }
+
+    // Sets the value as the result of a user-defined function
+    pub(super) fn result(&self, context: *mut sqlite3_context) {
+        // https://www.sqlite.org/c3ref/result_blob.html
+        match self {
+            SqliteArgumentValue::Blob(value) => {
+                let bytes = value.as_slice();
+                let bytes_ptr = bytes.as_ptr() as *const c_void;
+                let bytes_len = bytes.len() as i32;
+
+                unsafe { sqlite3_result_blob(context, bytes_ptr, bytes_len, SQLITE_TRANSIENT()) }
+            }
+
+            SqliteArgumentValue::Text(value) => {
+                let bytes = value.as_bytes();
+                let bytes_ptr = bytes.as_ptr() as *const c_char;
+                let bytes_len = bytes.len() as i32;
+
+                unsafe { sqlite3_result_text(context, bytes_ptr, bytes_len, SQLITE_TRANSIENT()) }
+            }
+
+            SqliteArgumentValue::Double(value) => unsafe { sqlite3_result_double(context, *value) },
+
+            SqliteArgumentValue::Int(value) => unsafe { sqlite3_result_int(context, *value) },
+
+            SqliteArgumentValue::Int64(value) => unsafe { sqlite3_result_int64(context, *value) },
+
+            SqliteArgumentValue::Null => unsafe { sqlite3_result_null(context) },
+        }
+    }
 }
@@ -0,0 +1,378 @@
+//! Online backup of a database to a file or another connection.
+
+use core::ptr::{null, null_mut, NonNull};
+
+use std::ffi::CString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use futures_channel::oneshot;
+use libsqlite3_sys::{
+    sqlite3, sqlite3_backup, sqlite3_backup_finish, sqlite3_backup_init, sqlite3_backup_pagecount,
+    sqlite3_backup_remaining, sqlite3_backup_step, sqlite3_close, sqlite3_open_v2, SQLITE_BUSY,
+    SQLITE_DONE, SQLITE_LOCKED, SQLITE_OK, SQLITE_OPEN_CREATE, SQLITE_OPEN_NOMUTEX,
+    SQLITE_OPEN_READWRITE,
+};
+
+use crate::sqlite::connection::SqliteConnectionHandle;
+use crate::sqlite::{path_to_str, SqliteConnection, SqliteError};
+
+/// The database a [`SqliteBackup`] copies to.
+pub enum SqliteBackupTarget<'c> {
+    /// A database file, which is created if it does not exist.
+    Path(PathBuf),
+
+    /// The main database of another connection.
+    ///
+    /// The connection is borrowed until the backup is finished. Its handle is used on the
+    /// worker thread of the source connection once what is queued on its own worker thread
+    /// has run.
+    Connection(&'c mut SqliteConnection),
+}
+
+impl From<&'_ str> for SqliteBackupTarget<'_> {
+    fn from(path: &str) -> Self {
+        SqliteBackupTarget::Path(path.into())
+    }
+}
+
+impl From<String> for SqliteBackupTarget<'_> {
+    fn from(path: String) -> Self {
+        SqliteBackupTarget::Path(path.into())
+    }
+}
+
+impl From<&'_ Path> for SqliteBackupTarget<'_> {
+    fn from(path: &Path) -> Self {
+        SqliteBackupTarget::Path(path.into())
+    }
+}
+
+impl From<PathBuf> for SqliteBackupTarget<'_> {
+    fn from(path: PathBuf) -> Self {
+        SqliteBackupTarget::Path(path)
+    }
+}
+
+impl<'c> From<&'c mut SqliteConnection> for SqliteBackupTarget<'c> {
+    fn from(conn: &'c mut SqliteConnection) -> Self {
+        SqliteBackupTarget::Connection(conn)
+    }
+}
+
+/// The progress of a [`SqliteBackup`], reported after each step.
+#[derive(Debug, Clone, Copy)]
+pub struct SqliteBackupProgress {
+    /// The number of pages left to copy.
+    pub remaining: u32,
+
+    /// The number of pages of the source database.
+    pub page_count: u32,
+}
+
+/// An online backup of the main database of a connection. Returned by
+/// [`SqliteConnection::backup_to`].
+///
+/// The database is copied a number of pages at a time on the worker thread of the connection;
+/// between steps, the task yields so other tasks can run. A write to the source database by
+/// another connection during the backup restarts it.
+///
+/// If the future of [`run`](SqliteBackup::run) is dropped, the backup is finished on the worker
+/// thread of the source connection and the next use of either connection waits for it.
+#[must_use = "a backup does nothing unless it is `run`"]
+pub struct SqliteBackup<'c> {
+    source: &'c mut SqliteConnection,
+    target: SqliteBackupTarget<'c>,
+    pages_per_step: i32,
+    busy_delay: Duration,
+    progress: Option<Box<dyn FnMut(SqliteBackupProgress) + Send + 'c>>,
+}
+
+impl SqliteConnection {
+    /// Start an online backup of the main database of this connection to a database file or
+    /// another connection.
+    ///
+    /// ```rust,ignore
+    /// conn.backup_to("snapshot.db")
+    ///     .pages_per_step(64)
+    ///     .progress(|p| println!("{} of {} pages left", p.remaining, p.page_count))
+    ///     .run()
+    ///     .await?;
+    /// ```
+    ///
+    /// The target database is overwritten.
+    pub fn backup_to<'c, T>(&'c mut self, target: T) -> SqliteBackup<'c>
+    where
+        T: Into<SqliteBackupTarget<'c>>,
+    {
+        SqliteBackup {
+            source: self,
+            target: target.into(),
+            pages_per_step: 100,
+            busy_delay: Duration::from_millis(100),
+            progress: None,
+        }
+    }
+
+    /// Write a vacuumed copy of the main database of this connection to a new file with
+    /// [`VACUUM INTO`](https://www.sqlite.org/lang_vacuum.html#vacuuminto).
+    ///
+    /// Unlike [`backup_to`](SqliteConnection::backup_to), the copy is as small as possible but
+    /// it is made in a single statement; the file must not exist.
+    pub async fn vacuum_into(&mut self, path: impl AsRef<Path>) -> crate::Result<()> {
+        let path = path_to_str(path.as_ref())?;
+
+        crate::query::query("VACUUM INTO ?")
+            .bind(path)
+            .execute(self)
+            .await?;
+
+        Ok(())
+    }
+}
+
+impl<'c> SqliteBackup<'c> {
+    /// Set the number of pages to copy in each step, or a negative number to copy the whole
+    /// database in one step.
+    ///
+    /// Defaults to `100`.
+    pub fn pages_per_step(mut self, pages: i32) -> Self {
+        self.pages_per_step = if pages == 0 { 1 } else { pages };
+        self
+    }
+
+    /// Set how long to wait before retrying a step when the source or target database is
+    /// locked by another connection.
+    ///
+    /// Defaults to 100 milliseconds.
+    pub fn busy_delay(mut self, delay: Duration) -> Self {
+        self.busy_delay = delay;
+        self
+    }
+
+    /// Set a callback that is called with the progress of the backup after each step.
+    pub fn progress<F>(mut self, progress: F) -> Self
+    where
+        F: FnMut(SqliteBackupProgress) + Send + 'c,
+    {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    /// Run the backup to completion.
+    pub async fn run(self) -> crate::Result<()> {
+        let SqliteBackup {
+            source: source_conn,
+            target,
+            pages_per_step,
+            busy_delay,
+            progress: mut on_progress,
+        } = self;
+
+        source_conn.wait_for_pending_close().await;
+
+        let mut worker = source_conn.worker.clone();
+        let source = source_conn.handle;
+
+        let (target, target_conn) = match target {
+            SqliteBackupTarget::Path(path) => {
+                let filename = CString::new(path_to_str(&path)?)
+                    .map_err(|_| arg_err!("the backup path contains a NUL byte"))?;
+
+                let handle = worker.run(move || open(filename)).await?;
+
+                let target = Target {
+                    handle,
+                    owned: true,
+                };
+
+                (target, None)
+            }
+
+            SqliteBackupTarget::Connection(conn) => {
+                // the handle of the target is used on the worker thread of the source, so
+                // what is still queued on the worker thread of the target must run first;
+                // nothing else is queued on it while it is borrowed by the backup
+                conn.wait_for_pending_close().await;
+                conn.worker.clone().run(|| ()).await;
+
+                let target = Target {
+                    handle: conn.handle,
+                    owned: false,
+                };
+
+                (target, Some(conn))
+            }
+        };
+
+        let mut guard = BackupGuard {
+            source: source_conn,
+            target_conn,
+            backup: None,
+            target: Some(target),
+        };
+
+        // https://www.sqlite.org/c3ref/backup_finish.html#sqlite3backupinit
+        let backup = worker
+            .run(move || unsafe {
+                let backup = sqlite3_backup_init(
+                    target.handle.0.as_ptr(),
+                    b"main\0".as_ptr() as _,
+                    source.0.as_ptr(),
+                    b"main\0".as_ptr() as _,
+                );
+
+                NonNull::new(backup)
+                    .map(BackupHandle)
+                    .ok_or_else(|| SqliteError::from_connection(target.handle.0.as_ptr()))
+            })
+            .await?;
+
+        guard.backup = Some(backup);
+
+        loop {
+            // https://www.sqlite.org/c3ref/backup_finish.html#sqlite3backupstep
+            let (status, progress) = worker
+                .run(move || unsafe {
+                    let status = sqlite3_backup_step(backup.0.as_ptr(), pages_per_step);
+
+                    let progress = SqliteBackupProgress {
+                        remaining: sqlite3_backup_remaining(backup.0.as_ptr()) as u32,
+                        page_count: sqlite3_backup_pagecount(backup.0.as_ptr()) as u32,
+                    };
+
+                    (status, progress)
+                })
+                .await;
+
+            match status {
+                SQLITE_OK | SQLITE_DONE => {
+                    if let Some(callback) = &mut on_progress {
+                        callback(progress);
+                    }
+
+                    if status == SQLITE_DONE {
+                        break;
+                    }
+                }
+
+                SQLITE_BUSY | SQLITE_LOCKED => crate::runtime::sleep(busy_delay).await,
+
+                // the error is returned by `sqlite3_backup_finish`
+                _ => break,
+            }
+        }
+
+        guard.backup = None;
+        guard.target = None;
+
+        // the error of the backup, if any, is set on the target connection
+        worker
+            .run(move || unsafe {
+                let status = sqlite3_backup_finish(backup.0.as_ptr());
+
+                let result = if status == SQLITE_OK {
+                    Ok(())
+                } else {
+                    Err(SqliteError::from_connection(target.handle.0.as_ptr()).into())
+                };
+
+                target.close();
+
+                result
+            })
+            .await
+    }
+}
+
+// The target database of a backup
+#[derive(Clone, Copy)]
+struct Target {
+    handle: SqliteConnectionHandle,
+
+    // if the backup opened the database itself
+    owned: bool,
+}
+
+impl Target {
+    unsafe fn close(self) {
+        if self.owned {
+            let _ = sqlite3_close(self.handle.0.as_ptr());
+        }
+    }
+}
+
+/// Thin wrapper around [sqlite3_backup] to impl `Send`.
+#[derive(Clone, Copy)]
+struct BackupHandle(NonNull<sqlite3_backup>);
+
+// A backup is only used on the worker thread of its source connection
+unsafe impl Send for BackupHandle {}
+
+// Finishes a backup that did not run to completion, e.g. because its future was dropped
+struct BackupGuard<'c> {
+    source: &'c mut SqliteConnection,
+    target_conn: Option<&'c mut SqliteConnection>,
+    backup: Option<BackupHandle>,
+    target: Option<Target>,
+}
+
+impl Drop for BackupGuard<'_> {
+    fn drop(&mut self) {
+        let backup = self.backup.take();
+        let target = self.target.take();
+
+        if backup.is_none() && target.is_none() {
+            return;
+        }
+
+        let (source_finished, source_wait) = oneshot::channel();
+        let (target_finished, target_wait) = oneshot::channel();
+
+        self.source.worker.execute(move || {
+            unsafe {
+                if let Some(backup) = backup {
+                    let _ = sqlite3_backup_finish(backup.0.as_ptr());
+                }
+
+                if let Some(target) = target {
+                    target.close();
+                }
+            }
+
+            let _ = source_finished.send(());
+            let _ = target_finished.send(());
+        });
+
+        // the backup uses the handles of both connections, so instead of blocking until it
+        // is finished, the next use of either connection waits for it
+        self.source.pending_close = Some(source_wait);
+
+        if let Some(conn) = &mut self.target_conn {
+            conn.pending_close = Some(target_wait);
+        }
+    }
+}
+
+fn open(filename: CString) -> crate::Result<SqliteConnectionHandle> {
+    let mut handle: *mut sqlite3 = null_mut();
+    let flags = SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE | SQLITE_OPEN_NOMUTEX;
+
+    // <https://www.sqlite.org/c3ref/open.html>
+    let status = unsafe { sqlite3_open_v2(filename.as_ptr(), &mut handle, flags, null()) };
+
+    let handle = NonNull::new(handle)
+        .expect("SQLite is unable to allocate memory to hold the sqlite3 object");
+
+    if status != SQLITE_OK {
+        let error = SqliteError::from_connection(handle.as_ptr());
+
+        unsafe {
+            let _ = sqlite3_close(handle.as_ptr());
+        }
+
+        return Err(error.into());
+    }
+
+    Ok(SqliteConnectionHandle(handle))
+}
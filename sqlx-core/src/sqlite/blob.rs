@@ -0,0 +1,429 @@
+//! Incremental I/O of a BLOB value.
+
+use core::ffi::c_void;
+use core::pin::Pin;
+use core::ptr::{null_mut, NonNull};
+use core::task::{Context, Poll};
+
+use std::convert::TryFrom;
+use std::ffi::CString;
+use std::future::Future;
+use std::io::{self, SeekFrom};
+use std::os::raw::c_int;
+
+use futures_channel::oneshot;
+use libsqlite3_sys::{
+    sqlite3_blob, sqlite3_blob_bytes, sqlite3_blob_close, sqlite3_blob_open, sqlite3_blob_read,
+    sqlite3_blob_write, SQLITE_OK,
+};
+
+use crate::runtime::{AsyncRead, AsyncSeek, AsyncWrite};
+use crate::sqlite::connection::SqliteConnectionHandle;
+use crate::sqlite::{SqliteConnection, SqliteError};
+
+/// A handle to read and write a BLOB value incrementally, without loading the whole value
+/// into memory. Returned by [`SqliteConnection::open_blob`].
+///
+/// `SqliteBlob` implements the `AsyncRead`, `AsyncWrite` and `AsyncSeek` traits of the
+/// runtime. The size of a BLOB cannot be changed; a write past its end writes nothing. To store
+/// a new value of a known size, insert a `zeroblob(N)` and write to it:
+///
+/// ```rust,ignore
+/// let row_id = sqlx::query("INSERT INTO attachments (data) VALUES (zeroblob(?))")
+///     .bind(len)
+///     .execute(&mut conn)
+///     .await?;
+///
+/// let mut blob = conn.open_blob("attachments", "data", row_id, true).await?;
+///
+/// io::copy(&mut file, &mut blob).await?;
+/// ```
+///
+/// The connection is borrowed for the lifetime of the handle. A change to the row by another
+/// connection expires the handle; every read or write after that fails.
+pub struct SqliteBlob<'c> {
+    conn: &'c mut SqliteConnection,
+    handle: BlobHandle,
+    len: u64,
+    position: u64,
+    pending: Option<Pending>,
+}
+
+// I/O that is running on the worker thread
+enum Pending {
+    Read(oneshot::Receiver<io::Result<Vec<u8>>>),
+    Write(oneshot::Receiver<io::Result<usize>>),
+}
+
+/// Thin wrapper around [sqlite3_blob] to impl `Send`.
+#[derive(Clone, Copy)]
+struct BlobHandle(NonNull<sqlite3_blob>);
+
+// A BLOB handle is only used on the worker thread of its connection
+unsafe impl Send for BlobHandle {}
+
+impl SqliteConnection {
+    /// Open the BLOB in `column` of the row with `row_id` in `table` for incremental I/O.
+    ///
+    /// The BLOB can only be written to if it is opened as `writable`.
+    ///
+    /// See [`SqliteBlob`].
+    pub async fn open_blob(
+        &mut self,
+        table: &str,
+        column: &str,
+        row_id: i64,
+        writable: bool,
+    ) -> crate::Result<SqliteBlob<'_>> {
+        let table = CString::new(table)
+            .map_err(|_| arg_err!("the table name {:?} contains a NUL byte", table))?;
+
+        let column = CString::new(column)
+            .map_err(|_| arg_err!("the column name {:?} contains a NUL byte", column))?;
+
+        let conn = self.handle;
+
+        self.wait_for_pending_close().await;
+
+        // https://www.sqlite.org/c3ref/blob_open.html
+        let (handle, len) = self
+            .worker
+            .run(move || unsafe {
+                let mut handle = null_mut();
+
+                let status = sqlite3_blob_open(
+                    conn.0.as_ptr(),
+                    b"main\0".as_ptr() as _,
+                    table.as_ptr(),
+                    column.as_ptr(),
+                    row_id,
+                    writable as c_int,
+                    &mut handle,
+                );
+
+                if status != SQLITE_OK {
+                    // the handle is set to null on an error
+                    return Err(SqliteError::from_connection(conn.0.as_ptr()));
+                }
+
+                let handle = BlobHandle(NonNull::new(handle).unwrap());
+
+                Ok((handle, sqlite3_blob_bytes(handle.0.as_ptr()) as u64))
+            })
+            .await?;
+
+        Ok(SqliteBlob {
+            conn: self,
+            handle,
+            len,
+            position: 0,
+            pending: None,
+        })
+    }
+}
+
+impl SqliteBlob<'_> {
+    /// Returns the size of the BLOB in bytes.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the BLOB is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The number of bytes from the position to the end of the BLOB, up to `max`
+    fn available(&self, max: usize) -> usize {
+        (self.len.saturating_sub(self.position) as usize).min(max)
+    }
+
+    // The position as the offset of a read or write
+    fn offset(&self) -> io::Result<c_int> {
+        c_int::try_from(self.position).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the position is past the largest offset of a BLOB",
+            )
+        })
+    }
+
+    fn poll_pending<T>(
+        receiver: &mut oneshot::Receiver<io::Result<T>>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<T>> {
+        Pin::new(receiver).poll(cx).map(|result| {
+            result.unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::Other, "worker stopped")))
+        })
+    }
+
+    fn poll_read_blob(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        if self.pending.is_none() {
+            let len = self.available(buf.len());
+
+            if len == 0 {
+                return Poll::Ready(Ok(0));
+            }
+
+            let offset = match self.offset() {
+                Ok(offset) => offset,
+                Err(error) => return Poll::Ready(Err(error)),
+            };
+
+            let (sender, receiver) = oneshot::channel();
+            let handle = self.handle;
+            let conn = self.conn.handle;
+
+            // https://www.sqlite.org/c3ref/blob_read.html
+            self.conn.worker.execute(move || {
+                let mut data = vec![0; len];
+
+                let status = unsafe {
+                    sqlite3_blob_read(
+                        handle.0.as_ptr(),
+                        data.as_mut_ptr() as *mut c_void,
+                        len as c_int,
+                        offset,
+                    )
+                };
+
+                let _ = sender.send(check_status(conn, status).map(|_| data));
+            });
+
+            self.pending = Some(Pending::Read(receiver));
+        }
+
+        let data = match &mut self.pending {
+            Some(Pending::Read(receiver)) => match Self::poll_pending(receiver, cx) {
+                Poll::Ready(data) => data,
+                Poll::Pending => return Poll::Pending,
+            },
+
+            _ => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "a write to the BLOB is in progress",
+                )))
+            }
+        };
+
+        self.pending = None;
+
+        // if `buf` is smaller than on the first poll, the rest of the data is read again
+        let data = data?;
+        let len = data.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&data[..len]);
+        self.position += len as u64;
+
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_write_blob(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        if self.pending.is_none() {
+            let len = self.available(buf.len());
+
+            if len == 0 {
+                return Poll::Ready(Ok(0));
+            }
+
+            let offset = match self.offset() {
+                Ok(offset) => offset,
+                Err(error) => return Poll::Ready(Err(error)),
+            };
+
+            let (sender, receiver) = oneshot::channel();
+            let handle = self.handle;
+            let conn = self.conn.handle;
+            let data = buf[..len].to_vec();
+
+            // https://www.sqlite.org/c3ref/blob_write.html
+            self.conn.worker.execute(move || {
+                let status = unsafe {
+                    sqlite3_blob_write(
+                        handle.0.as_ptr(),
+                        data.as_ptr() as *const c_void,
+                        len as c_int,
+                        offset,
+                    )
+                };
+
+                let _ = sender.send(check_status(conn, status).map(|_| len));
+            });
+
+            self.pending = Some(Pending::Write(receiver));
+        }
+
+        let len = match &mut self.pending {
+            Some(Pending::Write(receiver)) => match Self::poll_pending(receiver, cx) {
+                Poll::Ready(len) => len,
+                Poll::Pending => return Poll::Pending,
+            },
+
+            _ => {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "a read of the BLOB is in progress",
+                )))
+            }
+        };
+
+        self.pending = None;
+
+        let len = len?;
+        self.position += len as u64;
+
+        Poll::Ready(Ok(len))
+    }
+
+    fn poll_complete_pending(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let result = match &mut self.pending {
+            Some(Pending::Write(receiver)) => match Self::poll_pending(receiver, cx) {
+                Poll::Ready(len) => len.map(|len| self.position += len as u64),
+                Poll::Pending => return Poll::Pending,
+            },
+
+            // a read is discarded
+            Some(Pending::Read(receiver)) => match Self::poll_pending(receiver, cx) {
+                Poll::Ready(_) => Ok(()),
+                Poll::Pending => return Poll::Pending,
+            },
+
+            None => Ok(()),
+        };
+
+        self.pending = None;
+
+        Poll::Ready(result)
+    }
+
+    fn seek_to(&mut self, position: SeekFrom) -> io::Result<u64> {
+        let position = match position {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => offset_by(self.len, offset),
+            SeekFrom::Current(offset) => offset_by(self.position, offset),
+        };
+
+        match position {
+            Some(position) => {
+                self.position = position;
+
+                Ok(position)
+            }
+
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            )),
+        }
+    }
+}
+
+impl AsyncRead for SqliteBlob<'_> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_read_blob(cx, buf)
+    }
+}
+
+impl AsyncWrite for SqliteBlob<'_> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.get_mut().poll_write_blob(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.get_mut().poll_complete_pending(cx)
+    }
+
+    #[cfg(feature = "runtime-async-std")]
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.get_mut().poll_complete_pending(cx)
+    }
+
+    #[cfg(feature = "runtime-tokio")]
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context) -> Poll<io::Result<()>> {
+        self.get_mut().poll_complete_pending(cx)
+    }
+}
+
+#[cfg(feature = "runtime-async-std")]
+impl AsyncSeek for SqliteBlob<'_> {
+    fn poll_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        position: SeekFrom,
+    ) -> Poll<io::Result<u64>> {
+        let this = self.get_mut();
+
+        match this.poll_complete_pending(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(this.seek_to(position)),
+            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(feature = "runtime-tokio")]
+impl AsyncSeek for SqliteBlob<'_> {
+    fn start_seek(
+        self: Pin<&mut Self>,
+        cx: &mut Context,
+        position: SeekFrom,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        match this.poll_complete_pending(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(this.seek_to(position).map(|_| ())),
+            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_complete(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<io::Result<u64>> {
+        Poll::Ready(Ok(self.position))
+    }
+}
+
+impl Drop for SqliteBlob<'_> {
+    fn drop(&mut self) {
+        let handle = self.handle;
+        let (sender, receiver) = oneshot::channel();
+
+        // https://www.sqlite.org/c3ref/blob_close.html
+        // runs after any I/O that is still pending on the worker thread and before the
+        // connection, which is also closed on the worker thread
+        self.conn.worker.execute(move || {
+            unsafe {
+                let _ = sqlite3_blob_close(handle.0.as_ptr());
+            }
+
+            let _ = sender.send(());
+        });
+
+        // the next query on the connection waits for the close, as it uses the handle of the
+        // connection on its own thread
+        self.conn.pending_close = Some(receiver);
+    }
+}
+
+fn offset_by(position: u64, offset: i64) -> Option<u64> {
+    if offset < 0 {
+        position.checked_sub(offset.wrapping_neg() as u64)
+    } else {
+        position.checked_add(offset as u64)
+    }
+}
+
+fn check_status(conn: SqliteConnectionHandle, status: c_int) -> io::Result<()> {
+    if status != SQLITE_OK {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            SqliteError::from_connection(conn.0.as_ptr()),
+        ));
+    }
+
+    Ok(())
+}
@@ -1,18 +1,22 @@
 use core::ptr::{null, null_mut, NonNull};
 
-use std::collections::HashMap;
-use std::convert::TryInto;
-use std::ffi::CString;
+use std::convert::{TryFrom, TryInto};
+use std::mem;
+use std::os::raw::c_int;
 
+use futures_channel::oneshot;
 use futures_core::future::BoxFuture;
 use futures_util::future;
 use libsqlite3_sys::{
-    sqlite3, sqlite3_close, sqlite3_extended_result_codes, sqlite3_open_v2, SQLITE_OK,
-    SQLITE_OPEN_CREATE, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_READWRITE, SQLITE_OPEN_SHAREDCACHE,
+    sqlite3, sqlite3_busy_timeout, sqlite3_close, sqlite3_extended_result_codes, sqlite3_open_v2,
+    SQLITE_OK,
 };
 
+use crate::cache::StatementCache;
 use crate::connection::{Connect, Connection};
 use crate::executor::Executor;
+use crate::sqlite::hooks::Hooks;
+use crate::sqlite::options::SqliteOptions;
 use crate::sqlite::statement::Statement;
 use crate::sqlite::worker::Worker;
 
@@ -24,6 +28,94 @@ use crate::url::Url;
 pub(super) struct SqliteConnectionHandle(pub(super) NonNull<sqlite3>);
 
 /// A connection to a [Sqlite](struct.Sqlite.html) database.
+///
+/// ### Connection URL
+/// The path of the connection URL is the path of the database file, relative to the current
+/// working directory unless it starts with `/`:
+///
+/// ```text
+/// sqlite://<path>[?mode=<mode>][&cache=<cache>][&immutable=<immutable>]
+/// ```
+/// where
+/// ```text
+/// path = percent (URL) encoded path on the local machine
+/// mode = ro | rw | rwc | memory
+/// cache = shared | private
+/// immutable = true | false
+/// ```
+///
+/// * `mode` opens the database read-only (`ro`), read-write (`rw`), read-write and creates it
+///   if it does not exist (`rwc`, the default) or as an in-memory database (`memory`) named by
+///   the path, which is shared by the connections of a process that use the shared cache.
+/// * `cache` enables (`shared`, the default) or disables (`private`) the
+///   [shared cache](https://www.sqlite.org/sharedcache.html).
+/// * `immutable` declares that the database file cannot change, e.g. because it is on
+///   read-only media, so SQLite does not lock it or check it for changes.
+///
+/// `sqlite::memory:` opens a new, private in-memory database.
+///
+/// A [URI filename](https://www.sqlite.org/uri.html) is passed to SQLite as-is, with its own
+/// query parameters (e.g. `sqlite:file:data.db?mode=ro&vfs=unix-dotfile`).
+///
+/// ### In-Memory Databases
+/// Each connection of a [`SqlitePool`](type.SqlitePool.html) to `sqlite::memory:` has a
+/// database of its own. For the connections of a pool, e.g. in tests, to share an in-memory
+/// database, name it:
+///
+/// ```text
+/// sqlite://test_db?mode=memory
+/// sqlite:file:test_db?mode=memory&cache=shared
+/// ```
+///
+/// SQLite destroys such a database when its last connection is closed, so the pool keeps a
+/// connection to it open, which is never reaped, until the pool is closed.
+///
+/// ### Pragmas
+/// These parameters set the [pragmas](https://www.sqlite.org/pragma.html) of every connection
+/// when it is opened:
+///
+/// ```text
+/// journal_mode = delete | truncate | persist | memory | wal | off
+/// synchronous = off | normal | full | extra
+/// foreign_keys = true | false
+/// busy_timeout = <milliseconds>
+/// ```
+///
+/// `journal_mode`, `synchronous` and `foreign_keys` are left as they are set by the database
+/// (or the defaults of SQLite) unless they are given. Note that `journal_mode=wal` is
+/// persistent: it converts the database file to [WAL](https://www.sqlite.org/wal.html) mode
+/// for every later connection. `busy_timeout` is `5000` by default.
+///
+/// When the database is locked by another connection, a statement retries until
+/// `busy_timeout` elapses before it fails with `SQLITE_BUSY`; this is what lets a
+/// [`SqlitePool`](type.SqlitePool.html) write from several connections at once.
+///
+/// ### Statement Cache
+/// `statement_cache_capacity` sets the number of prepared statements the connection keeps for
+/// reuse (`100` by default); when it is exceeded, the least recently used statement is
+/// finalized.
+///
+/// ### Worker Threads
+/// The calls to SQLite of a connection run on a thread of its own by default. With
+/// `worker=shared`, they run on a pool of threads that is shared by all such connections of the
+/// process instead, which suits an application with many connections, e.g. to a database per
+/// tenant:
+///
+/// ```text
+/// sqlite://tenant_1.db?worker=shared&worker_threads=8
+/// ```
+///
+/// `worker_threads` is the number of threads of the pool (`4` by default); it is only used by
+/// the first connection that starts the pool (a different number given later is logged as a
+/// warning and ignored). A statement that waits for a lock held by another
+/// connection occupies a thread of the pool while it waits, so the pool should have more
+/// threads than connections that write at the same time.
+///
+/// ### Extensions
+/// Each `extension` parameter is the path of an [extension](https://www.sqlite.org/loadext.html)
+/// to load when the connection is opened, with the name of its entry point derived from the
+/// file name (e.g. `sqlite://data.db?extension=mod_spatialite&extension=fts5`). See
+/// [`load_extension`](#method.load_extension).
 pub struct SqliteConnection {
     pub(super) handle: SqliteConnectionHandle,
     pub(super) worker: Worker,
@@ -31,7 +123,13 @@ pub struct SqliteConnection {
     pub(super) statement: Option<Statement>,
     // Storage of persistent statements
     pub(super) statements: Vec<Statement>,
-    pub(super) statement_by_query: HashMap<String, usize>,
+    // Cache of the index of the persistent statement of a query
+    pub(super) statement_by_query: StatementCache<usize>,
+    // The user data of the update, commit and rollback hooks, once they are registered
+    pub(super) hooks: Option<Hooks>,
+    // Completes once the messages that a drop queued on a worker thread, e.g. to close a blob
+    // or to finish a backup, have run
+    pub(super) pending_close: Option<oneshot::Receiver<()>>,
 }
 
 // A SQLite3 handle is safe to send between threads, provided not more than
@@ -45,24 +143,20 @@ pub struct SqliteConnection {
 
 unsafe impl Send for SqliteConnectionHandle {}
 
-async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteConnection> {
-    let mut worker = Worker::new();
+async fn establish(options: &SqliteOptions) -> crate::Result<SqliteConnection> {
+    let mut worker = Worker::new(options.worker);
 
-    // By default, we connect to an in-memory database.
-    // TODO: Handle the error when there are internal NULs in the database URL
-    let filename = CString::new(url?.path_decoded().to_string()).unwrap();
+    let filename = options.filename.clone();
+    let flags = options.flags;
+    let busy_timeout = options
+        .busy_timeout
+        .as_millis()
+        .min(c_int::max_value() as u128) as c_int;
 
     let handle = worker
         .run(move || -> crate::Result<SqliteConnectionHandle> {
             let mut handle = null_mut();
 
-            // [SQLITE_OPEN_NOMUTEX] will instruct [sqlite3_open_v2] to return an error if it
-            // cannot satisfy our wish for a thread-safe, lock-free connection object
-            let flags = SQLITE_OPEN_READWRITE
-                | SQLITE_OPEN_CREATE
-                | SQLITE_OPEN_NOMUTEX
-                | SQLITE_OPEN_SHAREDCACHE;
-
             // <https://www.sqlite.org/c3ref/open.html>
             let status = unsafe { sqlite3_open_v2(filename.as_ptr(), &mut handle, flags, null()) };
 
@@ -87,6 +181,13 @@ async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteCon
                 sqlite3_extended_result_codes(handle, 1);
             }
 
+            // Retry for up to `busy_timeout` instead of failing with `SQLITE_BUSY` when
+            // the database is locked by another connection
+            // https://www.sqlite.org/c3ref/busy_timeout.html
+            unsafe {
+                sqlite3_busy_timeout(handle, busy_timeout);
+            }
+
             Ok(SqliteConnectionHandle(NonNull::new(handle).unwrap()))
         })
         .await?;
@@ -96,7 +197,9 @@ async fn establish(url: Result<Url, url::ParseError>) -> crate::Result<SqliteCon
         handle,
         statement: None,
         statements: Vec::with_capacity(10),
-        statement_by_query: HashMap::with_capacity(10),
+        statement_by_query: StatementCache::new(options.statement_cache_capacity),
+        hooks: None,
+        pending_close: None,
     })
 }
 
@@ -105,6 +208,32 @@ impl SqliteConnection {
     pub(super) fn handle(&mut self) -> *mut sqlite3 {
         self.handle.0.as_ptr()
     }
+
+    // Waits for the messages that a drop queued on a worker thread to run, as the handle must
+    // not be used by two threads at once; this must be awaited before the handle is used, as
+    // the messages may run on the worker thread of another connection (the source of a backup)
+    pub(super) async fn wait_for_pending_close(&mut self) {
+        if let Some(pending_close) = &mut self.pending_close {
+            // the worker only drops the sender if it stopped
+            let _ = pending_close.await;
+
+            self.pending_close = None;
+        }
+    }
+
+    // Finalizes statements on the worker thread, as `Drop` does; this must only be called once
+    // the pending close was awaited, and the handle must not be used again until it is awaited
+    pub(super) fn finalize_on_worker<T: Send + 'static>(&mut self, statements: T) {
+        let (sender, receiver) = oneshot::channel();
+
+        self.worker.execute(move || {
+            drop(statements);
+
+            let _ = sender.send(());
+        });
+
+        self.pending_close = Some(receiver);
+    }
 }
 
 impl Connect for SqliteConnection {
@@ -116,46 +245,88 @@ impl Connect for SqliteConnection {
         let url = url.try_into();
 
         Box::pin(async move {
-            let mut conn = establish(url).await?;
+            let options = SqliteOptions::parse(&url?)?;
+            let mut conn = establish(&options).await?;
 
-            // https://www.sqlite.org/wal.html
+            for extension in &options.extensions {
+                conn.load_extension(extension, None).await?;
+            }
 
-            // language=SQLite
-            conn.execute(
-                r#"
-PRAGMA journal_mode = WAL;
-PRAGMA synchronous = NORMAL;
-                "#,
-            )
-            .await?;
+            let pragmas = options.pragmas();
+
+            if !pragmas.is_empty() {
+                conn.execute(&*pragmas).await?;
+            }
 
             Ok(conn)
         })
     }
+
+    fn needs_keep_alive(url: &str) -> bool {
+        Url::try_from(url)
+            .ok()
+            .and_then(|url| SqliteOptions::parse(&url).ok())
+            .map_or(false, |options| options.is_shared_memory())
+    }
 }
 
 impl Connection for SqliteConnection {
     fn close(self) -> BoxFuture<'static, crate::Result<()>> {
-        // All necessary behavior is handled on drop
-        Box::pin(future::ok(()))
+        let mut worker = self.worker.clone();
+
+        // The connection is closed on drop; this waits until it is
+        drop(self);
+
+        Box::pin(async move {
+            worker.run(|| ()).await;
+
+            Ok(())
+        })
     }
 
     fn ping(&mut self) -> BoxFuture<crate::Result<()>> {
         // For SQLite connections, PING does effectively nothing
         Box::pin(future::ok(()))
     }
+
+    fn clear_cached_statements(&mut self) -> BoxFuture<crate::Result<()>> {
+        Box::pin(async move {
+            self.wait_for_pending_close().await;
+
+            self.statement_by_query.clear();
+
+            let statements = mem::replace(&mut self.statements, Vec::new());
+
+            self.finalize_on_worker(statements);
+            self.wait_for_pending_close().await;
+
+            Ok(())
+        })
+    }
 }
 
 impl Drop for SqliteConnection {
     fn drop(&mut self) {
-        // Drop all statements first
-        self.statements.clear();
-        drop(self.statement.take());
-
-        // Next close the statement
-        // https://sqlite.org/c3ref/close.html
-        unsafe {
-            let _ = sqlite3_close(self.handle());
-        }
+        let statements = mem::replace(&mut self.statements, Vec::new());
+        let statement = self.statement.take();
+        let hooks = self.hooks.take();
+        let handle = self.handle;
+
+        // The connection is closed on the worker thread after the messages that are still
+        // queued, e.g. to close a blob, as the handle must not be used by two threads at once
+        self.worker.execute(move || {
+            // Drop all statements first
+            drop(statements);
+            drop(statement);
+
+            // Next close the connection
+            // https://sqlite.org/c3ref/close.html
+            unsafe {
+                let _ = sqlite3_close(handle.0.as_ptr());
+            }
+
+            // The hooks can only be freed once they can no longer be called
+            drop(hooks);
+        });
     }
 }
@@ -61,9 +61,13 @@ async fn next<'a, 'c: 'a, 'q: 'a>(
 ) -> crate::Result<Option<SqliteRow<'a>>> {
     let conn = cursor.source.resolve().await?;
 
+    conn.wait_for_pending_close().await;
+
     loop {
         if cursor.statement.is_none() {
-            let key = conn.prepare(&mut cursor.query, cursor.arguments.is_some())?;
+            let key = conn
+                .prepare(&mut cursor.query, cursor.arguments.is_some())
+                .await?;
 
             if let Some(arguments) = &mut cursor.arguments {
                 conn.statement_mut(key).bind(arguments)?;
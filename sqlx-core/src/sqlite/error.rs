@@ -17,6 +17,13 @@ pub struct SqliteError {
 // https://www.sqlite.org/c3ref/errcode.html
 
 impl SqliteError {
+    pub(super) fn new(code: c_int, message: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.to_owned(),
+        }
+    }
+
     pub(super) fn from_connection(conn: *mut sqlite3) -> Self {
         let code: c_int = unsafe { sqlite3_extended_errcode(conn) };
 
@@ -1,3 +1,5 @@
+use std::mem;
+
 use futures_core::future::BoxFuture;
 
 use libsqlite3_sys::sqlite3_changes;
@@ -11,14 +13,11 @@ use crate::sqlite::type_info::SqliteType;
 use crate::sqlite::{Sqlite, SqliteConnection, SqliteTypeInfo};
 
 impl SqliteConnection {
-    pub(super) fn prepare(
+    pub(super) async fn prepare(
         &mut self,
         query: &mut &str,
         persistent: bool,
     ) -> crate::Result<Option<usize>> {
-        // TODO: Revisit statement caching and allow cache expiration by using a
-        //       generational index
-
         if !persistent {
             // A non-persistent query will be immediately prepared and returned,
             // regardless of the current state of the cache
@@ -26,30 +25,51 @@ impl SqliteConnection {
             return Ok(None);
         }
 
-        if let Some(key) = self.statement_by_query.get(&**query) {
-            let statement = &mut self.statements[*key];
+        if let Some(&mut key) = self.statement_by_query.get_mut(query) {
+            let statement = &mut self.statements[key];
 
             // Adjust the passed in query string as if [string3_prepare]
             // did the tail parsing
-            *query = &query[statement.tail..];
+            *query = query[statement.tail..].trim();
 
             // As this statement has very likely been used before, we reset
             // it to clear the bindings and its program state
             statement.reset();
 
-            return Ok(Some(*key));
+            return Ok(Some(key));
         }
 
         // Prepare a new statement object; ensuring to tell SQLite that this will be stored
         // for a "long" time and re-used multiple times
 
-        let query_key = query.to_owned();
+        let query_key = *query;
         let statement = Statement::new(self, query, true)?;
 
-        let key = self.statements.len();
+        // When the cache is full, the least recently used statement is finalized on the worker
+        // thread and its storage is reused
+        let evicted = if self.statement_by_query.is_full() {
+            self.statement_by_query.remove_lru()
+        } else {
+            None
+        };
+
+        let key = match evicted {
+            Some(key) => {
+                let evicted = mem::replace(&mut self.statements[key], statement);
+
+                self.finalize_on_worker(evicted);
+                self.wait_for_pending_close().await;
+
+                key
+            }
+
+            None => {
+                self.statements.push(statement);
+                self.statements.len() - 1
+            }
+        };
 
         self.statement_by_query.insert(query_key, key);
-        self.statements.push(statement);
 
         Ok(Some(key))
     }
@@ -95,8 +115,10 @@ impl Executor for SqliteConnection {
             let (mut query, mut arguments) = query.into_parts();
 
             Box::pin(async move {
+                self.wait_for_pending_close().await;
+
                 loop {
-                    let key = self.prepare(&mut query, arguments.is_some())?;
+                    let key = self.prepare(&mut query, arguments.is_some()).await?;
                     let statement = self.statement_mut(key);
 
                     if let Some(arguments) = &mut arguments {
@@ -134,7 +156,10 @@ impl Executor for SqliteConnection {
     {
         Box::pin(async move {
             let (mut query, _) = query.into_parts();
-            let key = self.prepare(&mut query, false)?;
+
+            self.wait_for_pending_close().await;
+
+            let key = self.prepare(&mut query, false).await?;
             let statement = self.statement_mut(key);
 
             // First let's attempt to describe what we can about parameter types
@@ -0,0 +1,81 @@
+//! Loading of extensions from shared libraries.
+
+use core::ptr::{null, null_mut};
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::path::Path;
+
+use libsqlite3_sys::{
+    sqlite3_enable_load_extension, sqlite3_free, sqlite3_load_extension, SQLITE_OK,
+};
+
+use crate::sqlite::{path_to_str, SqliteConnection, SqliteError};
+
+impl SqliteConnection {
+    /// Load an [extension](https://www.sqlite.org/loadext.html) from a shared library into
+    /// this connection, e.g. `mod_spatialite`.
+    ///
+    /// If the `entry_point` is `None`, SQLite derives the name of the function that
+    /// initializes the extension from the name of the file. If the `path` has no extension,
+    /// the extension of shared libraries of the platform is tried as well.
+    ///
+    /// Loading extensions is only enabled for the duration of this call, so a query cannot load
+    /// one with the `load_extension()` SQL function. Extensions can also be loaded when the
+    /// connection is opened with the `extension` parameter of the
+    /// [connection URL](struct.SqliteConnection.html#extensions).
+    pub async fn load_extension(
+        &mut self,
+        path: impl AsRef<Path>,
+        entry_point: Option<&str>,
+    ) -> crate::Result<()> {
+        let path = CString::new(path_to_str(path.as_ref())?)
+            .map_err(|_| arg_err!("the extension path contains a NUL byte"))?;
+
+        let entry_point = entry_point
+            .map(CString::new)
+            .transpose()
+            .map_err(|_| arg_err!("the extension entry point contains a NUL byte"))?;
+
+        let conn = self.handle;
+
+        self.wait_for_pending_close().await;
+
+        self.worker
+            .run(move || unsafe {
+                let db = conn.0.as_ptr();
+                let mut message: *mut c_char = null_mut();
+
+                // https://www.sqlite.org/c3ref/enable_load_extension.html
+                sqlite3_enable_load_extension(db, 1);
+
+                // https://www.sqlite.org/c3ref/load_extension.html
+                let status = sqlite3_load_extension(
+                    db,
+                    path.as_ptr(),
+                    entry_point.as_ref().map_or(null(), |entry| entry.as_ptr()),
+                    &mut message,
+                );
+
+                sqlite3_enable_load_extension(db, 0);
+
+                if status == SQLITE_OK {
+                    return Ok(());
+                }
+
+                // the error message is returned instead of being set on the connection
+                let error = if message.is_null() {
+                    SqliteError::new(status, "unable to load the extension")
+                } else {
+                    let error =
+                        SqliteError::new(status, &CStr::from_ptr(message).to_string_lossy());
+                    sqlite3_free(message as _);
+
+                    error
+                };
+
+                Err(error.into())
+            })
+            .await
+    }
+}
@@ -0,0 +1,436 @@
+//! User-defined SQL functions, aggregates and collations.
+
+use core::ffi::c_void;
+use core::mem::size_of;
+use core::slice;
+
+use std::cmp::Ordering;
+use std::ffi::CString;
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use libsqlite3_sys::{
+    sqlite3_aggregate_context, sqlite3_context, sqlite3_create_collation_v2,
+    sqlite3_create_function_v2, sqlite3_result_error, sqlite3_result_error_nomem,
+    sqlite3_user_data, sqlite3_value, SQLITE_DETERMINISTIC, SQLITE_OK, SQLITE_UTF8,
+};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::sqlite::connection::SqliteConnectionHandle;
+use crate::sqlite::value::SqliteValue;
+use crate::sqlite::{Sqlite, SqliteArgumentValue, SqliteConnection, SqliteError};
+use crate::types::{Type, TypeInfo};
+use crate::value::RawValue;
+
+/// The arguments of a call to a user-defined function.
+pub struct SqliteFunctionArgs<'a> {
+    values: &'a [*mut sqlite3_value],
+}
+
+impl<'a> SqliteFunctionArgs<'a> {
+    /// Returns the number of arguments.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the function was called without arguments.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Decode an argument, like [`Row::try_get`](crate::row::Row::try_get).
+    pub fn try_get<T>(&self, index: usize) -> crate::Result<T>
+    where
+        T: Type<Sqlite>,
+        T: Decode<'a, Sqlite>,
+    {
+        let value = self.try_get_raw(index)?;
+
+        if let Some(expected_ty) = value.type_info() {
+            if !expected_ty.compatible(&T::type_info()) {
+                return Err(crate::Error::mismatched_types::<Sqlite, T>(expected_ty));
+            }
+        }
+
+        T::decode(value)
+    }
+
+    #[doc(hidden)]
+    pub fn try_get_raw(&self, index: usize) -> crate::Result<SqliteValue<'a>> {
+        let len = self.values.len();
+        let value = *self
+            .values
+            .get(index)
+            .ok_or(crate::Error::ColumnIndexOutOfBounds { index, len })?;
+
+        // the arguments are valid for the call of the function
+        Ok(unsafe { SqliteValue::value(value) })
+    }
+}
+
+/// An aggregate function, e.g. `SUM` or `GROUP_CONCAT`.
+///
+/// A value of the aggregate is created for each group of rows; [`step`] is called for each
+/// row of the group and [`finalize`] for the result of the group.
+///
+/// [`step`]: SqliteAggregate::step
+/// [`finalize`]: SqliteAggregate::finalize
+pub trait SqliteAggregate: Send + 'static {
+    /// The type of the result.
+    type Output: Encode<Sqlite>;
+
+    /// Add the arguments for a row to the aggregate.
+    fn step(&mut self, args: &SqliteFunctionArgs<'_>) -> crate::Result<()>;
+
+    /// Returns the result of the aggregate.
+    fn finalize(self) -> crate::Result<Self::Output>;
+}
+
+impl SqliteConnection {
+    /// Register a scalar SQL function implemented by `func`, or replace the function
+    /// with the same name and number of arguments.
+    ///
+    /// `num_args` is the number of arguments the function takes, or `-1` for any number.
+    /// A `deterministic` function always returns the same result for the same arguments, which
+    /// lets SQLite use it in indexes and optimize it out of loops.
+    ///
+    /// An error returned by `func` is raised as an error of the statement that called it.
+    ///
+    /// ```rust,ignore
+    /// conn.create_function("add_one", 1, true, |args| {
+    ///     Ok(args.try_get::<i64>(0)? + 1)
+    /// })
+    /// .await?;
+    /// ```
+    ///
+    /// Functions are only registered on this connection; see
+    /// [`Builder::after_connect`](crate::pool::Builder::after_connect) to register them on
+    /// every connection of a pool.
+    pub async fn create_function<F, R>(
+        &mut self,
+        name: &str,
+        num_args: i32,
+        deterministic: bool,
+        func: F,
+    ) -> crate::Result<()>
+    where
+        F: Fn(&SqliteFunctionArgs<'_>) -> crate::Result<R> + Send + 'static,
+        R: Encode<Sqlite>,
+    {
+        let name = function_name(name)?;
+        let handle = self.handle;
+
+        self.wait_for_pending_close().await;
+
+        let mut flags = SQLITE_UTF8;
+
+        if deterministic {
+            flags |= SQLITE_DETERMINISTIC;
+        }
+
+        self.worker
+            .run(move || {
+                let func = Box::into_raw(Box::new(func));
+
+                // https://www.sqlite.org/c3ref/create_function.html
+                // if this fails, SQLite calls `destroy` for `func` itself
+                let status = unsafe {
+                    sqlite3_create_function_v2(
+                        handle.0.as_ptr(),
+                        name.as_ptr(),
+                        num_args,
+                        flags,
+                        func as *mut c_void,
+                        Some(call_function::<F, R>),
+                        None,
+                        None,
+                        Some(destroy::<F>),
+                    )
+                };
+
+                check_status(handle, status)
+            })
+            .await
+    }
+
+    /// Register an aggregate SQL function, or replace the function with the same name and
+    /// number of arguments.
+    ///
+    /// `init` creates the aggregate for each group of rows; see [`SqliteAggregate`].
+    ///
+    /// ```rust,ignore
+    /// struct Product(i64);
+    ///
+    /// impl SqliteAggregate for Product {
+    ///     type Output = i64;
+    ///
+    ///     fn step(&mut self, args: &SqliteFunctionArgs<'_>) -> sqlx::Result<()> {
+    ///         self.0 *= args.try_get::<i64>(0)?;
+    ///         Ok(())
+    ///     }
+    ///
+    ///     fn finalize(self) -> sqlx::Result<i64> {
+    ///         Ok(self.0)
+    ///     }
+    /// }
+    ///
+    /// conn.create_aggregate("product", 1, true, || Product(1)).await?;
+    /// ```
+    pub async fn create_aggregate<I, A>(
+        &mut self,
+        name: &str,
+        num_args: i32,
+        deterministic: bool,
+        init: I,
+    ) -> crate::Result<()>
+    where
+        I: Fn() -> A + Send + 'static,
+        A: SqliteAggregate,
+    {
+        let name = function_name(name)?;
+        let handle = self.handle;
+
+        self.wait_for_pending_close().await;
+
+        let mut flags = SQLITE_UTF8;
+
+        if deterministic {
+            flags |= SQLITE_DETERMINISTIC;
+        }
+
+        self.worker
+            .run(move || {
+                let init = Box::into_raw(Box::new(init));
+
+                // https://www.sqlite.org/c3ref/create_function.html
+                let status = unsafe {
+                    sqlite3_create_function_v2(
+                        handle.0.as_ptr(),
+                        name.as_ptr(),
+                        num_args,
+                        flags,
+                        init as *mut c_void,
+                        None,
+                        Some(step_aggregate::<I, A>),
+                        Some(finalize_aggregate::<I, A>),
+                        Some(destroy::<I>),
+                    )
+                };
+
+                check_status(handle, status)
+            })
+            .await
+    }
+
+    /// Register a collation that orders text with `compare`, or replace the collation with
+    /// the same name.
+    ///
+    /// ```rust,ignore
+    /// conn.create_collation("nocase_reverse", |a, b| {
+    ///     b.to_lowercase().cmp(&a.to_lowercase())
+    /// })
+    /// .await?;
+    ///
+    /// sqlx::query("SELECT name FROM users ORDER BY name COLLATE nocase_reverse")
+    /// ```
+    ///
+    /// Text that is not valid UTF-8 is compared after invalid sequences are replaced with
+    /// `U+FFFD`.
+    pub async fn create_collation<F>(&mut self, name: &str, compare: F) -> crate::Result<()>
+    where
+        F: Fn(&str, &str) -> Ordering + Send + 'static,
+    {
+        let name = function_name(name)?;
+        let handle = self.handle;
+
+        self.wait_for_pending_close().await;
+
+        self.worker
+            .run(move || {
+                let compare = Box::into_raw(Box::new(compare));
+
+                // https://www.sqlite.org/c3ref/create_collation.html
+                let status = unsafe {
+                    sqlite3_create_collation_v2(
+                        handle.0.as_ptr(),
+                        name.as_ptr(),
+                        SQLITE_UTF8,
+                        compare as *mut c_void,
+                        Some(call_collation::<F>),
+                        Some(destroy::<F>),
+                    )
+                };
+
+                if status != SQLITE_OK {
+                    // unlike for functions, SQLite does not destroy the collation on an error
+                    unsafe { destroy::<F>(compare as *mut c_void) };
+                }
+
+                check_status(handle, status)
+            })
+            .await
+    }
+}
+
+fn function_name(name: &str) -> crate::Result<CString> {
+    CString::new(name).map_err(|_| arg_err!("the name {:?} contains a NUL byte", name))
+}
+
+fn check_status(handle: SqliteConnectionHandle, status: c_int) -> crate::Result<()> {
+    if status != SQLITE_OK {
+        return Err(SqliteError::from_connection(handle.0.as_ptr()).into());
+    }
+
+    Ok(())
+}
+
+unsafe extern "C" fn destroy<T>(data: *mut c_void) {
+    drop(Box::from_raw(data as *mut T));
+}
+
+unsafe extern "C" fn call_function<F, R>(
+    context: *mut sqlite3_context,
+    num_args: c_int,
+    args: *mut *mut sqlite3_value,
+) where
+    F: Fn(&SqliteFunctionArgs<'_>) -> crate::Result<R>,
+    R: Encode<Sqlite>,
+{
+    let func = &*(sqlite3_user_data(context) as *const F);
+    let args = function_args(num_args, args);
+
+    set_result(context, catch_unwind(AssertUnwindSafe(|| func(&args))));
+}
+
+unsafe extern "C" fn step_aggregate<I, A>(
+    context: *mut sqlite3_context,
+    num_args: c_int,
+    args: *mut *mut sqlite3_value,
+) where
+    I: Fn() -> A,
+    A: SqliteAggregate,
+{
+    // https://www.sqlite.org/c3ref/aggregate_context.html
+    // the memory is zeroed when it is allocated for the first row of the group
+    let state = sqlite3_aggregate_context(context, size_of::<*mut A>() as c_int) as *mut *mut A;
+
+    if state.is_null() {
+        sqlite3_result_error_nomem(context);
+        return;
+    }
+
+    let init = &*(sqlite3_user_data(context) as *const I);
+    let args = function_args(num_args, args);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        if (*state).is_null() {
+            *state = Box::into_raw(Box::new(init()));
+        }
+
+        (**state).step(&args)
+    }));
+
+    match result {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => set_error(context, &error.to_string()),
+        Err(_) => set_error(context, "user-defined function panicked"),
+    }
+}
+
+unsafe extern "C" fn finalize_aggregate<I, A>(context: *mut sqlite3_context)
+where
+    I: Fn() -> A,
+    A: SqliteAggregate,
+{
+    // a null pointer if `step_aggregate` was never called, i.e. the group has no rows
+    let state = sqlite3_aggregate_context(context, 0) as *mut *mut A;
+    let init = &*(sqlite3_user_data(context) as *const I);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let aggregate = if state.is_null() || (*state).is_null() {
+            init()
+        } else {
+            *Box::from_raw(*state)
+        };
+
+        aggregate.finalize()
+    }));
+
+    set_result(context, result);
+}
+
+unsafe extern "C" fn call_collation<F>(
+    data: *mut c_void,
+    left_len: c_int,
+    left: *const c_void,
+    right_len: c_int,
+    right: *const c_void,
+) -> c_int
+where
+    F: Fn(&str, &str) -> Ordering,
+{
+    let compare = &*(data as *const F);
+
+    let left = String::from_utf8_lossy(slice::from_raw_parts(left as *const u8, left_len as usize));
+
+    let right = String::from_utf8_lossy(slice::from_raw_parts(
+        right as *const u8,
+        right_len as usize,
+    ));
+
+    // a collation cannot fail; a panic is treated as the text being equal
+    match catch_unwind(AssertUnwindSafe(|| compare(&left, &right))) {
+        Ok(Ordering::Less) => -1,
+        Ok(Ordering::Greater) => 1,
+        Ok(Ordering::Equal) | Err(_) => 0,
+    }
+}
+
+unsafe fn function_args<'a>(
+    num_args: c_int,
+    args: *mut *mut sqlite3_value,
+) -> SqliteFunctionArgs<'a> {
+    SqliteFunctionArgs {
+        values: if num_args <= 0 || args.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(args, num_args as usize)
+        },
+    }
+}
+
+unsafe fn set_result<R>(
+    context: *mut sqlite3_context,
+    result: std::thread::Result<crate::Result<R>>,
+) where
+    R: Encode<Sqlite>,
+{
+    match result {
+        Ok(Ok(value)) => {
+            let mut values = Vec::with_capacity(1);
+
+            if let IsNull::Yes = value.encode_nullable(&mut values) {
+                values.push(SqliteArgumentValue::Null);
+            }
+
+            values
+                .first()
+                .unwrap_or(&SqliteArgumentValue::Null)
+                .result(context);
+        }
+
+        Ok(Err(error)) => set_error(context, &error.to_string()),
+        Err(_) => set_error(context, "user-defined function panicked"),
+    }
+}
+
+unsafe fn set_error(context: *mut sqlite3_context, message: &str) {
+    // https://www.sqlite.org/c3ref/result_blob.html
+    // SQLite makes a copy of the message
+    sqlite3_result_error(
+        context,
+        message.as_ptr() as *const c_char,
+        message.len() as c_int,
+    );
+}
@@ -0,0 +1,177 @@
+//! Notifications of changes to a database from the update, commit and rollback hooks.
+
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+use std::ffi::CStr;
+use std::os::raw::{c_char, c_int};
+
+use futures_channel::mpsc;
+use futures_core::stream::Stream;
+use libsqlite3_sys::{
+    sqlite3_commit_hook, sqlite3_int64, sqlite3_rollback_hook, sqlite3_update_hook, SQLITE_DELETE,
+    SQLITE_INSERT, SQLITE_UPDATE,
+};
+
+use crate::sqlite::SqliteConnection;
+
+/// The kind of change to a row reported by [`SqliteEvent::Update`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqliteOperation {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// A change to a database, received from [`SqliteConnection::events`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqliteEvent {
+    /// A row was inserted, updated or deleted.
+    Update {
+        operation: SqliteOperation,
+
+        /// The name of the database, e.g. `main`.
+        database: String,
+
+        table: String,
+
+        row_id: i64,
+    },
+
+    /// A transaction is being committed.
+    Commit,
+
+    /// A transaction was rolled back.
+    Rollback,
+}
+
+// The senders of the streams of a connection; only used on its worker thread
+struct HookSenders {
+    senders: Vec<mpsc::UnboundedSender<SqliteEvent>>,
+}
+
+impl HookSenders {
+    fn send(&mut self, event: SqliteEvent) {
+        // the streams that were dropped are removed
+        self.senders
+            .retain(|sender| sender.unbounded_send(event.clone()).is_ok());
+    }
+}
+
+/// The user data of the hooks of a connection, which is freed when the connection is dropped.
+pub(super) struct Hooks(NonNull<HookSenders>);
+
+// The hooks are only called on the worker thread of their connection
+unsafe impl Send for Hooks {}
+
+impl Drop for Hooks {
+    fn drop(&mut self) {
+        unsafe {
+            drop(Box::from_raw(self.0.as_ptr()));
+        }
+    }
+}
+
+// A pointer to the user data of the hooks, to be sent to the worker thread
+#[derive(Clone, Copy)]
+struct HooksPtr(NonNull<HookSenders>);
+
+unsafe impl Send for HooksPtr {}
+
+impl SqliteConnection {
+    /// Returns a stream of the changes made to the database by this connection.
+    ///
+    /// ```rust,ignore
+    /// let mut events = conn.events().await;
+    ///
+    /// while let Some(event) = events.next().await {
+    ///     if let SqliteEvent::Update { table, row_id, .. } = event {
+    ///         cache.invalidate(&table, row_id);
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The events are sent by the [update](https://www.sqlite.org/c3ref/update_hook.html),
+    /// [commit and rollback](https://www.sqlite.org/c3ref/commit_hook.html) hooks of SQLite
+    /// as the statements of this connection run; a change made in a transaction is only
+    /// durable after the [`Commit`](SqliteEvent::Commit) that follows it. Changes to a
+    /// `WITHOUT ROWID` table and rows deleted by a `DELETE` without a `WHERE` clause are not
+    /// reported.
+    ///
+    /// Each call returns a new stream. The events are buffered until they are received;
+    /// a stream that is no longer used should be dropped.
+    pub async fn events(&mut self) -> impl Stream<Item = SqliteEvent> + Send + Unpin + 'static {
+        let (sender, receiver) = mpsc::unbounded();
+
+        self.wait_for_pending_close().await;
+
+        let conn = self.handle;
+        let registered = self.hooks.is_some();
+
+        let hooks = self.hooks.get_or_insert_with(|| {
+            let senders = Box::new(HookSenders {
+                senders: Vec::new(),
+            });
+
+            Hooks(NonNull::new(Box::into_raw(senders)).unwrap())
+        });
+
+        let senders = HooksPtr(hooks.0);
+
+        self.worker
+            .run(move || unsafe {
+                let data = senders.0.as_ptr();
+
+                (*data).senders.push(sender);
+
+                if !registered {
+                    let db = conn.0.as_ptr();
+
+                    sqlite3_update_hook(db, Some(update_hook), data as *mut c_void);
+                    sqlite3_commit_hook(db, Some(commit_hook), data as *mut c_void);
+                    sqlite3_rollback_hook(db, Some(rollback_hook), data as *mut c_void);
+                }
+            })
+            .await;
+
+        receiver
+    }
+}
+
+// https://www.sqlite.org/c3ref/update_hook.html
+unsafe extern "C" fn update_hook(
+    data: *mut c_void,
+    operation: c_int,
+    database: *const c_char,
+    table: *const c_char,
+    row_id: sqlite3_int64,
+) {
+    let operation = match operation {
+        SQLITE_INSERT => SqliteOperation::Insert,
+        SQLITE_UPDATE => SqliteOperation::Update,
+        SQLITE_DELETE => SqliteOperation::Delete,
+        _ => return,
+    };
+
+    let database = CStr::from_ptr(database).to_string_lossy().into_owned();
+    let table = CStr::from_ptr(table).to_string_lossy().into_owned();
+
+    (*(data as *mut HookSenders)).send(SqliteEvent::Update {
+        operation,
+        database,
+        table,
+        row_id,
+    });
+}
+
+// https://www.sqlite.org/c3ref/commit_hook.html
+unsafe extern "C" fn commit_hook(data: *mut c_void) -> c_int {
+    (*(data as *mut HookSenders)).send(SqliteEvent::Commit);
+
+    // a non-zero value would turn the commit into a rollback
+    0
+}
+
+unsafe extern "C" fn rollback_hook(data: *mut c_void) {
+    (*(data as *mut HookSenders)).send(SqliteEvent::Rollback);
+}
@@ -5,12 +5,20 @@
 // invariants.
 #![allow(unsafe_code)]
 
+use std::path::Path;
+
 mod arguments;
+mod backup;
+mod blob;
 mod connection;
 mod cursor;
 mod database;
 mod error;
 mod executor;
+mod extension;
+mod function;
+mod hooks;
+mod options;
 mod row;
 mod statement;
 mod type_info;
@@ -19,10 +27,14 @@ mod value;
 mod worker;
 
 pub use arguments::{SqliteArgumentValue, SqliteArguments};
+pub use backup::{SqliteBackup, SqliteBackupProgress, SqliteBackupTarget};
+pub use blob::SqliteBlob;
 pub use connection::SqliteConnection;
 pub use cursor::SqliteCursor;
 pub use database::Sqlite;
 pub use error::SqliteError;
+pub use function::{SqliteAggregate, SqliteFunctionArgs};
+pub use hooks::{SqliteEvent, SqliteOperation};
 pub use row::SqliteRow;
 pub use type_info::SqliteTypeInfo;
 pub use value::SqliteValue;
@@ -33,4 +45,11 @@ pub type SqlitePool = crate::pool::Pool<SqliteConnection>;
 
 make_query_as!(SqliteQueryAs, Sqlite, SqliteRow);
 impl_map_row_for_row!(Sqlite, SqliteRow);
+impl_map_scalar_for_row!(Sqlite, SqliteRow);
 impl_from_row_for_tuples!(Sqlite, SqliteRow);
+
+// The paths of databases and extensions are passed to SQLite as UTF-8
+fn path_to_str(path: &Path) -> crate::Result<&str> {
+    path.to_str()
+        .ok_or_else(|| arg_err!("the path {:?} is not valid UTF-8", path))
+}
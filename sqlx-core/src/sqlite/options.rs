@@ -0,0 +1,452 @@
+use std::ffi::CString;
+use std::os::raw::c_int;
+use std::time::Duration;
+
+use libsqlite3_sys::{
+    SQLITE_OPEN_CREATE, SQLITE_OPEN_MEMORY, SQLITE_OPEN_NOMUTEX, SQLITE_OPEN_PRIVATECACHE,
+    SQLITE_OPEN_READONLY, SQLITE_OPEN_READWRITE, SQLITE_OPEN_SHAREDCACHE, SQLITE_OPEN_URI,
+};
+
+use crate::sqlite::worker::WorkerMode;
+use crate::url::Url;
+
+// The options of a connection, parsed from its URL
+pub(super) struct SqliteOptions {
+    pub(super) filename: CString,
+    pub(super) flags: c_int,
+    pub(super) busy_timeout: Duration,
+    journal_mode: Option<&'static str>,
+    synchronous: Option<&'static str>,
+    foreign_keys: Option<bool>,
+    pub(super) extensions: Vec<String>,
+    pub(super) statement_cache_capacity: usize,
+    pub(super) worker: WorkerMode,
+}
+
+impl SqliteOptions {
+    pub(super) fn parse(url: &Url) -> crate::Result<Self> {
+        let (filename, flags) = parse_open(url)?;
+
+        // the pragmas are left as they are set by the database (or SQLite) unless they are given
+        let journal_mode = param_choice(url, "journal_mode", JOURNAL_MODES)?;
+        let synchronous = param_choice(url, "synchronous", SYNCHRONOUS)?;
+
+        let foreign_keys = match url.param("foreign_keys").as_deref() {
+            None => None,
+            Some("true") | Some("1") => Some(true),
+            Some("false") | Some("0") => Some(false),
+
+            Some(foreign_keys) => {
+                return Err(config_err!(
+                    "unknown value {:?} for `foreign_keys`; expected `true` or `false`",
+                    foreign_keys
+                ));
+            }
+        };
+
+        let busy_timeout = match url.param("busy_timeout") {
+            Some(ms) => Duration::from_millis(ms.parse().map_err(|_| {
+                config_err!(
+                    "expected a number of milliseconds for `busy_timeout`, got {:?}",
+                    ms
+                )
+            })?),
+
+            None => Duration::from_secs(5),
+        };
+
+        let worker = match url.param("worker").as_deref() {
+            None | Some("dedicated") => WorkerMode::Dedicated,
+
+            Some("shared") => WorkerMode::Shared {
+                threads: match url.param("worker_threads") {
+                    Some(threads) => threads
+                        .parse()
+                        .ok()
+                        .filter(|&threads| threads > 0)
+                        .ok_or_else(|| {
+                            config_err!(
+                                "expected a number greater than 0 for `worker_threads`, got {:?}",
+                                threads
+                            )
+                        })?,
+
+                    None => 4,
+                },
+            },
+
+            Some(worker) => {
+                return Err(config_err!(
+                    "unknown value {:?} for `worker`; expected `dedicated` or `shared`",
+                    worker
+                ));
+            }
+        };
+
+        let extensions = url
+            .params("extension")
+            .map(|path| path.into_owned())
+            .collect();
+
+        Ok(SqliteOptions {
+            filename,
+            flags,
+            busy_timeout,
+            journal_mode,
+            synchronous,
+            foreign_keys,
+            extensions,
+            statement_cache_capacity: crate::cache::capacity(url)?,
+            worker,
+        })
+    }
+
+    // If the database is an in-memory database that is shared by the connections which open it
+    // by its name, and is destroyed when the last of them is closed
+    // <https://www.sqlite.org/inmemorydb.html>
+    pub(super) fn is_shared_memory(&self) -> bool {
+        let filename = self.filename.to_str().unwrap_or_default();
+
+        if !filename.starts_with("file:") {
+            return false;
+        }
+
+        let mut parts = filename["file:".len()..].splitn(2, '?');
+        let path = parts.next().unwrap_or_default();
+
+        let mut memory = path == ":memory:";
+        let mut shared = self.flags & SQLITE_OPEN_SHAREDCACHE != 0;
+
+        for param in parts.next().unwrap_or_default().split('&') {
+            match param {
+                "mode=memory" => memory = true,
+                "cache=shared" => shared = true,
+                "cache=private" => shared = false,
+                _ => {}
+            }
+        }
+
+        memory && shared
+    }
+
+    // The PRAGMA statements to run when a connection is opened
+    pub(super) fn pragmas(&self) -> String {
+        let mut pragmas = String::new();
+
+        // https://www.sqlite.org/wal.html
+        if let Some(journal_mode) = self.journal_mode {
+            pragmas.push_str(&format!("PRAGMA journal_mode = {};\n", journal_mode));
+        }
+
+        if let Some(synchronous) = self.synchronous {
+            pragmas.push_str(&format!("PRAGMA synchronous = {};\n", synchronous));
+        }
+
+        if let Some(foreign_keys) = self.foreign_keys {
+            pragmas.push_str(&format!(
+                "PRAGMA foreign_keys = {};\n",
+                if foreign_keys { "ON" } else { "OFF" }
+            ));
+        }
+
+        pragmas
+    }
+}
+
+const JOURNAL_MODES: &[&str] = &["DELETE", "TRUNCATE", "PERSIST", "MEMORY", "WAL", "OFF"];
+
+const SYNCHRONOUS: &[&str] = &["OFF", "NORMAL", "FULL", "EXTRA"];
+
+// The value of a parameter which is one of `choices` (case-insensitive)
+fn param_choice(
+    url: &Url,
+    key: &str,
+    choices: &[&'static str],
+) -> crate::Result<Option<&'static str>> {
+    let value = match url.param(key) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+
+    match choices
+        .iter()
+        .find(|choice| choice.eq_ignore_ascii_case(&value))
+    {
+        Some(choice) => Ok(Some(choice)),
+
+        None => Err(config_err!(
+            "unknown value {:?} for `{}`; expected one of {}",
+            value,
+            key,
+            choices.join(", ")
+        )),
+    }
+}
+
+// The filename and flags to open the database with
+fn parse_open(url: &Url) -> crate::Result<(CString, c_int)> {
+    // a SQLite URL is a file path and not _really_ a URL so the path is taken from the
+    // URL string as-is (e.g. `sqlite://data.db`, `sqlite::memory:`)
+    let url_str = url.as_str();
+    let url_str =
+        url_str[url_str.find(':').map_or(0, |colon| colon + 1)..].trim_start_matches("//");
+
+    let path = url_str.split('?').next().unwrap_or_default();
+
+    // [SQLITE_OPEN_NOMUTEX] will instruct [sqlite3_open_v2] to return an error if it
+    // cannot satisfy our wish for a thread-safe, lock-free connection object
+    let mut flags = SQLITE_OPEN_NOMUTEX;
+
+    // a URI filename is passed to SQLite as-is with its own query parameters
+    // <https://www.sqlite.org/uri.html>
+    if path.starts_with("file:") {
+        return Ok((
+            filename(url_str)?,
+            flags
+                | SQLITE_OPEN_READWRITE
+                | SQLITE_OPEN_CREATE
+                | SQLITE_OPEN_SHAREDCACHE
+                | SQLITE_OPEN_URI,
+        ));
+    }
+
+    // the parameters of a URI filename for the options that can only be set with one
+    let mut uri_params = Vec::new();
+
+    flags |= match url.param("mode").as_deref() {
+        None | Some("rwc") => SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE,
+        Some("rw") => SQLITE_OPEN_READWRITE,
+        Some("ro") => SQLITE_OPEN_READONLY,
+
+        // SQLite shares an in-memory database by its name only if it is a URI filename
+        Some("memory") => {
+            uri_params.push("mode=memory");
+
+            SQLITE_OPEN_READWRITE | SQLITE_OPEN_CREATE | SQLITE_OPEN_MEMORY
+        }
+
+        Some(mode) => {
+            return Err(config_err!(
+                "unknown value {:?} for `mode`; expected one of `ro`, `rw`, `rwc` or `memory`",
+                mode
+            ));
+        }
+    };
+
+    flags |= match url.param("cache").as_deref() {
+        None | Some("shared") => SQLITE_OPEN_SHAREDCACHE,
+        Some("private") => SQLITE_OPEN_PRIVATECACHE,
+
+        Some(cache) => {
+            return Err(config_err!(
+                "unknown value {:?} for `cache`; expected `shared` or `private`",
+                cache
+            ));
+        }
+    };
+
+    match url.param("immutable").as_deref() {
+        None | Some("false") | Some("0") => {}
+        Some("true") | Some("1") => uri_params.push("immutable=1"),
+
+        Some(immutable) => {
+            return Err(config_err!(
+                "unknown value {:?} for `immutable`; expected `true` or `false`",
+                immutable
+            ));
+        }
+    }
+
+    let filename = if path == ":memory:" {
+        filename(":memory:")?
+    } else if !uri_params.is_empty() {
+        // the path is still percent-encoded as a URI expects
+        flags |= SQLITE_OPEN_URI;
+
+        filename(&format!("file:{}?{}", path, uri_params.join("&")))?
+    } else {
+        let path = percent_encoding::percent_decode_str(path)
+            .decode_utf8()
+            .map_err(|_| config_err!("the SQLite database path contains non-UTF-8 bytes"))?;
+
+        filename(&path)?
+    };
+
+    Ok((filename, flags))
+}
+
+fn filename(filename: &str) -> crate::Result<CString> {
+    CString::new(filename)
+        .map_err(|_| config_err!("the SQLite database filename contains a NUL byte"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::*;
+
+    fn parse(url: &str) -> crate::Result<SqliteOptions> {
+        SqliteOptions::parse(&Url::try_from(url).unwrap())
+    }
+
+    #[test]
+    fn it_parses_the_default_flags() {
+        let options = parse("sqlite://data.db").unwrap();
+
+        assert_eq!(options.filename.to_str().unwrap(), "data.db");
+        assert_eq!(
+            options.flags,
+            SQLITE_OPEN_NOMUTEX
+                | SQLITE_OPEN_READWRITE
+                | SQLITE_OPEN_CREATE
+                | SQLITE_OPEN_SHAREDCACHE
+        );
+    }
+
+    #[test]
+    fn it_parses_mode_and_cache() {
+        let options = parse("sqlite://data.db?mode=ro&cache=private").unwrap();
+
+        assert_eq!(options.filename.to_str().unwrap(), "data.db");
+        assert_eq!(
+            options.flags,
+            SQLITE_OPEN_NOMUTEX | SQLITE_OPEN_READONLY | SQLITE_OPEN_PRIVATECACHE
+        );
+
+        let options = parse("sqlite://test_db?mode=memory").unwrap();
+
+        assert_eq!(
+            options.filename.to_str().unwrap(),
+            "file:test_db?mode=memory"
+        );
+        assert_ne!(options.flags & SQLITE_OPEN_MEMORY, 0);
+        assert!(parse("sqlite://data.db?mode=readonly").is_err());
+        assert!(parse("sqlite://data.db?cache=none").is_err());
+    }
+
+    #[test]
+    fn it_parses_memory_and_uri_filenames() {
+        let options = parse("sqlite::memory:").unwrap();
+
+        assert_eq!(options.filename.to_str().unwrap(), ":memory:");
+
+        let options = parse("sqlite:file:data.db?mode=ro&vfs=unix").unwrap();
+
+        assert_eq!(
+            options.filename.to_str().unwrap(),
+            "file:data.db?mode=ro&vfs=unix"
+        );
+        assert_ne!(options.flags & SQLITE_OPEN_URI, 0);
+
+        let options = parse("sqlite://my%20data.db?immutable=true").unwrap();
+
+        assert_eq!(
+            options.filename.to_str().unwrap(),
+            "file:my%20data.db?immutable=1"
+        );
+        assert_ne!(options.flags & SQLITE_OPEN_URI, 0);
+    }
+
+    #[test]
+    fn it_detects_shared_in_memory_databases() {
+        assert!(parse("sqlite://test_db?mode=memory")
+            .unwrap()
+            .is_shared_memory());
+
+        assert!(parse("sqlite:file:test_db?mode=memory&cache=shared")
+            .unwrap()
+            .is_shared_memory());
+
+        assert!(parse("sqlite:file::memory:?cache=shared")
+            .unwrap()
+            .is_shared_memory());
+
+        assert!(!parse("sqlite::memory:").unwrap().is_shared_memory());
+        assert!(!parse("sqlite://data.db").unwrap().is_shared_memory());
+
+        assert!(!parse("sqlite://test_db?mode=memory&cache=private")
+            .unwrap()
+            .is_shared_memory());
+
+        assert!(!parse("sqlite:file:test_db?mode=memory&cache=private")
+            .unwrap()
+            .is_shared_memory());
+    }
+
+    #[test]
+    fn it_parses_pragmas() {
+        let options = parse("sqlite://data.db").unwrap();
+
+        // the pragmas of the database are left as they are by default
+        assert_eq!(options.busy_timeout, Duration::from_secs(5));
+        assert_eq!(options.pragmas(), "");
+
+        let options =
+            parse("sqlite://data.db?journal_mode=wal&synchronous=normal&foreign_keys=true")
+                .unwrap();
+
+        assert_eq!(
+            options.pragmas(),
+            "PRAGMA journal_mode = WAL;\nPRAGMA synchronous = NORMAL;\nPRAGMA foreign_keys = ON;\n"
+        );
+
+        let options = parse(
+            "sqlite://data.db?journal_mode=delete&synchronous=full&foreign_keys=false&busy_timeout=100",
+        )
+        .unwrap();
+
+        assert_eq!(options.busy_timeout, Duration::from_millis(100));
+        assert_eq!(
+            options.pragmas(),
+            "PRAGMA journal_mode = DELETE;\nPRAGMA synchronous = FULL;\nPRAGMA foreign_keys = OFF;\n"
+        );
+
+        assert!(parse("sqlite://data.db?journal_mode=fast").is_err());
+        assert!(parse("sqlite://data.db?busy_timeout=5s").is_err());
+    }
+
+    #[test]
+    fn it_parses_extensions() {
+        let options = parse("sqlite://data.db").unwrap();
+
+        assert!(options.extensions.is_empty());
+
+        let options =
+            parse("sqlite://data.db?extension=mod_spatialite&extension=%2Fusr%2Flib%2Ffts5.so")
+                .unwrap();
+
+        assert_eq!(options.extensions, ["mod_spatialite", "/usr/lib/fts5.so"]);
+    }
+
+    #[test]
+    fn it_parses_the_worker_mode() {
+        assert_eq!(
+            parse("sqlite://data.db").unwrap().worker,
+            WorkerMode::Dedicated
+        );
+
+        assert_eq!(
+            parse("sqlite://data.db?worker=shared").unwrap().worker,
+            WorkerMode::Shared { threads: 4 }
+        );
+
+        assert_eq!(
+            parse("sqlite://data.db?worker=shared&worker_threads=16")
+                .unwrap()
+                .worker,
+            WorkerMode::Shared { threads: 16 }
+        );
+
+        assert!(parse("sqlite://data.db?worker=pool").is_err());
+        assert!(parse("sqlite://data.db?worker=shared&worker_threads=0").is_err());
+    }
+
+    #[test]
+    fn it_rejects_nul_bytes() {
+        match parse("sqlite://foo%00bar") {
+            Err(crate::Error::Configuration(_)) => {}
+            _ => panic!("expected a configuration error"),
+        }
+    }
+}
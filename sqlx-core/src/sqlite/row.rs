@@ -38,10 +38,10 @@ impl<'c> Row<'c> for SqliteRow<'c> {
     where
         I: ColumnIndex<'c, Self>,
     {
-        Ok(SqliteValue {
-            statement: self.statement(),
-            index: index.index(self)? as i32,
-        })
+        Ok(SqliteValue::column(
+            self.statement(),
+            index.index(self)? as i32,
+        ))
     }
 }
 
@@ -7,11 +7,12 @@ use std::os::raw::{c_char, c_int};
 use std::ptr;
 
 use libsqlite3_sys::{
-    sqlite3_bind_parameter_count, sqlite3_clear_bindings, sqlite3_column_count,
-    sqlite3_column_database_name, sqlite3_column_decltype, sqlite3_column_name,
-    sqlite3_column_origin_name, sqlite3_column_table_name, sqlite3_data_count, sqlite3_finalize,
-    sqlite3_prepare_v3, sqlite3_reset, sqlite3_step, sqlite3_stmt, sqlite3_table_column_metadata,
-    SQLITE_DONE, SQLITE_OK, SQLITE_PREPARE_NO_VTAB, SQLITE_PREPARE_PERSISTENT, SQLITE_ROW,
+    sqlite3_bind_parameter_count, sqlite3_bind_parameter_name, sqlite3_clear_bindings,
+    sqlite3_column_count, sqlite3_column_database_name, sqlite3_column_decltype,
+    sqlite3_column_name, sqlite3_column_origin_name, sqlite3_column_table_name, sqlite3_data_count,
+    sqlite3_finalize, sqlite3_prepare_v3, sqlite3_reset, sqlite3_step, sqlite3_stmt,
+    sqlite3_table_column_metadata, SQLITE_DONE, SQLITE_OK, SQLITE_PREPARE_NO_VTAB,
+    SQLITE_PREPARE_PERSISTENT, SQLITE_ROW,
 };
 
 use crate::sqlite::connection::SqliteConnectionHandle;
@@ -235,7 +236,35 @@ impl Statement {
         }
     }
 
+    // Returns the name of the parameter at `index` (1-based) or an empty string if it is unnamed
+    pub(super) fn param_name(&mut self, index: usize) -> String {
+        // https://www.sqlite.org/c3ref/bind_parameter_name.html
+        unsafe {
+            self.handle().map_or_else(String::new, |handle| {
+                let name = sqlite3_bind_parameter_name(handle, index as c_int);
+
+                if name.is_null() {
+                    String::new()
+                } else {
+                    CStr::from_ptr(name).to_string_lossy().into_owned()
+                }
+            })
+        }
+    }
+
     pub(super) fn bind(&mut self, arguments: &mut SqliteArguments) -> crate::Result<()> {
+        if arguments.is_named() {
+            // SQLite supports named parameters (`:name`, `@name` and `$name`) itself
+            for index in 0..self.params() {
+                let name = self.param_name(index + 1);
+                let value = arguments.named(name.get(1..).unwrap_or_default())?;
+
+                value.bind(self, index + 1)?;
+            }
+
+            return Ok(());
+        }
+
         for index in 0..self.params() {
             if let Some(value) = arguments.next() {
                 value.bind(self, index + 1)?;
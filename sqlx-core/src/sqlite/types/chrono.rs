@@ -0,0 +1,134 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use std::fmt::Display;
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::sqlite::type_info::{SqliteType, SqliteTypeAffinity};
+use crate::sqlite::types::datetime::SqliteDateTime;
+use crate::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValue};
+use crate::types::Type;
+
+impl Type<Sqlite> for NaiveDateTime {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo::new(SqliteType::Text, SqliteTypeAffinity::Text)
+    }
+}
+
+impl<Tz> Type<Sqlite> for DateTime<Tz>
+where
+    Tz: TimeZone,
+{
+    fn type_info() -> SqliteTypeInfo {
+        <NaiveDateTime as Type<Sqlite>>::type_info()
+    }
+}
+
+impl Type<Sqlite> for NaiveDate {
+    fn type_info() -> SqliteTypeInfo {
+        <NaiveDateTime as Type<Sqlite>>::type_info()
+    }
+}
+
+impl Type<Sqlite> for NaiveTime {
+    fn type_info() -> SqliteTypeInfo {
+        <NaiveDateTime as Type<Sqlite>>::type_info()
+    }
+}
+
+impl Encode<Sqlite> for NaiveDateTime {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(
+            self.format("%F %T%.f").to_string(),
+        ));
+    }
+}
+
+impl<Tz> Encode<Sqlite> for DateTime<Tz>
+where
+    Tz: TimeZone,
+    Tz::Offset: Display,
+{
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(
+            self.format("%F %T%.f%:z").to_string(),
+        ));
+    }
+}
+
+impl Encode<Sqlite> for NaiveDate {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(self.format("%F").to_string()));
+    }
+}
+
+impl Encode<Sqlite> for NaiveTime {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(self.format("%T%.f").to_string()));
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for DateTime<FixedOffset> {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime = match SqliteDateTime::decode(&value)? {
+            SqliteDateTime::Unix {
+                seconds,
+                nanoseconds,
+            } => NaiveDateTime::from_timestamp_opt(seconds, nanoseconds)
+                .map(|naive| FixedOffset::east(0).from_utc_datetime(&naive)),
+
+            SqliteDateTime::Text(text) => NaiveDate::from_ymd_opt(text.year, text.month, text.day)
+                .and_then(|date| {
+                    date.and_hms_nano_opt(text.hour, text.minute, text.second, text.nanosecond)
+                })
+                .and_then(|naive| {
+                    FixedOffset::east_opt(text.offset)?
+                        .from_local_datetime(&naive)
+                        .single()
+                }),
+        };
+
+        datetime.ok_or_else(|| crate::Error::Decode("date or time out of range for chrono".into()))
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for DateTime<Utc> {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: DateTime<FixedOffset> = Decode::<Sqlite>::decode(value)?;
+
+        Ok(datetime.with_timezone(&Utc))
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for DateTime<Local> {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: DateTime<FixedOffset> = Decode::<Sqlite>::decode(value)?;
+
+        Ok(datetime.with_timezone(&Local))
+    }
+}
+
+// Like the SQLite date and time functions, a date or time with an offset is converted to UTC
+
+impl<'de> Decode<'de, Sqlite> for NaiveDateTime {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: DateTime<FixedOffset> = Decode::<Sqlite>::decode(value)?;
+
+        Ok(datetime.naive_utc())
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for NaiveDate {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: NaiveDateTime = Decode::<Sqlite>::decode(value)?;
+
+        Ok(datetime.date())
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for NaiveTime {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: NaiveDateTime = Decode::<Sqlite>::decode(value)?;
+
+        Ok(datetime.time())
+    }
+}
@@ -0,0 +1,235 @@
+// SQLite does not have a storage class for dates and times. Instead, the built-in date and time
+// functions understand dates and times stored as any of:
+//
+//  * TEXT as ISO-8601 strings ("YYYY-MM-DD HH:MM:SS.SSS")
+//  * REAL as Julian day numbers
+//  * INTEGER as Unix time, the number of seconds since 1970-01-01 00:00:00 UTC
+//
+// https://www.sqlite.org/datatype3.html#date_and_time_datatype
+// https://www.sqlite.org/lang_datefunc.html
+
+use crate::error::UnexpectedNullError;
+use crate::sqlite::type_info::SqliteType;
+use crate::sqlite::SqliteValue;
+
+// The Julian day number of the Unix epoch
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2_440_587.5;
+
+pub(super) enum SqliteDateTime {
+    // Stored as INTEGER or REAL and so always in UTC
+    Unix { seconds: i64, nanoseconds: u32 },
+
+    // Stored as TEXT
+    Text(SqliteDateTimeText),
+}
+
+pub(super) struct SqliteDateTimeText {
+    // A time without a date is on 2000-01-01, as it is for SQLite
+    pub(super) year: i32,
+    pub(super) month: u32,
+    pub(super) day: u32,
+
+    pub(super) hour: u32,
+    pub(super) minute: u32,
+    pub(super) second: u32,
+    pub(super) nanosecond: u32,
+
+    // Seconds east of UTC; a time without an offset is taken to be in UTC
+    pub(super) offset: i32,
+}
+
+impl SqliteDateTime {
+    pub(super) fn decode(value: &SqliteValue<'_>) -> crate::Result<Self> {
+        match value.r#type() {
+            Some(SqliteType::Integer) => Ok(SqliteDateTime::Unix {
+                seconds: value.int64(),
+                nanoseconds: 0,
+            }),
+
+            Some(SqliteType::Float) => {
+                // SQLite keeps millisecond precision when converting from a Julian day number
+                let millis =
+                    ((value.double() - UNIX_EPOCH_JULIAN_DAY) * 86_400_000.0).round() as i64;
+
+                Ok(SqliteDateTime::Unix {
+                    seconds: millis.div_euclid(1000),
+                    nanoseconds: (millis.rem_euclid(1000) * 1_000_000) as u32,
+                })
+            }
+
+            Some(SqliteType::Text) => {
+                let s = value.text().unwrap_or_default();
+
+                parse(s)
+                    .map(SqliteDateTime::Text)
+                    .ok_or_else(|| decode_err!("invalid date or time {:?}", s))
+            }
+
+            Some(ty) => Err(decode_err!(
+                "expected a date or time stored as TEXT, INTEGER or REAL; received {:?}",
+                ty
+            )),
+
+            None => Err(crate::Error::decode(UnexpectedNullError)),
+        }
+    }
+}
+
+// Parses the time strings understood by the SQLite date and time functions:
+//
+//   YYYY-MM-DD
+//   YYYY-MM-DD HH:MM
+//   YYYY-MM-DD HH:MM:SS
+//   YYYY-MM-DD HH:MM:SS.SSS
+//   HH:MM
+//   HH:MM:SS
+//   HH:MM:SS.SSS
+//
+// The date and time may be separated by a `T` instead of a space and the time may be followed
+// by a timezone of the form `[+-]HH:MM` or `Z`.
+fn parse(s: &str) -> Option<SqliteDateTimeText> {
+    if !s.is_ascii() {
+        return None;
+    }
+
+    let mut rest = s.trim();
+
+    let mut value = SqliteDateTimeText {
+        year: 2000,
+        month: 1,
+        day: 1,
+        hour: 0,
+        minute: 0,
+        second: 0,
+        nanosecond: 0,
+        offset: 0,
+    };
+
+    let has_date = rest.len() >= 10 && &rest[4..5] == "-" && &rest[7..8] == "-";
+
+    if has_date {
+        value.year = number(&rest[..4])?;
+        value.month = number(&rest[5..7])?;
+        value.day = number(&rest[8..10])?;
+
+        rest = &rest[10..];
+
+        if rest.starts_with(' ') || rest.starts_with('T') {
+            rest = &rest[1..];
+        }
+    }
+
+    let has_time = rest.len() >= 5 && &rest[2..3] == ":";
+
+    if has_time {
+        value.hour = number(&rest[..2])?;
+        value.minute = number(&rest[3..5])?;
+
+        rest = &rest[5..];
+
+        if rest.starts_with(':') && rest.len() >= 3 {
+            value.second = number(&rest[1..3])?;
+
+            rest = &rest[3..];
+
+            if rest.starts_with('.') {
+                let digits = rest[1..].bytes().take_while(u8::is_ascii_digit).count();
+
+                if digits == 0 {
+                    return None;
+                }
+
+                // only nanosecond precision is kept
+                let fraction = &rest[1..(1 + digits.min(9))];
+
+                value.nanosecond = number::<u32>(fraction)? * 10_u32.pow(9 - fraction.len() as u32);
+
+                rest = &rest[(1 + digits)..];
+            }
+        }
+    } else if !has_date {
+        return None;
+    }
+
+    value.offset = match rest.trim_start() {
+        "" | "Z" | "z" => 0,
+
+        offset if offset.len() == 6 && &offset[3..4] == ":" => {
+            let hours: i32 = number(&offset[1..3])?;
+            let minutes: i32 = number(&offset[4..6])?;
+
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+
+            let seconds = hours * 3600 + minutes * 60;
+
+            match &offset[..1] {
+                "+" => seconds,
+                "-" => -seconds,
+
+                _ => return None,
+            }
+        }
+
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn number<T: std::str::FromStr>(s: &str) -> Option<T> {
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+
+    #[test]
+    fn it_parses_date_time() {
+        let value = parse("2020-05-17 10:05:01.125").unwrap();
+
+        assert_eq!((value.year, value.month, value.day), (2020, 5, 17));
+        assert_eq!((value.hour, value.minute, value.second), (10, 5, 1));
+        assert_eq!(value.nanosecond, 125_000_000);
+        assert_eq!(value.offset, 0);
+    }
+
+    #[test]
+    fn it_parses_date_time_with_offset() {
+        let value = parse("2020-05-17T10:05-04:30").unwrap();
+
+        assert_eq!((value.hour, value.minute, value.second), (10, 5, 0));
+        assert_eq!(value.offset, -(4 * 3600 + 30 * 60));
+
+        assert_eq!(parse("2020-05-17 10:05:00Z").unwrap().offset, 0);
+    }
+
+    #[test]
+    fn it_parses_date_or_time() {
+        let value = parse("2020-05-17").unwrap();
+
+        assert_eq!((value.year, value.month, value.day), (2020, 5, 17));
+        assert_eq!((value.hour, value.minute, value.second), (0, 0, 0));
+
+        let value = parse("23:59:59.123456789123").unwrap();
+
+        assert_eq!((value.year, value.month, value.day), (2000, 1, 1));
+        assert_eq!((value.hour, value.minute, value.second), (23, 59, 59));
+        assert_eq!(value.nanosecond, 123_456_789);
+    }
+
+    #[test]
+    fn it_rejects_invalid_date_time() {
+        assert!(parse("").is_none());
+        assert!(parse("now").is_none());
+        assert!(parse("2020-05-17 10").is_none());
+        assert!(parse("2020-05-17 10:05:01.").is_none());
+        assert!(parse("2020-05-17 10:05:01 +0100").is_none());
+    }
+}
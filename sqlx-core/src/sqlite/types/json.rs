@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue as JsonRawValue;
+use serde_json::Value as JsonValue;
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::sqlite::type_info::{SqliteType, SqliteTypeAffinity};
+use crate::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValue};
+use crate::types::{Json, Type};
+
+// JSON is stored as TEXT which is what the JSON1 extension expects
+// https://www.sqlite.org/json1.html
+
+impl Type<Sqlite> for JsonValue {
+    fn type_info() -> SqliteTypeInfo {
+        <Json<Self> as Type<Sqlite>>::type_info()
+    }
+}
+
+impl Type<Sqlite> for &'_ JsonRawValue {
+    fn type_info() -> SqliteTypeInfo {
+        <Json<Self> as Type<Sqlite>>::type_info()
+    }
+}
+
+impl<T> Type<Sqlite> for Json<T> {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo::new(SqliteType::Text, SqliteTypeAffinity::Text)
+    }
+}
+
+impl<T> Encode<Sqlite> for Json<T>
+where
+    T: Serialize,
+{
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        let json = serde_json::to_string(&self.0)
+            .expect("failed to serialize json for encoding to database");
+
+        values.push(SqliteArgumentValue::Text(json));
+    }
+}
+
+impl<'de, T> Decode<'de, Sqlite> for Json<T>
+where
+    T: 'de,
+    T: Deserialize<'de>,
+{
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let s = <&'de str as Decode<Sqlite>>::decode(value)?;
+
+        serde_json::from_str(s)
+            .map(Json)
+            .map_err(crate::Error::decode)
+    }
+}
@@ -13,6 +13,65 @@
 //! | `&str`, `String`                      | TEXT                                                 |
 //! | `&[u8]`, `Vec<u8>`                    | BLOB                                                 |
 //!
+//! ### [`chrono`](https://crates.io/crates/chrono)
+//!
+//! Requires the `chrono` Cargo feature flag.
+//!
+//! | Rust type                             | SQLite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `chrono::DateTime<Utc>`               | TEXT, INTEGER, REAL                                  |
+//! | `chrono::DateTime<Local>`             | TEXT, INTEGER, REAL                                  |
+//! | `chrono::DateTime<FixedOffset>`       | TEXT, INTEGER, REAL                                  |
+//! | `chrono::NaiveDateTime`               | TEXT, INTEGER, REAL                                  |
+//! | `chrono::NaiveDate`                   | TEXT, INTEGER, REAL                                  |
+//! | `chrono::NaiveTime`                   | TEXT, INTEGER, REAL                                  |
+//!
+//! ### [`time`](https://crates.io/crates/time)
+//!
+//! Requires the `time` Cargo feature flag.
+//!
+//! | Rust type                             | SQLite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `time::PrimitiveDateTime`             | TEXT, INTEGER, REAL                                  |
+//! | `time::OffsetDateTime`                | TEXT, INTEGER, REAL                                  |
+//! | `time::Date`                          | TEXT, INTEGER, REAL                                  |
+//! | `time::Time`                          | TEXT, INTEGER, REAL                                  |
+//!
+//! SQLite has no storage class for dates and times. Dates and times are encoded as ISO-8601
+//! TEXT (e.g. `2020-05-17 10:05:01.125`, with a `+HH:MM` suffix for types with an offset) which
+//! sorts correctly and is understood by the SQLite [date and time functions].
+//!
+//! Values are decoded from any of the formats understood by those functions: ISO-8601 TEXT,
+//! INTEGER as Unix time or REAL as a Julian day number. Dates and times that have an offset are
+//! converted to UTC when decoded into a type without one. To store Unix time, bind the
+//! timestamp itself (e.g. `DateTime::timestamp`).
+//!
+//! [date and time functions]: https://www.sqlite.org/lang_datefunc.html
+//!
+//! ### [`uuid`](https://crates.io/crates/uuid)
+//!
+//! Requires the `uuid` Cargo feature flag.
+//!
+//! | Rust type                             | SQLite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `uuid::Uuid`                          | BLOB, TEXT                                           |
+//! | `uuid::adapter::Hyphenated`           | TEXT, BLOB                                           |
+//!
+//! A `Uuid` is encoded as a 16-byte BLOB and a `Hyphenated` as TEXT. Either is decoded from a
+//! BLOB or TEXT.
+//!
+//! ### [`json`](https://crates.io/crates/serde_json)
+//!
+//! Requires the `json` Cargo feature flag.
+//!
+//! | Rust type                             | SQLite type(s)                                       |
+//! |---------------------------------------|------------------------------------------------------|
+//! | [`Json<T>`]                           | TEXT                                                 |
+//! | `serde_json::Value`                   | TEXT                                                 |
+//! | `&serde_json::value::RawValue`        | TEXT                                                 |
+//!
+//! [`Json<T>`]: crate::types::Json
+//!
 //! # Nullable
 //!
 //! In addition, `Option<T>` is supported where `T` implements `Type`. An `Option<T>` represents
@@ -29,6 +88,21 @@ mod float;
 mod int;
 mod str;
 
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
+
+#[cfg(feature = "chrono")]
+mod chrono;
+
+#[cfg(feature = "time")]
+mod time;
+
+#[cfg(feature = "uuid")]
+mod uuid;
+
+#[cfg(feature = "json")]
+mod json;
+
 impl<'de, T> Decode<'de, Sqlite> for Option<T>
 where
     T: Decode<'de, Sqlite>,
@@ -0,0 +1,154 @@
+use time::{Date, NumericalDuration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::sqlite::type_info::{SqliteType, SqliteTypeAffinity};
+use crate::sqlite::types::datetime::SqliteDateTime;
+use crate::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValue};
+use crate::types::Type;
+
+impl Type<Sqlite> for PrimitiveDateTime {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo::new(SqliteType::Text, SqliteTypeAffinity::Text)
+    }
+}
+
+impl Type<Sqlite> for OffsetDateTime {
+    fn type_info() -> SqliteTypeInfo {
+        <PrimitiveDateTime as Type<Sqlite>>::type_info()
+    }
+}
+
+impl Type<Sqlite> for Date {
+    fn type_info() -> SqliteTypeInfo {
+        <PrimitiveDateTime as Type<Sqlite>>::type_info()
+    }
+}
+
+impl Type<Sqlite> for Time {
+    fn type_info() -> SqliteTypeInfo {
+        <PrimitiveDateTime as Type<Sqlite>>::type_info()
+    }
+}
+
+// Formats as `HH:MM:SS` followed by as many digits of the fractional second as are needed
+// out of 3, 6 or 9 (as `%T%.f` does for chrono)
+fn format_time(time: Time) -> String {
+    let mut s = time.format("%H:%M:%S");
+    let nanosecond = time.nanosecond();
+
+    if nanosecond == 0 {
+        // no fractional seconds
+    } else if nanosecond % 1_000_000 == 0 {
+        s.push_str(&format!(".{:03}", nanosecond / 1_000_000));
+    } else if nanosecond % 1_000 == 0 {
+        s.push_str(&format!(".{:06}", nanosecond / 1_000));
+    } else {
+        s.push_str(&format!(".{:09}", nanosecond));
+    }
+
+    s
+}
+
+impl Encode<Sqlite> for PrimitiveDateTime {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(format!(
+            "{} {}",
+            self.date().format("%F"),
+            format_time(self.time())
+        )));
+    }
+}
+
+impl Encode<Sqlite> for OffsetDateTime {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        let offset = self.offset().as_seconds();
+
+        values.push(SqliteArgumentValue::Text(format!(
+            "{} {}{}{:02}:{:02}",
+            self.date().format("%F"),
+            format_time(self.time()),
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 3600,
+            offset.abs() / 60 % 60
+        )));
+    }
+}
+
+impl Encode<Sqlite> for Date {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(self.format("%F")));
+    }
+}
+
+impl Encode<Sqlite> for Time {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(format_time(*self)));
+    }
+}
+
+// The Unix times of -9999-01-01 00:00:00 and 9999-12-31 23:59:59 UTC, the range of dates of
+// `time` unless its `large-dates` feature is enabled
+const MIN_UNIX_TIME: i64 = -377_705_116_800;
+const MAX_UNIX_TIME: i64 = 253_402_300_799;
+
+impl<'de> Decode<'de, Sqlite> for OffsetDateTime {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        match SqliteDateTime::decode(&value)? {
+            SqliteDateTime::Unix {
+                seconds,
+                nanoseconds,
+            } => {
+                if seconds < MIN_UNIX_TIME || seconds > MAX_UNIX_TIME {
+                    return Err(decode_err!("Unix time {} out of range for time", seconds));
+                }
+
+                Ok(OffsetDateTime::from_unix_timestamp(seconds)
+                    + i64::from(nanoseconds).nanoseconds())
+            }
+
+            SqliteDateTime::Text(text) => {
+                let date = Date::try_from_ymd(text.year, text.month as u8, text.day as u8)
+                    .map_err(crate::Error::decode)?;
+
+                let time = Time::try_from_hms_nano(
+                    text.hour as u8,
+                    text.minute as u8,
+                    text.second as u8,
+                    text.nanosecond,
+                )
+                .map_err(crate::Error::decode)?;
+
+                Ok(PrimitiveDateTime::new(date, time)
+                    .assume_offset(UtcOffset::seconds(text.offset)))
+            }
+        }
+    }
+}
+
+// Like the SQLite date and time functions, a date or time with an offset is converted to UTC
+
+impl<'de> Decode<'de, Sqlite> for PrimitiveDateTime {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: OffsetDateTime = Decode::<Sqlite>::decode(value)?;
+        let datetime = datetime.to_offset(UtcOffset::UTC);
+
+        Ok(PrimitiveDateTime::new(datetime.date(), datetime.time()))
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for Date {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: PrimitiveDateTime = Decode::<Sqlite>::decode(value)?;
+
+        Ok(datetime.date())
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for Time {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let datetime: PrimitiveDateTime = Decode::<Sqlite>::decode(value)?;
+
+        Ok(datetime.time())
+    }
+}
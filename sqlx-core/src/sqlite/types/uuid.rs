@@ -0,0 +1,62 @@
+use uuid::adapter::Hyphenated;
+use uuid::Uuid;
+
+use crate::decode::Decode;
+use crate::encode::Encode;
+use crate::error::UnexpectedNullError;
+use crate::sqlite::type_info::{SqliteType, SqliteTypeAffinity};
+use crate::sqlite::{Sqlite, SqliteArgumentValue, SqliteTypeInfo, SqliteValue};
+use crate::types::Type;
+
+impl Type<Sqlite> for Uuid {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo::new(SqliteType::Blob, SqliteTypeAffinity::Blob)
+    }
+}
+
+impl Encode<Sqlite> for Uuid {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Blob(self.as_bytes().to_vec()));
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for Uuid {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        match value.r#type() {
+            // either the 16 bytes of the UUID or its text form
+            Some(SqliteType::Blob) => Uuid::from_slice(value.blob()).map_err(crate::Error::decode),
+            Some(SqliteType::Text) => {
+                Uuid::parse_str(value.text().unwrap_or_default()).map_err(crate::Error::decode)
+            }
+
+            Some(ty) => Err(decode_err!(
+                "expected a UUID stored as BLOB or TEXT; received {:?}",
+                ty
+            )),
+
+            None => Err(crate::Error::decode(UnexpectedNullError)),
+        }
+    }
+}
+
+// A UUID in its hyphenated text form, for storing a UUID as TEXT
+
+impl Type<Sqlite> for Hyphenated {
+    fn type_info() -> SqliteTypeInfo {
+        SqliteTypeInfo::new(SqliteType::Text, SqliteTypeAffinity::Text)
+    }
+}
+
+impl Encode<Sqlite> for Hyphenated {
+    fn encode(&self, values: &mut Vec<SqliteArgumentValue>) {
+        values.push(SqliteArgumentValue::Text(self.to_string()));
+    }
+}
+
+impl<'de> Decode<'de, Sqlite> for Hyphenated {
+    fn decode(value: SqliteValue<'de>) -> crate::Result<Self> {
+        let uuid: Uuid = Decode::<Sqlite>::decode(value)?;
+
+        Ok(uuid.to_hyphenated())
+    }
+}
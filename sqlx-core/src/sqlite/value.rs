@@ -1,11 +1,16 @@
+use core::marker::PhantomData;
+use core::ptr::NonNull;
 use core::slice;
 
 use std::ffi::CStr;
+use std::os::raw::{c_int, c_void};
 use std::str::from_utf8_unchecked;
 
 use libsqlite3_sys::{
     sqlite3_column_blob, sqlite3_column_bytes, sqlite3_column_double, sqlite3_column_int,
-    sqlite3_column_int64, sqlite3_column_text, sqlite3_column_type, SQLITE_BLOB, SQLITE_FLOAT,
+    sqlite3_column_int64, sqlite3_column_text, sqlite3_column_type, sqlite3_value,
+    sqlite3_value_blob, sqlite3_value_bytes, sqlite3_value_double, sqlite3_value_int,
+    sqlite3_value_int64, sqlite3_value_text, sqlite3_value_type, SQLITE_BLOB, SQLITE_FLOAT,
     SQLITE_INTEGER, SQLITE_NULL, SQLITE_TEXT,
 };
 
@@ -15,14 +20,44 @@ use crate::sqlite::{Sqlite, SqliteTypeInfo};
 use crate::value::RawValue;
 
 pub struct SqliteValue<'c> {
-    pub(super) index: i32,
-    pub(super) statement: &'c Statement,
+    pub(super) handle: ValueHandle<'c>,
+}
+
+pub(super) enum ValueHandle<'c> {
+    // A column of the current row of a statement
+    Column {
+        statement: &'c Statement,
+        index: i32,
+    },
+
+    // A protected value, e.g. an argument of a user-defined function
+    Value(NonNull<sqlite3_value>, PhantomData<&'c ()>),
+}
+
+impl<'c> SqliteValue<'c> {
+    pub(super) fn column(statement: &'c Statement, index: i32) -> Self {
+        SqliteValue {
+            handle: ValueHandle::Column { statement, index },
+        }
+    }
+
+    // SAFETY: the value must be valid for `'c`
+    pub(super) unsafe fn value(value: *mut sqlite3_value) -> Self {
+        SqliteValue {
+            handle: ValueHandle::Value(
+                NonNull::new(value).expect("BUG: null sqlite3_value"),
+                PhantomData,
+            ),
+        }
+    }
 }
 
 // https://www.sqlite.org/c3ref/column_blob.html
 // https://www.sqlite.org/capi3ref.html#sqlite3_column_blob
+// https://www.sqlite.org/c3ref/value_blob.html
 
-// These routines return information about a single column of the current result row of a query.
+// These routines return information about a single column of the current result row of a query
+// or about a protected value.
 
 impl<'c> SqliteValue<'c> {
     /// Returns true if the value should be intrepreted as NULL.
@@ -30,14 +65,18 @@ impl<'c> SqliteValue<'c> {
         self.r#type().is_none()
     }
 
-    fn r#type(&self) -> Option<SqliteType> {
-        let type_code = unsafe {
-            if let Some(handle) = self.statement.handle() {
-                sqlite3_column_type(handle, self.index)
-            } else {
-                // unreachable: null statements do not have any values to type
-                return None;
-            }
+    pub(super) fn r#type(&self) -> Option<SqliteType> {
+        let type_code = match self.handle {
+            ValueHandle::Column { statement, index } => unsafe {
+                if let Some(handle) = statement.handle() {
+                    sqlite3_column_type(handle, index)
+                } else {
+                    // unreachable: null statements do not have any values to type
+                    return None;
+                }
+            },
+
+            ValueHandle::Value(value, _) => unsafe { sqlite3_value_type(value.as_ptr()) },
         };
 
         // SQLITE_INTEGER, SQLITE_FLOAT, SQLITE_TEXT, SQLITE_BLOB, or SQLITE_NULL
@@ -54,64 +93,90 @@ impl<'c> SqliteValue<'c> {
 
     /// Returns the 32-bit INTEGER result.
     pub(super) fn int(&self) -> i32 {
-        unsafe {
-            self.statement
-                .handle()
-                .map_or(0, |handle| sqlite3_column_int(handle, self.index))
+        match self.handle {
+            ValueHandle::Column { statement, index } => unsafe {
+                statement
+                    .handle()
+                    .map_or(0, |handle| sqlite3_column_int(handle, index))
+            },
+
+            ValueHandle::Value(value, _) => unsafe { sqlite3_value_int(value.as_ptr()) },
         }
     }
 
     /// Returns the 64-bit INTEGER result.
     pub(super) fn int64(&self) -> i64 {
-        unsafe {
-            self.statement
-                .handle()
-                .map_or(0, |handle| sqlite3_column_int64(handle, self.index))
+        match self.handle {
+            ValueHandle::Column { statement, index } => unsafe {
+                statement
+                    .handle()
+                    .map_or(0, |handle| sqlite3_column_int64(handle, index))
+            },
+
+            ValueHandle::Value(value, _) => unsafe { sqlite3_value_int64(value.as_ptr()) },
         }
     }
 
     /// Returns the 64-bit, REAL result.
     pub(super) fn double(&self) -> f64 {
-        unsafe {
-            self.statement
-                .handle()
-                .map_or(0.0, |handle| sqlite3_column_double(handle, self.index))
+        match self.handle {
+            ValueHandle::Column { statement, index } => unsafe {
+                statement
+                    .handle()
+                    .map_or(0.0, |handle| sqlite3_column_double(handle, index))
+            },
+
+            ValueHandle::Value(value, _) => unsafe { sqlite3_value_double(value.as_ptr()) },
         }
     }
 
     /// Returns the UTF-8 TEXT result.
     pub(super) fn text(&self) -> Option<&'c str> {
-        unsafe {
-            self.statement.handle().and_then(|handle| {
-                let ptr = sqlite3_column_text(handle, self.index);
+        let ptr = match self.handle {
+            ValueHandle::Column { statement, index } => unsafe {
+                statement
+                    .handle()
+                    .map(|handle| sqlite3_column_text(handle, index))?
+            },
+
+            ValueHandle::Value(value, _) => unsafe { sqlite3_value_text(value.as_ptr()) },
+        };
 
-                if ptr.is_null() {
-                    None
-                } else {
-                    Some(from_utf8_unchecked(CStr::from_ptr(ptr as _).to_bytes()))
-                }
-            })
+        if ptr.is_null() {
+            None
+        } else {
+            unsafe { Some(from_utf8_unchecked(CStr::from_ptr(ptr as _).to_bytes())) }
         }
     }
 
     fn bytes(&self) -> usize {
         // Returns the size of the result in bytes.
-        unsafe {
-            self.statement
-                .handle()
-                .map_or(0, |handle| sqlite3_column_bytes(handle, self.index)) as usize
-        }
+        let bytes: c_int = match self.handle {
+            ValueHandle::Column { statement, index } => unsafe {
+                statement
+                    .handle()
+                    .map_or(0, |handle| sqlite3_column_bytes(handle, index))
+            },
+
+            ValueHandle::Value(value, _) => unsafe { sqlite3_value_bytes(value.as_ptr()) },
+        };
+
+        bytes as usize
     }
 
     /// Returns the BLOB result.
     pub(super) fn blob(&self) -> &'c [u8] {
-        let ptr = unsafe {
-            if let Some(handle) = self.statement.handle() {
-                sqlite3_column_blob(handle, self.index)
-            } else {
-                // Null statements do not exist
-                return &[];
-            }
+        let ptr: *const c_void = match self.handle {
+            ValueHandle::Column { statement, index } => unsafe {
+                if let Some(handle) = statement.handle() {
+                    sqlite3_column_blob(handle, index)
+                } else {
+                    // Null statements do not exist
+                    return &[];
+                }
+            },
+
+            ValueHandle::Value(value, _) => unsafe { sqlite3_value_blob(value.as_ptr()) },
         };
 
         if ptr.is_null() {
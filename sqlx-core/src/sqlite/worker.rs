@@ -1,43 +1,87 @@
-use crossbeam_queue::ArrayQueue;
+use crossbeam_queue::SegQueue;
 use futures_channel::oneshot;
+use once_cell::sync::OnceCell;
+use std::collections::VecDeque;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use std::thread::{park, spawn, JoinHandle};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{park, spawn, Thread};
 
 // After tinkering with this, I believe the safest solution is to spin up a discrete thread per
 // SQLite connection and perform all I/O operations for SQLite on _that_ thread. To this effect
 // we have a worker struct that is a thin message passing API to run messages on the worker thread.
 
+// With many connections, e.g. to a database per tenant, a thread per connection is a lot of
+// threads. A connection can instead queue its messages to a pool of threads that is shared by
+// all such connections; a thread of the pool runs one message of a connection at a time so the
+// messages of a connection still run in order and never at the same time.
+
+type Message = Box<dyn FnOnce() + Send>;
+
+/// How the SQLite calls of a connection are run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WorkerMode {
+    /// On a thread of the connection.
+    Dedicated,
+
+    /// On the threads of a pool shared by the connections of the process, which is started
+    /// with `threads` threads by the first connection that uses it.
+    Shared { threads: usize },
+}
+
 #[derive(Clone)]
-pub(crate) struct Worker {
-    running: Arc<AtomicBool>,
-    queue: Arc<ArrayQueue<Box<dyn FnOnce() + Send>>>,
-    handle: Arc<JoinHandle<()>>,
+pub(crate) enum Worker {
+    Dedicated(Arc<DedicatedThread>),
+
+    Shared(Arc<SharedQueue>),
 }
 
 impl Worker {
-    pub(crate) fn new() -> Self {
-        let queue: Arc<ArrayQueue<Box<dyn FnOnce() + Send>>> = Arc::new(ArrayQueue::new(1));
+    pub(crate) fn new(mode: WorkerMode) -> Self {
+        let threads = match mode {
+            WorkerMode::Dedicated => return Self::dedicated(),
+            WorkerMode::Shared { threads } => threads,
+        };
+
+        Worker::Shared(Arc::new(SharedQueue {
+            pool: SharedPool::get_or_start(threads),
+            state: Mutex::new(SharedQueueState {
+                messages: VecDeque::new(),
+                scheduled: false,
+            }),
+        }))
+    }
+
+    fn dedicated() -> Self {
+        let queue: Arc<SegQueue<Message>> = Arc::new(SegQueue::new());
         let running = Arc::new(AtomicBool::new(true));
 
-        Self {
-            handle: Arc::new(spawn({
-                let queue = queue.clone();
-                let running = running.clone();
+        let handle = spawn({
+            let queue = queue.clone();
+            let running = running.clone();
+
+            move || loop {
+                // the messages that were queued before the worker was dropped still run,
+                // e.g. to close the connection
+                let running = running.load(Ordering::SeqCst);
 
-                move || {
-                    while running.load(Ordering::SeqCst) {
-                        if let Ok(message) = queue.pop() {
-                            (message)();
-                        }
+                while let Ok(message) = queue.pop() {
+                    (message)();
+                }
 
-                        park();
-                    }
+                if !running {
+                    break;
                 }
-            })),
+
+                park();
+            }
+        });
+
+        Worker::Dedicated(Arc::new(DedicatedThread {
             queue,
             running,
-        }
+            thread: handle.thread().clone(),
+        }))
     }
 
     pub(crate) async fn run<F, R>(&mut self, f: F) -> R
@@ -48,20 +92,154 @@ impl Worker {
     {
         let (sender, receiver) = oneshot::channel::<R>();
 
-        let _ = self.queue.push(Box::new(move || {
+        self.execute(move || {
             let _ = sender.send(f());
-        }));
-
-        self.handle.thread().unpark();
+        });
 
         receiver.await.unwrap()
     }
+
+    // Runs `f` on the worker thread, after the messages that are already queued, without
+    // waiting for it to complete, e.g. to clean up in `Drop`
+    pub(crate) fn execute<F>(&self, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        match self {
+            Worker::Dedicated(thread) => {
+                thread.queue.push(Box::new(f));
+                thread.thread.unpark();
+            }
+
+            Worker::Shared(queue) => queue.push(Box::new(f)),
+        }
+    }
+}
+
+// The thread of a connection, which stops once the last worker of the connection is dropped
+pub(crate) struct DedicatedThread {
+    queue: Arc<SegQueue<Message>>,
+    running: Arc<AtomicBool>,
+    thread: Thread,
 }
 
-impl Drop for Worker {
+impl Drop for DedicatedThread {
     fn drop(&mut self) {
-        if Arc::strong_count(&self.handle) == 1 {
-            self.running.store(false, Ordering::SeqCst);
+        self.running.store(false, Ordering::SeqCst);
+        self.thread.unpark();
+    }
+}
+
+// The messages of a connection that uses the shared pool
+pub(crate) struct SharedQueue {
+    pool: &'static SharedPool,
+    state: Mutex<SharedQueueState>,
+}
+
+struct SharedQueueState {
+    messages: VecDeque<Message>,
+
+    // if the queue is waiting in the pool for a thread to run its next message, or a
+    // thread is running it
+    scheduled: bool,
+}
+
+impl SharedQueue {
+    fn push(self: &Arc<Self>, message: Message) {
+        let mut state = self.state.lock().unwrap();
+
+        state.messages.push_back(message);
+
+        if !state.scheduled {
+            state.scheduled = true;
+            drop(state);
+
+            self.pool.schedule(Arc::clone(self));
+        }
+    }
+
+    // Runs the next message, then lets the queue of another connection run before this one
+    // runs its next message
+    fn run_next(self: Arc<Self>) {
+        let message = self.state.lock().unwrap().messages.pop_front();
+
+        if let Some(message) = message {
+            // a panic must not stop the thread, which runs the messages of other connections
+            let _ = catch_unwind(AssertUnwindSafe(message));
+        }
+
+        let mut state = self.state.lock().unwrap();
+
+        if state.messages.is_empty() {
+            state.scheduled = false;
+        } else {
+            drop(state);
+
+            self.pool.schedule(self);
+        }
+    }
+}
+
+struct SharedPool {
+    queues: Mutex<VecDeque<Arc<SharedQueue>>>,
+    available: Condvar,
+
+    // the number of threads the pool was started with
+    threads: usize,
+}
+
+static SHARED_POOL: OnceCell<SharedPool> = OnceCell::new();
+
+impl SharedPool {
+    fn get_or_start(threads: usize) -> &'static SharedPool {
+        let mut started = false;
+
+        let pool = SHARED_POOL.get_or_init(|| {
+            started = true;
+
+            SharedPool {
+                queues: Mutex::new(VecDeque::new()),
+                available: Condvar::new(),
+                threads,
+            }
+        });
+
+        if started {
+            for _ in 0..threads {
+                spawn(move || pool.work());
+            }
+        } else if pool.threads != threads {
+            log::warn!(
+                "the shared SQLite worker pool was already started with {} threads; \
+                 `worker_threads={}` is ignored",
+                pool.threads,
+                threads
+            );
+        }
+
+        pool
+    }
+
+    fn schedule(&self, queue: Arc<SharedQueue>) {
+        self.queues.lock().unwrap().push_back(queue);
+        self.available.notify_one();
+    }
+
+    // The threads of the pool run for the lifetime of the process
+    fn work(&self) {
+        loop {
+            let mut queues = self.queues.lock().unwrap();
+
+            let queue = loop {
+                match queues.pop_front() {
+                    Some(queue) => break queue,
+                    None => queues = self.available.wait(queues).unwrap(),
+                }
+            };
+
+            drop(queues);
+
+            queue.run_next();
         }
     }
 }
@@ -156,6 +156,11 @@ where
     fn ping(&mut self) -> BoxFuture<'_, crate::Result<()>> {
         self.deref_mut().ping()
     }
+
+    #[inline]
+    fn clear_cached_statements(&mut self) -> BoxFuture<'_, crate::Result<()>> {
+        self.deref_mut().clear_cached_statements()
+    }
 }
 
 impl<DB, C> Executor for Transaction<C>
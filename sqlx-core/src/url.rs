@@ -106,6 +106,12 @@ impl Url {
             .query_pairs()
             .find_map(|(key_, val)| if key == key_ { Some(val) } else { None })
     }
+
+    pub fn params<'a>(&'a self, key: &'a str) -> impl Iterator<Item = Cow<'a, str>> + 'a {
+        self.0
+            .query_pairs()
+            .filter_map(move |(key_, val)| if key == key_ { Some(val) } else { None })
+    }
 }
 
 #[cfg(test)]
@@ -7,12 +7,42 @@ pub enum ParamChecking {
     Weak,
 }
 
+// How the bind parameters of a query are written
+pub enum ParamStyle {
+    // `$1 .. $N`; a list argument is bound as an array
+    #[cfg(feature = "postgres")]
+    Dollar,
+
+    // `?`; a list argument is expanded to a `?` for each element, or to `empty_list` if it
+    // has no elements (which must be valid SQL in place of the list in `IN (..)`); with
+    // `user_variables`, an `@name` that is not a named argument is a user variable
+    #[cfg(any(feature = "mysql", feature = "sqlite"))]
+    Question {
+        empty_list: &'static str,
+        user_variables: bool,
+    },
+}
+
+impl ParamStyle {
+    pub fn user_variables(&self) -> bool {
+        match self {
+            #[cfg(feature = "postgres")]
+            ParamStyle::Dollar => false,
+
+            #[cfg(any(feature = "mysql", feature = "sqlite"))]
+            ParamStyle::Question { user_variables, .. } => *user_variables,
+        }
+    }
+}
+
 pub trait DatabaseExt: Database {
     const DATABASE_PATH: &'static str;
     const ROW_PATH: &'static str;
 
     const PARAM_CHECKING: ParamChecking;
 
+    const PARAM_STYLE: ParamStyle;
+
     fn db_path() -> syn::Path {
         syn::parse_str(Self::DATABASE_PATH).unwrap()
     }
@@ -34,6 +64,7 @@ macro_rules! impl_database_ext {
             $($(#[$meta:meta])? $ty:ty $(| $input:ty)?),*$(,)?
         },
         ParamChecking::$param_checking:ident,
+        params = $param_style:expr,
         feature-types: $name:ident => $get_gate:expr,
         row = $row:path
     ) => {
@@ -41,6 +72,7 @@ macro_rules! impl_database_ext {
             const DATABASE_PATH: &'static str = stringify!($database);
             const ROW_PATH: &'static str = stringify!($row);
             const PARAM_CHECKING: $crate::database::ParamChecking = $crate::database::ParamChecking::$param_checking;
+            const PARAM_STYLE: $crate::database::ParamStyle = $param_style;
 
             fn param_type_for_id(info: &Self::TypeInfo) -> Option<&'static str> {
                 match () {
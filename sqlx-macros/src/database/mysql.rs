@@ -45,6 +45,10 @@ impl_database_ext! {
         sqlx::types::BigDecimal,
     },
     ParamChecking::Weak,
+    params = crate::database::ParamStyle::Question {
+        empty_list: "SELECT NULL FROM DUAL WHERE FALSE",
+        user_variables: true,
+    },
     feature-types: info => info.type_feature_gate(),
     row = sqlx::mysql::MySqlRow
 }
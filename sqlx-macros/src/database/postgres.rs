@@ -96,6 +96,7 @@ impl_database_ext! {
 
     },
     ParamChecking::Strong,
+    params = crate::database::ParamStyle::Dollar,
     feature-types: info => info.type_feature_gate(),
     row = sqlx::postgres::PgRow
 }
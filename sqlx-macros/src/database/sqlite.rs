@@ -9,6 +9,10 @@ impl_database_ext! {
         Vec<u8>,
     },
     ParamChecking::Weak,
+    params = crate::database::ParamStyle::Question {
+        empty_list: "",
+        user_variables: false,
+    },
     feature-types: _info => None,
     row = sqlx::sqlite::SqliteRow
 }
@@ -1,7 +1,7 @@
 use proc_macro2::Ident;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{Attribute, DeriveInput, Field, Lit, Meta, MetaNameValue, NestedMeta, Variant};
+use syn::{Attribute, DeriveInput, Field, Lit, Meta, MetaNameValue, NestedMeta, Type, Variant};
 
 macro_rules! assert_attribute {
     ($e:expr, $err:expr, $input:expr) => {
@@ -30,6 +30,11 @@ macro_rules! try_set {
 pub enum RenameAll {
     LowerCase,
     SnakeCase,
+    UpperCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    CamelCase,
+    PascalCase,
 }
 
 pub struct SqlxContainerAttributes {
@@ -37,10 +42,15 @@ pub struct SqlxContainerAttributes {
     pub rename: Option<String>,
     pub rename_all: Option<RenameAll>,
     pub repr: Option<Ident>,
+    pub no_pg_array: bool,
 }
 
 pub struct SqlxChildAttributes {
     pub rename: Option<String>,
+    pub default: bool,
+    pub flatten: bool,
+    pub skip: bool,
+    pub try_from: Option<Type>,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
@@ -48,6 +58,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
     let mut repr = None;
     let mut rename = None;
     let mut rename_all = None;
+    let mut no_pg_array = None;
 
     for attr in input {
         let meta = attr
@@ -62,6 +73,10 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 try_set!(transparent, true, value)
                             }
 
+                            Meta::Path(p) if p.is_ident("no_pg_array") => {
+                                try_set!(no_pg_array, true, value)
+                            }
+
                             Meta::NameValue(MetaNameValue {
                                 path,
                                 lit: Lit::Str(val),
@@ -70,6 +85,11 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
                                 let val = match &*val.value() {
                                     "lowercase" => RenameAll::LowerCase,
                                     "snake_case" => RenameAll::SnakeCase,
+                                    "UPPERCASE" => RenameAll::UpperCase,
+                                    "SCREAMING_SNAKE_CASE" => RenameAll::ScreamingSnakeCase,
+                                    "kebab-case" => RenameAll::KebabCase,
+                                    "camelCase" => RenameAll::CamelCase,
+                                    "PascalCase" => RenameAll::PascalCase,
 
                                     _ => fail!(meta, "unexpected value for rename_all"),
                                 };
@@ -109,11 +129,16 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
         repr,
         rename,
         rename_all,
+        no_pg_array: no_pg_array.unwrap_or(false),
     })
 }
 
 pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttributes> {
     let mut rename = None;
+    let mut default = None;
+    let mut flatten = None;
+    let mut skip = None;
+    let mut try_from = None;
 
     for attr in input {
         let meta = attr
@@ -131,6 +156,24 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                                 ..
                             }) if path.is_ident("rename") => try_set!(rename, val.value(), value),
 
+                            Meta::NameValue(MetaNameValue {
+                                path,
+                                lit: Lit::Str(val),
+                                ..
+                            }) if path.is_ident("try_from") => {
+                                try_set!(try_from, val.parse()?, value)
+                            }
+
+                            Meta::Path(p) if p.is_ident("default") => {
+                                try_set!(default, true, value)
+                            }
+
+                            Meta::Path(p) if p.is_ident("flatten") => {
+                                try_set!(flatten, true, value)
+                            }
+
+                            Meta::Path(p) if p.is_ident("skip") => try_set!(skip, true, value),
+
                             u => fail!(u, "unexpected attribute"),
                         },
                         u => fail!(u, "unexpected attribute"),
@@ -141,10 +184,19 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         }
     }
 
-    Ok(SqlxChildAttributes { rename })
+    Ok(SqlxChildAttributes {
+        rename,
+        default: default.unwrap_or(false),
+        flatten: flatten.unwrap_or(false),
+        skip: skip.unwrap_or(false),
+        try_from,
+    })
 }
 
-pub fn check_transparent_attributes(input: &DeriveInput, field: &Field) -> syn::Result<()> {
+pub fn check_transparent_attributes(
+    input: &DeriveInput,
+    field: &Field,
+) -> syn::Result<SqlxContainerAttributes> {
     let attributes = parse_container_attributes(&input.attrs)?;
 
     assert_attribute!(
@@ -161,15 +213,15 @@ pub fn check_transparent_attributes(input: &DeriveInput, field: &Field) -> syn::
 
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
-    let attributes = parse_child_attributes(&field.attrs)?;
+    let field_attributes = parse_child_attributes(&field.attrs)?;
 
     assert_attribute!(
-        attributes.rename.is_none(),
+        field_attributes.rename.is_none(),
         "unexpected #[sqlx(rename = ..)]",
         field
     );
 
-    Ok(())
+    Ok(attributes)
 }
 
 pub fn check_enum_attributes<'a>(input: &'a DeriveInput) -> syn::Result<SqlxContainerAttributes> {
@@ -219,6 +271,12 @@ pub fn check_strong_enum_attributes(
 
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
+    assert_attribute!(
+        !attributes.no_pg_array,
+        "unexpected #[sqlx(no_pg_array)]",
+        input
+    );
+
     Ok(attributes)
 }
 
@@ -242,6 +300,12 @@ pub fn check_struct_attributes<'a>(
 
     assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
 
+    assert_attribute!(
+        !attributes.no_pg_array,
+        "unexpected #[sqlx(no_pg_array)]",
+        input
+    );
+
     for field in fields {
         let attributes = parse_child_attributes(&field.attrs)?;
 
@@ -254,3 +318,84 @@ pub fn check_struct_attributes<'a>(
 
     Ok(attributes)
 }
+
+pub fn check_row_attributes<'a>(
+    input: &'a DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<SqlxContainerAttributes> {
+    let attributes = parse_container_attributes(&input.attrs)?;
+
+    assert_attribute!(
+        !attributes.transparent,
+        "unexpected #[sqlx(transparent)]",
+        input
+    );
+
+    assert_attribute!(
+        attributes.rename.is_none(),
+        "unexpected #[sqlx(rename = ..)]",
+        input
+    );
+
+    assert_attribute!(attributes.repr.is_none(), "unexpected #[repr(..)]", input);
+
+    assert_attribute!(
+        !attributes.no_pg_array,
+        "unexpected #[sqlx(no_pg_array)]",
+        input
+    );
+
+    for field in fields {
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        if attributes.skip {
+            assert_attribute!(
+                attributes.rename.is_none()
+                    && !attributes.default
+                    && !attributes.flatten
+                    && attributes.try_from.is_none(),
+                "#[sqlx(skip)] cannot be combined with other attributes",
+                field
+            );
+        }
+
+        if attributes.flatten {
+            assert_attribute!(
+                attributes.rename.is_none() && attributes.try_from.is_none(),
+                "#[sqlx(flatten)] cannot be combined with #[sqlx(rename = ..)] or #[sqlx(try_from = ..)]",
+                field
+            );
+        }
+    }
+
+    Ok(attributes)
+}
+
+pub fn check_unnamed_row_attributes<'a>(
+    input: &'a DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<SqlxContainerAttributes> {
+    let attributes = check_row_attributes(input, fields)?;
+
+    assert_attribute!(
+        attributes.rename_all.is_none(),
+        "unexpected #[sqlx(rename_all = ..)]",
+        input
+    );
+
+    for field in fields {
+        let attributes = parse_child_attributes(&field.attrs)?;
+
+        assert_attribute!(
+            attributes.rename.is_none()
+                && !attributes.default
+                && !attributes.flatten
+                && !attributes.skip
+                && attributes.try_from.is_none(),
+            "the fields of a tuple struct are decoded by position and take no attributes",
+            field
+        );
+    }
+
+    Ok(attributes)
+}
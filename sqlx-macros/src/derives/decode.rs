@@ -176,7 +176,9 @@ fn expand_derive_decode_struct(
         for field in fields {
             let ty = &field.ty;
 
-            predicates.push(parse_quote!(#ty: sqlx::decode::Decode<'de, sqlx::Postgres>));
+            // fields are decoded from a borrowed record so they must be decodable
+            // for any lifetime; this allows composites to be nested in one another
+            predicates.push(parse_quote!(#ty: for<'r> sqlx::decode::Decode<'r, sqlx::Postgres>));
             predicates.push(parse_quote!(#ty: sqlx::types::Type<sqlx::Postgres>));
         }
 
@@ -1,11 +1,14 @@
 use proc_macro2::Span;
 use quote::quote;
 use syn::{
-    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Field,
-    Fields, FieldsNamed, Lifetime, Stmt,
+    parse_quote, punctuated::Punctuated, token::Comma, Data, DataStruct, DeriveInput, Expr, Field,
+    Fields, FieldsNamed, FieldsUnnamed, Lifetime, Stmt,
 };
 
-use super::attributes::parse_child_attributes;
+use super::attributes::{
+    check_row_attributes, check_unnamed_row_attributes, parse_child_attributes,
+};
+use super::rename_all;
 
 pub fn expand_derive_from_row(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     match &input.data {
@@ -15,12 +18,9 @@ pub fn expand_derive_from_row(input: &DeriveInput) -> syn::Result<proc_macro2::T
         }) => expand_derive_from_row_struct(input, named),
 
         Data::Struct(DataStruct {
-            fields: Fields::Unnamed(_),
+            fields: Fields::Unnamed(FieldsUnnamed { unnamed, .. }),
             ..
-        }) => Err(syn::Error::new_spanned(
-            input,
-            "tuple structs are not supported",
-        )),
+        }) => expand_derive_from_row_struct_unnamed(input, unnamed),
 
         Data::Struct(DataStruct {
             fields: Fields::Unit,
@@ -61,32 +61,94 @@ fn expand_derive_from_row_struct(
         generics.params.insert(0, parse_quote!(#lifetime));
     }
 
+    let container_attributes = check_row_attributes(input, fields)?;
+
     let predicates = &mut generics.make_where_clause().predicates;
 
     predicates.push(parse_quote!(&#lifetime str: sqlx::row::ColumnIndex<#lifetime, R>));
 
+    let mut reads: Vec<Stmt> = Vec::with_capacity(fields.len());
+
     for field in fields {
+        let id = &field.ident;
+        let attributes = parse_child_attributes(&field.attrs)?;
         let ty = &field.ty;
 
-        predicates.push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
-        predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
-    }
+        if attributes.skip {
+            predicates.push(parse_quote!(#ty: std::default::Default));
 
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
+            reads.push(parse_quote!(
+                let #id: #ty = std::default::Default::default();
+            ));
+
+            continue;
+        }
 
-    let reads = fields.iter().filter_map(|field| -> Option<Stmt> {
-        let id = &field.ident.as_ref()?;
-        let attributes = parse_child_attributes(&field.attrs).unwrap();
-        let id_s = match attributes.rename {
-            Some(rename) => rename,
-            None => id.to_string().trim_start_matches("r#").to_owned(),
+        let expr: Expr = if attributes.flatten {
+            predicates.push(parse_quote!(#ty: sqlx::row::FromRow<#lifetime, R>));
+
+            parse_quote!(<#ty as sqlx::row::FromRow<#lifetime, R>>::from_row(row))
+        } else {
+            let id_s = match attributes.rename {
+                Some(rename) => rename,
+                None => {
+                    let name = id.as_ref().unwrap().to_string();
+                    let name = name.trim_start_matches("r#");
+
+                    match container_attributes.rename_all {
+                        Some(pattern) => rename_all(name, pattern),
+                        None => name.to_owned(),
+                    }
+                }
+            };
+
+            match attributes.try_from {
+                Some(try_from) => {
+                    predicates.push(
+                        parse_quote!(#try_from: sqlx::decode::Decode<#lifetime, R::Database>),
+                    );
+                    predicates.push(parse_quote!(#try_from: sqlx::types::Type<R::Database>));
+                    predicates.push(parse_quote!(#ty: std::convert::TryFrom<#try_from>));
+                    predicates.push(parse_quote!(
+                        <#ty as std::convert::TryFrom<#try_from>>::Error:
+                            std::error::Error + Send + Sync + 'static
+                    ));
+
+                    parse_quote!(row.try_get::<#try_from, _>(#id_s).and_then(|value| {
+                        <#ty as std::convert::TryFrom<#try_from>>::try_from(value)
+                            .map_err(|err| sqlx::Error::Decode(err.into()))
+                    }))
+                }
+
+                None => {
+                    predicates
+                        .push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
+                    predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
+
+                    parse_quote!(row.try_get::<#ty, _>(#id_s))
+                }
+            }
         };
-        let ty = &field.ty;
 
-        Some(parse_quote!(
-            let #id: #ty = row.try_get(#id_s)?;
-        ))
-    });
+        if attributes.default {
+            predicates.push(parse_quote!(#ty: std::default::Default));
+
+            // a missing column is not an error but a value which fails to decode still is
+            reads.push(parse_quote!(
+                let #id: #ty = match #expr {
+                    Ok(value) => value,
+                    Err(sqlx::Error::ColumnNotFound(_)) => std::default::Default::default(),
+                    Err(err) => return Err(err),
+                };
+            ));
+        } else {
+            reads.push(parse_quote!(
+                let #id: #ty = #expr?;
+            ));
+        }
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
 
     let names = fields.iter().map(|field| &field.ident);
 
@@ -102,3 +164,57 @@ fn expand_derive_from_row_struct(
         }
     ))
 }
+
+// A tuple struct is decoded by the position of each column instead of its name
+fn expand_derive_from_row_struct_unnamed(
+    input: &DeriveInput,
+    fields: &Punctuated<Field, Comma>,
+) -> syn::Result<proc_macro2::TokenStream> {
+    check_unnamed_row_attributes(input, fields)?;
+
+    let ident = &input.ident;
+
+    let generics = &input.generics;
+
+    let (lifetime, provided) = generics
+        .lifetimes()
+        .next()
+        .map(|def| (def.lifetime.clone(), false))
+        .unwrap_or_else(|| (Lifetime::new("'a", Span::call_site()), true));
+
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let mut generics = generics.clone();
+    generics
+        .params
+        .insert(0, parse_quote!(R: sqlx::Row<#lifetime>));
+
+    if provided {
+        generics.params.insert(0, parse_quote!(#lifetime));
+    }
+
+    let predicates = &mut generics.make_where_clause().predicates;
+
+    predicates.push(parse_quote!(usize: sqlx::row::ColumnIndex<#lifetime, R>));
+
+    for field in fields {
+        let ty = &field.ty;
+
+        predicates.push(parse_quote!(#ty: sqlx::decode::Decode<#lifetime, R::Database>));
+        predicates.push(parse_quote!(#ty: sqlx::types::Type<R::Database>));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+    let gets = (0..fields.len()).map(|i| quote!(row.try_get(#i)?));
+
+    Ok(quote!(
+        impl #impl_generics sqlx::row::FromRow<#lifetime, R> for #ident #ty_generics #where_clause {
+            fn from_row(row: &R) -> sqlx::Result<Self> {
+                Ok(#ident (
+                    #(#gets),*
+                ))
+            }
+        }
+    ))
+}
@@ -49,7 +49,7 @@ fn expand_derive_has_sql_type_transparent(
     input: &DeriveInput,
     field: &Field,
 ) -> syn::Result<proc_macro2::TokenStream> {
-    check_transparent_attributes(input, field)?;
+    let attributes = check_transparent_attributes(input, field)?;
 
     let ident = &input.ident;
     let ty = &field.ty;
@@ -59,22 +59,44 @@ fn expand_derive_has_sql_type_transparent(
     let (_, ty_generics, _) = generics.split_for_impl();
 
     // add db type for clause
-    let mut generics = generics.clone();
-    generics.params.insert(0, parse_quote!(DB: sqlx::Database));
-    generics
+    let mut db_generics = generics.clone();
+    db_generics
+        .params
+        .insert(0, parse_quote!(DB: sqlx::Database));
+    db_generics
         .make_where_clause()
         .predicates
         .push(parse_quote!(#ty: sqlx::types::Type<DB>));
 
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (impl_generics, _, where_clause) = db_generics.split_for_impl();
 
-    Ok(quote!(
+    let mut tts = quote!(
         impl #impl_generics sqlx::types::Type< DB > for #ident #ty_generics #where_clause {
             fn type_info() -> DB::TypeInfo {
                 <#ty as sqlx::Type<DB>>::type_info()
             }
         }
-    ))
+    );
+
+    if cfg!(feature = "postgres") && !attributes.no_pg_array {
+        let mut generics = generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(parse_quote!(#ty: sqlx::postgres::types::PgHasArrayType));
+
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        tts.extend(quote!(
+            impl #impl_generics sqlx::postgres::types::PgHasArrayType for #ident #ty_generics #where_clause {
+                fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                    <#ty as sqlx::postgres::types::PgHasArrayType>::array_type_info()
+                }
+            }
+        ));
+    }
+
+    Ok(tts)
 }
 
 fn expand_derive_has_sql_type_weak_enum(
@@ -85,7 +107,7 @@ fn expand_derive_has_sql_type_weak_enum(
     let repr = attr.repr.unwrap();
     let ident = &input.ident;
 
-    Ok(quote!(
+    let mut tts = quote!(
         impl<DB: sqlx::Database> sqlx::Type<DB> for #ident
         where
             #repr: sqlx::Type<DB>,
@@ -94,7 +116,22 @@ fn expand_derive_has_sql_type_weak_enum(
                 <#repr as sqlx::Type<DB>>::type_info()
             }
         }
-    ))
+    );
+
+    if cfg!(feature = "postgres") && !attr.no_pg_array {
+        tts.extend(quote!(
+            impl sqlx::postgres::types::PgHasArrayType for #ident
+            where
+                #repr: sqlx::postgres::types::PgHasArrayType,
+            {
+                fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                    <#repr as sqlx::postgres::types::PgHasArrayType>::array_type_info()
+                }
+            }
+        ));
+    }
+
+    Ok(tts)
 }
 
 fn expand_derive_has_sql_type_strong_enum(
@@ -118,6 +155,7 @@ fn expand_derive_has_sql_type_strong_enum(
 
     if cfg!(feature = "postgres") {
         let ty_name = attributes.rename.unwrap_or_else(|| ident.to_string());
+        let array_ty_name = pg_array_type_name(&ty_name);
 
         tts.extend(quote!(
             impl sqlx::Type< sqlx::Postgres > for #ident {
@@ -125,6 +163,12 @@ fn expand_derive_has_sql_type_strong_enum(
                     sqlx::postgres::PgTypeInfo::with_name(#ty_name)
                 }
             }
+
+            impl sqlx::postgres::types::PgHasArrayType for #ident {
+                fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                    sqlx::postgres::PgTypeInfo::with_name(#array_ty_name)
+                }
+            }
         ));
     }
 
@@ -152,6 +196,7 @@ fn expand_derive_has_sql_type_struct(
 
     if cfg!(feature = "postgres") {
         let ty_name = attributes.rename.unwrap_or_else(|| ident.to_string());
+        let array_ty_name = pg_array_type_name(&ty_name);
 
         tts.extend(quote!(
             impl sqlx::types::Type< sqlx::Postgres > for #ident {
@@ -159,8 +204,20 @@ fn expand_derive_has_sql_type_struct(
                     sqlx::postgres::PgTypeInfo::with_name(#ty_name)
                 }
             }
+
+            impl sqlx::postgres::types::PgHasArrayType for #ident {
+                fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+                    sqlx::postgres::PgTypeInfo::with_name(#array_ty_name)
+                }
+            }
         ));
     }
 
     Ok(tts)
 }
+
+// the OID of the array type of a user-defined type is looked up through its element type
+// (`typarray`) so the array is named after the element; see `PgTypeInfo::with_name`
+fn pg_array_type_name(ty_name: &str) -> String {
+    format!("{}[]", ty_name)
+}
@@ -52,11 +52,20 @@ fn macro_result(tokens: proc_macro2::TokenStream) -> TokenStream {
     .into()
 }
 
+// emitted as-is for a macro in item position
+fn item_result(tokens: proc_macro2::TokenStream) -> TokenStream {
+    tokens.into()
+}
+
 macro_rules! async_macro (
-    ($db:ident, $input:ident: $ty:ty => $expr:expr) => {{
+    ($db:ident, $input:ident: $ty:ty => $expr:expr) => {
+        async_macro!($db, $input: $ty => $expr, macro_result)
+    };
+
+    ($db:ident, $input:ident: $ty:ty => $expr:expr, $result:ident) => {{
         let $input = match syn::parse::<$ty>($input) {
             Ok(input) => input,
-            Err(e) => return macro_result(e.to_compile_error()),
+            Err(e) => return $result(e.to_compile_error()),
         };
 
         let res: Result<proc_macro2::TokenStream> = block_on(async {
@@ -125,10 +134,10 @@ macro_rules! async_macro (
             Ok(ts) => ts.into(),
             Err(e) => {
                 if let Some(parse_err) = e.downcast_ref::<syn::Error>() {
-                    macro_result(parse_err.to_compile_error())
+                    $result(parse_err.to_compile_error())
                 } else {
                     let msg = e.to_string();
-                    macro_result(quote!(compile_error!(#msg)))
+                    $result(quote!(compile_error!(#msg);))
                 }
             }
         }
@@ -189,6 +198,20 @@ pub fn query_file_as_unchecked(input: TokenStream) -> TokenStream {
     async_macro!(db, input: QueryAsMacroInput => expand_query_file_as(input, db, false))
 }
 
+#[proc_macro]
+#[allow(unused_variables)]
+pub fn query_scalar(input: TokenStream) -> TokenStream {
+    #[allow(unused_variables)]
+    async_macro!(db, input: QueryMacroInput => expand_query_scalar(input, db))
+}
+
+#[proc_macro]
+#[allow(unused_variables)]
+pub fn query_file_mod(input: TokenStream) -> TokenStream {
+    #[allow(unused_variables)]
+    async_macro!(db, input: QueryFileModInput => expand_query_file_mod(input, db), item_result)
+}
+
 #[proc_macro_derive(Encode, attributes(sqlx))]
 pub fn derive_encode(tokenstream: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(tokenstream as syn::DeriveInput);
@@ -1,18 +1,22 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use syn::spanned::Spanned;
-use syn::Expr;
+use syn::{Expr, ExprCast, Type};
 
-use quote::{quote, quote_spanned, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use sqlx::describe::Describe;
 
 use crate::database::{DatabaseExt, ParamChecking};
+use crate::query_macros::lists::{self, Expansion};
 use crate::query_macros::QueryMacroInput;
 
 /// Returns a tokenstream which typechecks the arguments passed to the macro
 /// and binds them to `DB::Arguments` with the ident `query_args`.
+///
+/// If the query is built when it is bound, it is built as a `String` with the ident `query_sql`.
 pub fn quote_args<DB: DatabaseExt>(
     input: &QueryMacroInput,
     describe: &Describe<DB>,
+    expansion: &Expansion,
     checked: bool,
 ) -> crate::Result<TokenStream> {
     let db_path = DB::db_path();
@@ -35,7 +39,14 @@ pub fn quote_args<DB: DatabaseExt>(
                 // TODO: We could remove the ParamChecking flag and just filter to only test params that are non-null
                 let param_ty = param_ty.as_ref().unwrap();
 
-                let param_ty = get_type_override(expr)
+                // `expr as _` opts out of checking the type of this argument
+                if let Expr::Cast(ExprCast { ty, .. }) = expr {
+                    if let Type::Infer(_) = **ty {
+                        return Ok(TokenStream::new());
+                    }
+                }
+
+                let param_ty = get_type_override(name, expr)
                     .or_else(|| {
                         Some(
                             DB::param_type_for_id(&param_ty)?
@@ -56,13 +67,15 @@ pub fn quote_args<DB: DatabaseExt>(
                         }
                     })?;
 
+                let arg_value = quote_arg_value(name, expr);
+
                 Ok(quote_spanned!(expr.span() =>
                     // this shouldn't actually run
                     if false {
                         use sqlx::ty_match::{WrapSameExt as _, MatchBorrowExt as _};
 
                         // evaluate the expression only once in case it contains moves
-                        let _expr = sqlx::ty_match::dupe_value(&$#name);
+                        let _expr = sqlx::ty_match::dupe_value(&#arg_value);
 
                         // if `_expr` is `Option<T>`, get `Option<$ty>`, otherwise `$ty`
                         let ty_check = sqlx::ty_match::WrapSame::<#param_ty, _>::new(&_expr).wrap_same();
@@ -82,26 +95,198 @@ pub fn quote_args<DB: DatabaseExt>(
         TokenStream::new()
     };
 
-    let args_count = input.arg_names.len();
+    // the argument bound to each bind parameter, in order
+    let param_arg_name = (0..input.params())
+        .map(|param| &input.arg_names[input.param_arg(param)])
+        .collect::<Vec<_>>();
+
+    let params_count = param_arg_name.len();
+    let arg_value = input
+        .arg_names
+        .iter()
+        .zip(&input.arg_exprs)
+        .map(|(name, expr)| quote_arg_value(name, expr));
+
+    match expansion {
+        #[cfg(any(feature = "mysql", feature = "sqlite"))]
+        Expansion::Built { pieces, empty_list } => {
+            let build = lists::quote_build(input, pieces, empty_list);
+
+            return Ok(quote! {
+                #args_check
+
+                #(let #arg_name = &#arg_value;)*
+                let mut query_args = <#db_path as sqlx::Database>::Arguments::default();
+
+                #build
+            });
+        }
+
+        _ => {}
+    }
 
     Ok(quote! {
         #args_check
 
         // bind as a local expression, by-ref
-        #(let #arg_name = &$#arg_name;)*
+        #(let #arg_name = &#arg_value;)*
         let mut query_args = <#db_path as sqlx::Database>::Arguments::default();
         query_args.reserve(
-            #args_count,
-            0 #(+ sqlx::encode::Encode::<#db_path>::size_hint(#arg_name))*
+            #params_count,
+            0 #(+ sqlx::encode::Encode::<#db_path>::size_hint(#param_arg_name))*
         );
-        #(query_args.add(#arg_name);)*
+        #(query_args.add(#param_arg_name);)*
     })
 }
 
-fn get_type_override(expr: &Expr) -> Option<TokenStream> {
+/// Returns the `macro_result!()` macro which is passed the arguments of the macro and expands
+/// to `body`.
+///
+/// An argument with a type override (`expr as Type`) cannot be matched as an `expr` fragment
+/// as that would perform the cast, nor as `$($tt)* as $ty:ty` as that is ambiguous to
+/// `macro_rules!`. The tokens of its expression are moved one at a time into brackets until
+/// the `as Type` of the cast follows, after which the arguments are matched by the arm that
+/// expands to `body`.
+pub fn quote_macro_result(input: &QueryMacroInput, body: TokenStream) -> TokenStream {
+    // named arguments are matched with their name, `name = ..`
+    let arg_param = |i: usize| {
+        input
+            .arg_params
+            .get(i)
+            .map(|name| quote!(#name =))
+            .unwrap_or_default()
+    };
+
+    let arg_pattern = input
+        .arg_names
+        .iter()
+        .zip(&input.arg_exprs)
+        .enumerate()
+        .map(|(i, (name, expr))| {
+            let param = arg_param(i);
+
+            match expr {
+                _ if lists::list_arg(expr).is_some() => quote!(#param .. $#name:expr),
+
+                Expr::Cast(_) => {
+                    let expr = cast_expr(name);
+                    let ty = cast_ty(name);
+
+                    quote!(#param [$($#expr:tt)*] as $#ty:ty)
+                }
+
+                _ => quote!(#param $#name:expr),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if !input
+        .arg_exprs
+        .iter()
+        .any(|expr| matches!(expr, Expr::Cast(_)))
+    {
+        return quote! {
+            macro_rules! macro_result {
+                (#(#arg_pattern),* $(,)?) => {{
+                    #body
+                }}
+            }
+        };
+    }
+
+    // the arms that move each argument into `[$($done:tt)*]`, from `@argN` to `@argN+1`
+    let split_arms = input.arg_exprs.iter().enumerate().map(|(i, expr)| {
+        let state = format_ident!("arg{}", i);
+        let param = arg_param(i);
+
+        let next = if i + 1 < input.arg_exprs.len() {
+            let next = format_ident!("arg{}", i + 1);
+
+            quote!(@#next)
+        } else {
+            quote!(@bind)
+        };
+
+        match expr {
+            Expr::Cast(_) => {
+                let cast = format_ident!("cast{}", i);
+
+                quote! {
+                    (@#state [$($done:tt)*] #param $($rest:tt)*) => {
+                        macro_result!(@#cast [$($done)*] [] $($rest)*)
+                    };
+                    (@#cast [$($done:tt)*] [$($expr:tt)*] as $ty:ty $(, $($rest:tt)*)?) => {
+                        macro_result!(#next [$($done)* #param [$($expr)*] as $ty,] $($($rest)*)?)
+                    };
+                    (@#cast [$($done:tt)*] [$($expr:tt)*] $token:tt $($rest:tt)*) => {
+                        macro_result!(@#cast [$($done)*] [$($expr)* $token] $($rest)*)
+                    };
+                }
+            }
+
+            _ if lists::list_arg(expr).is_some() => quote! {
+                (@#state [$($done:tt)*] #param .. $expr:expr $(, $($rest:tt)*)?) => {
+                    macro_result!(#next [$($done)* #param .. $expr,] $($($rest)*)?)
+                };
+            },
+
+            _ => quote! {
+                (@#state [$($done:tt)*] #param $expr:expr $(, $($rest:tt)*)?) => {
+                    macro_result!(#next [$($done)* #param $expr,] $($($rest)*)?)
+                };
+            },
+        }
+    });
+
+    quote! {
+        macro_rules! macro_result {
+            (@bind [#(#arg_pattern,)*]) => {{
+                #body
+            }};
+
+            #(#split_arms)*
+
+            ($($args:tt)*) => {
+                macro_result!(@arg0 [] $($args)*)
+            };
+        }
+    }
+}
+
+// The expression an argument is bound as, without its type override
+fn quote_arg_value(name: &Ident, expr: &Expr) -> TokenStream {
+    match expr {
+        // a list is bound as a slice of its elements
+        _ if lists::list_arg(expr).is_some() => quote!((&($#name)[..])),
+
+        Expr::Cast(_) => {
+            let expr = cast_expr(name);
+
+            quote!(($($#expr)*))
+        }
+
+        _ => quote!($#name),
+    }
+}
+
+fn get_type_override(name: &Ident, expr: &Expr) -> Option<TokenStream> {
     match expr {
-        Expr::Cast(cast) => Some(cast.ty.to_token_stream()),
+        Expr::Cast(_) => {
+            let ty = cast_ty(name);
+
+            Some(quote!($#ty))
+        }
+
         Expr::Type(ascription) => Some(ascription.ty.to_token_stream()),
         _ => None,
     }
 }
+
+// The token trees of the expression of a cast, without the cast
+fn cast_expr(name: &Ident) -> Ident {
+    format_ident!("{}_expr", name)
+}
+
+fn cast_ty(name: &Ident) -> Ident {
+    format_ident!("{}_ty", name)
+}
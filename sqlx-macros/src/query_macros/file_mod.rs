@@ -0,0 +1,410 @@
+//! `query_file_mod!()` which expands a SQL file of named queries to a module with a function
+//! for each query.
+//!
+//! ```sql
+//! -- name: find_user -> super::User
+//! -- Find a user by their id.
+//! -- param: id - the id of the user
+//! SELECT id, name FROM users WHERE id = :id;
+//! ```
+//!
+//! A query starts with a `-- name:` line, optionally followed by `->` and the type the rows are
+//! mapped to. The comment lines directly after it are the doc comment of the function, except
+//! for `-- param: name[: Type][ - description]` lines which declare the type of a parameter
+//! and document it. The parameters of the query are its named parameters (`:name`).
+
+use std::fmt::Display;
+
+use heck::CamelCase;
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Path, Token, Type, Visibility};
+
+use sqlx::connection::Connection;
+use sqlx::database::Database;
+use sqlx::named;
+
+use super::input::read_file_src;
+use super::{args, lists, output, QueryMacroInput};
+use crate::database::DatabaseExt;
+
+/// Input of `query_file_mod!()`: `[pub] mod name = "path/to/queries.sql"`
+pub struct QueryFileModInput {
+    vis: Visibility,
+    name: Ident,
+    path: LitStr,
+}
+
+impl Parse for QueryFileModInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        input.parse::<Token![mod]>()?;
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let path = input.parse()?;
+
+        Ok(QueryFileModInput { vis, name, path })
+    }
+}
+
+// A query of the file
+struct NamedQuery {
+    name: String,
+    out_ty: Option<String>,
+    doc: Vec<String>,
+    params: Vec<ParamDecl>,
+    sql: String,
+}
+
+// A `-- param:` line
+struct ParamDecl {
+    name: String,
+    ty: Option<String>,
+    doc: Option<String>,
+}
+
+pub async fn expand_query_file_mod<C: Connection>(
+    input: QueryFileModInput,
+    mut conn: C,
+) -> crate::Result<TokenStream>
+where
+    C::Database: DatabaseExt + Sized,
+    <C::Database as Database>::TypeInfo: Display,
+{
+    let span = input.path.span();
+    let source = read_file_src(&input.path.value(), span).await?;
+    let queries = parse_queries(&source).map_err(|e| syn::Error::new(span, e))?;
+
+    let mut functions = Vec::with_capacity(queries.len());
+
+    for query in &queries {
+        functions.push(
+            expand_query_fn(query, span, &mut conn)
+                .await
+                .map_err(|e| format!("query `{}`: {}", query.name, e))?,
+        );
+    }
+
+    let vis = &input.vis;
+    let name = &input.name;
+
+    Ok(quote! {
+        #vis mod #name {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #(#functions)*
+        }
+    })
+}
+
+async fn expand_query_fn<C: Connection>(
+    query: &NamedQuery,
+    span: Span,
+    conn: &mut C,
+) -> crate::Result<TokenStream>
+where
+    C::Database: DatabaseExt + Sized,
+    <C::Database as Database>::TypeInfo: Display,
+{
+    let db_path = <C::Database as DatabaseExt>::db_path();
+    let fn_name = parse_ident(&query.name, span)?;
+
+    // the declared parameters first, then the others in the order they appear in the query
+    let mut params: Vec<&str> = query.params.iter().map(|param| &*param.name).collect();
+
+    for param in named::parse(&query.sql) {
+        // an `@name` that is not declared is a user variable (MySQL)
+        if <C::Database as DatabaseExt>::PARAM_STYLE.user_variables()
+            && query.sql[param.range.start..].starts_with('@')
+        {
+            continue;
+        }
+
+        if !params.contains(&param.name) {
+            params.push(param.name);
+        }
+    }
+
+    let param_names = params
+        .iter()
+        .map(|name| parse_ident(name, span))
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let input = QueryMacroInput::from_params(query.sql.clone(), span, param_names.clone())
+        .expand_named::<C::Database>()?;
+
+    let (describe, expansion) = lists::describe_expand(&input, conn).await?;
+
+    // `$N` is numbered for the order of the arguments; `?` parameters have no type
+    let param_types = params
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let declared = query
+                .params
+                .iter()
+                .find(|param| param.name == *name)
+                .and_then(|param| param.ty.as_ref());
+
+            if let Some(ty) = declared {
+                return syn::parse_str::<Type>(ty).map_err(|e| syn::Error::new(span, e).into());
+            }
+
+            let inferred = describe
+                .param_types
+                .get(i)
+                .and_then(Option::as_ref)
+                .filter(|_| input.param_args.is_none())
+                .and_then(|ty| <C::Database as DatabaseExt>::param_type_for_id(ty));
+
+            match inferred {
+                Some(ty) => borrowed_param_type(ty, span).map_err(Into::into),
+
+                None => Err(format!(
+                    "the type of parameter `{}` must be declared with `-- param: {}: Type`",
+                    name, name
+                )
+                .into()),
+            }
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    let args = args::quote_args(&input, &describe, &expansion, true)?;
+    let query_tokens = lists::quote_query::<C::Database>(&input, &expansion);
+
+    let mut doc = query.doc.join("\n");
+
+    if query.params.iter().any(|param| param.doc.is_some()) {
+        doc.push_str("\n\n# Parameters\n");
+
+        for param in &query.params {
+            if let Some(param_doc) = &param.doc {
+                doc.push_str(&format!("* `{}`: {}\n", param.name, param_doc));
+            }
+        }
+    }
+
+    doc.push_str(&format!("\n\n```sql\n{}\n```", query.sql));
+
+    let (record, ret, output) = if describe.result_columns.is_empty() {
+        (
+            TokenStream::new(),
+            quote!(sqlx::query::Query<'static, #db_path>),
+            query_tokens,
+        )
+    } else {
+        let columns = output::columns_to_rust(&describe)?;
+
+        let (record, out_ty): (TokenStream, Path) = match &query.out_ty {
+            Some(out_ty) => (
+                TokenStream::new(),
+                syn::parse_str(out_ty).map_err(|e| syn::Error::new(span, e))?,
+            ),
+
+            None => {
+                let record_name = format_ident!("{}", query.name.to_camel_case());
+                let record_doc = format!("A row of [`{}`].", query.name);
+                let record_fields = columns.iter().map(|column| {
+                    let ident = &column.ident;
+                    let ty = &column.type_;
+
+                    quote!(pub #ident: #ty)
+                });
+
+                (
+                    quote! {
+                        #[doc = #record_doc]
+                        #[derive(Debug)]
+                        pub struct #record_name {
+                            #(#record_fields,)*
+                        }
+                    },
+                    record_name.into(),
+                )
+            }
+        };
+
+        (
+            record,
+            quote!(
+                sqlx::query::Map<
+                    'static,
+                    #db_path,
+                    impl sqlx::query::TryMapRow<#db_path, Output = #out_ty>,
+                >
+            ),
+            output::quote_query_as::<C::Database>(&query_tokens, &out_ty, &columns, true),
+        )
+    };
+
+    let macro_result = args::quote_macro_result(
+        &input,
+        quote! {
+            use sqlx::arguments::Arguments as _;
+
+            #args
+
+            #output
+        },
+    );
+
+    Ok(quote! {
+        #record
+
+        #[doc = #doc]
+        pub fn #fn_name(#(#param_names: #param_types),*) -> #ret {
+            #macro_result
+
+            macro_result!(#(#param_names = #param_names),*)
+        }
+    })
+}
+
+// The parameter type of a function for the type of a bind parameter; owned strings and
+// vectors are taken by reference
+fn borrowed_param_type(ty: &str, span: Span) -> syn::Result<Type> {
+    let borrowed = match ty {
+        "String" => "&str".to_string(),
+        _ if ty.starts_with("Vec<") && ty.ends_with('>') => {
+            format!("&[{}]", &ty["Vec<".len()..ty.len() - 1])
+        }
+        _ => ty.to_string(),
+    };
+
+    syn::parse_str(&borrowed).map_err(|e| {
+        syn::Error::new(
+            span,
+            format!("failed to parse the parameter type `{}`: {}", ty, e),
+        )
+    })
+}
+
+fn parse_ident(name: &str, span: Span) -> syn::Result<Ident> {
+    syn::parse_str::<Ident>(name)
+        .map(|ident| Ident::new(&ident.to_string(), span))
+        .map_err(|_| syn::Error::new(span, format!("`{}` is not a valid identifier", name)))
+}
+
+// Splits a file into its queries
+fn parse_queries(source: &str) -> Result<Vec<NamedQuery>, String> {
+    let mut queries: Vec<NamedQuery> = Vec::new();
+
+    // if the comment lines after `-- name:` are still being read
+    let mut in_header = false;
+
+    for line in source.lines() {
+        let comment = strip_prefix(line.trim(), "--").map(str::trim);
+
+        if let Some(name) = comment.and_then(|comment| strip_prefix(comment, "name:")) {
+            let (name, out_ty) = match name.find("->") {
+                Some(arrow) => (&name[..arrow], Some(name[arrow + 2..].trim().to_string())),
+                None => (name, None),
+            };
+
+            let name = name.trim().to_string();
+
+            if queries.iter().any(|query| query.name == name) {
+                return Err(format!("query `{}` is defined more than once", name));
+            }
+
+            queries.push(NamedQuery {
+                name,
+                out_ty,
+                doc: Vec::new(),
+                params: Vec::new(),
+                sql: String::new(),
+            });
+
+            in_header = true;
+            continue;
+        }
+
+        let query = match queries.last_mut() {
+            Some(query) => query,
+
+            None if line.trim().is_empty() || comment.is_some() => continue,
+            None => return Err("expected `-- name:` before the first query".into()),
+        };
+
+        match comment {
+            Some(comment) if in_header => {
+                if let Some(param) = strip_prefix(comment, "param:") {
+                    query.params.push(parse_param_decl(param)?);
+                } else {
+                    query.doc.push(comment.to_string());
+                }
+            }
+
+            Some(comment) if strip_prefix(comment, "param:").is_some() => {
+                return Err(format!(
+                    "`-- param:` of query `{}` must be in its header, right after `-- name:` \
+                     and before its SQL",
+                    query.name
+                ));
+            }
+
+            _ => {
+                in_header = false;
+
+                query.sql.push_str(line);
+                query.sql.push('\n');
+            }
+        }
+    }
+
+    for query in &mut queries {
+        query.sql = query
+            .sql
+            .trim()
+            .trim_end_matches(';')
+            .trim_end()
+            .to_string();
+
+        if query.sql.is_empty() {
+            return Err(format!("query `{}` is empty", query.name));
+        }
+    }
+
+    Ok(queries)
+}
+
+// `name[: Type][ - description]`
+fn parse_param_decl(decl: &str) -> Result<ParamDecl, String> {
+    let (decl, doc) = match decl.find(" - ") {
+        Some(dash) => (&decl[..dash], Some(decl[dash + 3..].trim().to_string())),
+        None => (decl, None),
+    };
+
+    // the name may be written with its prefix, `:name` or `@name`
+    let decl = decl.trim().trim_start_matches(|c| c == ':' || c == '@');
+
+    let (name, ty) = match decl.find(':') {
+        Some(colon) => (&decl[..colon], Some(decl[colon + 1..].trim().to_string())),
+        None => (decl, None),
+    };
+
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err(format!(
+            "expected the name of a parameter in `-- param:{}`",
+            decl
+        ));
+    }
+
+    Ok(ParamDecl {
+        name: name.to_string(),
+        ty,
+        doc,
+    })
+}
+
+fn strip_prefix<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    if s.starts_with(prefix) {
+        Some(&s[prefix.len()..])
+    } else {
+        None
+    }
+}
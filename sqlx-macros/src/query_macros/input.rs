@@ -2,16 +2,19 @@ use std::env;
 
 use proc_macro2::{Ident, Span};
 use quote::{format_ident, ToTokens};
+use syn::ext::IdentExt;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::token::Group;
-use syn::{Expr, ExprLit, ExprPath, Lit};
+use syn::{parse_quote, Expr, ExprAssign, ExprLit, ExprPath, Lit};
 use syn::{ExprGroup, Token};
 
 use sqlx::connection::Connection;
 use sqlx::describe::Describe;
+use sqlx::named::{self, Placeholder};
 
+use crate::database::{DatabaseExt, ParamStyle};
 use crate::runtime::fs;
 
 /// Macro input shared by `query!()` and `query_file!()`
@@ -21,6 +24,11 @@ pub struct QueryMacroInput {
     // `arg0 .. argN` for N arguments
     pub(super) arg_names: Vec<Ident>,
     pub(super) arg_exprs: Vec<Expr>,
+    // the names of the arguments if they are named (`name = expr`)
+    pub(super) arg_params: Vec<Ident>,
+    // the index of the argument bound to each bind parameter of the query, if that is not the
+    // argument at the same index (named arguments for a database with `?` parameters)
+    pub(super) param_args: Option<Vec<usize>>,
 }
 
 impl QueryMacroInput {
@@ -57,7 +65,35 @@ impl QueryMacroInput {
             None => return Err(input.error("expected SQL string literal")),
         };
 
-        let arg_exprs: Vec<_> = args.collect();
+        let mut arg_exprs = Vec::new();
+        let mut arg_params = Vec::new();
+
+        for expr in args {
+            match expr {
+                Expr::Assign(ExprAssign { left, right, .. }) => match *left {
+                    Expr::Path(ExprPath {
+                        qself: None, path, ..
+                    }) if path.get_ident().is_some() => {
+                        arg_params.push(path.get_ident().unwrap().clone());
+                        arg_exprs.push(*right);
+                    }
+
+                    left => {
+                        return Err(syn::Error::new(
+                            left.span(),
+                            "expected the name of a named parameter",
+                        ))
+                    }
+                },
+
+                expr => arg_exprs.push(expr),
+            }
+        }
+
+        if !arg_params.is_empty() && arg_params.len() != arg_exprs.len() {
+            return Err(input.error("named and positional arguments cannot be mixed"));
+        }
+
         let arg_names = (0..arg_exprs.len())
             .map(|i| format_ident!("arg{}", i))
             .collect();
@@ -67,9 +103,79 @@ impl QueryMacroInput {
             source_span,
             arg_exprs,
             arg_names,
+            arg_params,
+            param_args: None,
         })
     }
 
+    /// Input for a query whose arguments are its named parameters, passed by their names.
+    pub(super) fn from_params(source: String, source_span: Span, params: Vec<Ident>) -> Self {
+        let arg_exprs = params.iter().map(|param| parse_quote!(#param)).collect();
+        let arg_names = (0..params.len())
+            .map(|i| format_ident!("arg{}", i))
+            .collect();
+
+        Self {
+            source,
+            source_span,
+            arg_exprs,
+            arg_names,
+            arg_params: params,
+            param_args: None,
+        }
+    }
+
+    /// Rewrite the named parameters (`:name` or `@name`) of the query to the bind parameters
+    /// of the database, if the arguments are named.
+    pub fn expand_named<DB: DatabaseExt>(self) -> syn::Result<Self> {
+        if self.arg_params.is_empty() {
+            return Ok(self);
+        }
+
+        let names: Vec<String> = self
+            .arg_params
+            .iter()
+            .map(|name| name.unraw().to_string())
+            .collect();
+
+        let placeholder = match DB::PARAM_STYLE {
+            #[cfg(feature = "postgres")]
+            ParamStyle::Dollar => Placeholder::Numbered('$'),
+
+            #[cfg(any(feature = "mysql", feature = "sqlite"))]
+            ParamStyle::Question { user_variables, .. } => {
+                Placeholder::Positional { user_variables }
+            }
+        };
+
+        let (source, order) = named::rewrite(&self.source, &names, placeholder)
+            .map_err(|e| syn::Error::new(self.source_span, e))?;
+
+        // `$N` is numbered for the order of the arguments so only `?` needs a mapping
+        let param_args = match placeholder {
+            Placeholder::Numbered(_) => None,
+            Placeholder::Positional { .. } => Some(order),
+        };
+
+        Ok(Self {
+            source,
+            param_args,
+            ..self
+        })
+    }
+
+    /// The number of bind parameters in the query, once the arguments are bound.
+    pub(super) fn params(&self) -> usize {
+        self.param_args
+            .as_ref()
+            .map_or(self.arg_names.len(), Vec::len)
+    }
+
+    /// The index of the argument bound to the bind parameter at `param`.
+    pub(super) fn param_arg(&self, param: usize) -> usize {
+        self.param_args.as_ref().map_or(param, |args| args[param])
+    }
+
     pub async fn expand_file_src(self) -> syn::Result<Self> {
         let source = read_file_src(&self.source, self.source_span).await?;
 
@@ -87,13 +193,13 @@ impl QueryMacroInput {
             .await
             .map_err(|e| syn::Error::new(self.source_span, e))?;
 
-        if self.arg_names.len() != describe.param_types.len() {
+        if self.params() != describe.param_types.len() {
             return Err(syn::Error::new(
                 Span::call_site(),
                 format!(
                     "expected {} parameters, got {}",
                     describe.param_types.len(),
-                    self.arg_names.len()
+                    self.params()
                 ),
             )
             .into());
@@ -124,6 +230,13 @@ impl QueryAsMacroInput {
             ..self
         })
     }
+
+    pub fn expand_named<DB: DatabaseExt>(self) -> syn::Result<Self> {
+        Ok(Self {
+            query_input: self.query_input.expand_named::<DB>()?,
+            ..self
+        })
+    }
 }
 
 impl Parse for QueryAsMacroInput {
@@ -165,7 +278,7 @@ impl Parse for QueryAsMacroInput {
     }
 }
 
-async fn read_file_src(source: &str, source_span: Span) -> syn::Result<String> {
+pub(super) async fn read_file_src(source: &str, source_span: Span) -> syn::Result<String> {
     use std::path::Path;
 
     let path = Path::new(source);
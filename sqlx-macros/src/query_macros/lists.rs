@@ -0,0 +1,695 @@
+//! List arguments (`..expr`) of `query!()` which bind every element of a slice.
+//!
+//! A list is written in the query as a single bind parameter which is either the only element
+//! of an `IN (..)` list or in the row of a `VALUES (..)` list. For Postgres, the query is
+//! rewritten at compile time to bind the list as an array: `IN ($1)` becomes `= ANY($1)` and
+//! `VALUES ($1, $2)` becomes a `SELECT` from `UNNEST($1, $2)`. For MySQL and SQLite, the query
+//! string is built when the query is bound with a `?` for each element or for each row.
+
+use std::fmt::Display;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{Expr, ExprRange, RangeLimits};
+
+use sqlx::connection::Connection;
+use sqlx::database::Database;
+use sqlx::describe::Describe;
+
+use super::QueryMacroInput;
+use crate::database::{DatabaseExt, ParamStyle};
+
+/// How the query string is passed to `sqlx::query()`
+pub enum Expansion {
+    // there are no list arguments and the query is used as-is
+    None,
+
+    // the query is rewritten at compile time; `rows` are the list arguments of each
+    // `VALUES` row
+    #[cfg(feature = "postgres")]
+    Rewritten {
+        sql: String,
+        rows: Vec<Vec<usize>>,
+    },
+
+    // the query is built when it is bound
+    #[cfg(any(feature = "mysql", feature = "sqlite"))]
+    Built {
+        pieces: Vec<Piece>,
+        empty_list: &'static str,
+    },
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+pub enum Piece {
+    Text(String),
+
+    // a bind parameter of a single value
+    Param(usize),
+
+    // a list of a bind parameter for each element of the argument
+    List(usize),
+
+    // a `VALUES (..)` row, repeated for each element of the list arguments in it
+    Rows {
+        pieces: Vec<Piece>,
+        lists: Vec<usize>,
+    },
+
+    // a bind parameter of the element of a list argument for the current row
+    RowValue(usize),
+}
+
+/// Returns the expression of a list argument (`..expr`).
+pub fn list_arg(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Range(ExprRange {
+            from: None,
+            limits: RangeLimits::HalfOpen(_),
+            to: Some(to),
+            ..
+        }) => Some(to),
+
+        _ => None,
+    }
+}
+
+/// Describes the query of the input and finds how it is expanded for its list arguments.
+pub async fn describe_expand<C: Connection>(
+    input: &QueryMacroInput,
+    conn: &mut C,
+) -> crate::Result<(Describe<C::Database>, Expansion)>
+where
+    C::Database: DatabaseExt + Sized,
+    <C::Database as Database>::TypeInfo: Display,
+{
+    let describe = input.describe_validate(conn).await?;
+
+    let mut list_args = Vec::new();
+
+    for (i, expr) in input.arg_exprs.iter().enumerate() {
+        if let Some(expr) = list_arg(expr) {
+            if let Expr::Cast(_) = expr {
+                return Err(syn::Error::new(
+                    expr.span(),
+                    "type overrides are not supported for list arguments",
+                )
+                .into());
+            }
+
+            list_args.push(i);
+        }
+    }
+
+    if list_args.is_empty() {
+        return Ok((describe, Expansion::None));
+    }
+
+    let sql = &input.source;
+    let mut params = scan(sql, &<C::Database as DatabaseExt>::PARAM_STYLE)
+        .map_err(|e| syn::Error::new(input.source_span, e))?;
+
+    // `?` parameters are bound in order unless the arguments are named; `$N` is numbered for
+    // the order of the arguments
+    for param in &mut params {
+        param.arg = input.param_arg(param.arg);
+    }
+
+    let lists = list_args
+        .iter()
+        .map(|&arg| find_list(sql, &params, arg))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| syn::Error::new(input.source_span, e))?;
+
+    match <C::Database as DatabaseExt>::PARAM_STYLE {
+        #[cfg(feature = "postgres")]
+        ParamStyle::Dollar => {
+            let sql = rewrite(sql, &params, &lists, &describe);
+
+            let describe = conn
+                .describe(&*sql)
+                .await
+                .map_err(|e| syn::Error::new(input.source_span, e))?;
+
+            Ok((
+                describe,
+                Expansion::Rewritten {
+                    sql,
+                    rows: row_lists(&lists),
+                },
+            ))
+        }
+
+        #[cfg(any(feature = "mysql", feature = "sqlite"))]
+        ParamStyle::Question { empty_list, .. } => Ok((
+            describe,
+            Expansion::Built {
+                pieces: build_pieces(sql, &params, &lists),
+                empty_list,
+            },
+        )),
+    }
+}
+
+/// Returns the expression of the query with the arguments bound to it.
+pub fn quote_query<DB: DatabaseExt>(input: &QueryMacroInput, expansion: &Expansion) -> TokenStream {
+    let db_path = DB::db_path();
+
+    match expansion {
+        Expansion::None => {
+            let sql = &input.source;
+
+            quote!(sqlx::query::<#db_path>(#sql).bind_all(query_args))
+        }
+
+        #[cfg(feature = "postgres")]
+        Expansion::Rewritten { sql, rows } if rows.iter().all(|lists| lists.len() < 2) => {
+            quote!(sqlx::query::<#db_path>(#sql).bind_all(query_args))
+        }
+
+        // `UNNEST()` pads the shorter arrays of a row with `NULL` so the lengths are checked
+        // before the query is executed
+        #[cfg(feature = "postgres")]
+        Expansion::Rewritten { sql, rows } => {
+            let checks = quote_row_checks(input, rows.iter().map(Vec::as_slice));
+
+            quote! {
+                sqlx::query::OwnedQuery::<#db_path>::new(String::from(#sql), query_args)
+                    #checks
+            }
+        }
+
+        #[cfg(any(feature = "mysql", feature = "sqlite"))]
+        Expansion::Built { pieces, .. } => {
+            let checks = quote_row_checks(
+                input,
+                pieces.iter().filter_map(|piece| match piece {
+                    Piece::Rows { lists, .. } => Some(lists.as_slice()),
+                    _ => None,
+                }),
+            );
+
+            quote! {
+                sqlx::query::OwnedQuery::<#db_path>::new(query_sql, query_args)
+                    #checks
+            }
+        }
+    }
+}
+
+// Checks that the list arguments of each `VALUES` row have the same length when the query
+// is bound, which returns an error when it is executed if they do not
+fn quote_row_checks<'a>(
+    input: &QueryMacroInput,
+    rows: impl Iterator<Item = &'a [usize]>,
+) -> TokenStream {
+    let names = &input.arg_names;
+
+    rows.filter(|lists| lists.len() > 1)
+        .map(|lists| {
+            let names = lists.iter().map(|&arg| &names[arg]);
+
+            quote!(.check_row_lengths(&[#(#names.len()),*]))
+        })
+        .collect()
+}
+
+/// Returns the statements that build `query_sql` and bind the arguments to `query_args`.
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+pub fn quote_build(input: &QueryMacroInput, pieces: &[Piece], empty_list: &str) -> TokenStream {
+    let statements = pieces
+        .iter()
+        .map(|piece| quote_piece(input, piece, empty_list));
+
+    let capacity = input.source.len();
+
+    quote! {
+        let mut query_sql = String::with_capacity(#capacity);
+
+        #(#statements)*
+    }
+}
+
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+fn quote_piece(input: &QueryMacroInput, piece: &Piece, empty_list: &str) -> TokenStream {
+    let names = &input.arg_names;
+
+    match piece {
+        Piece::Text(text) => quote!(query_sql.push_str(#text);),
+
+        Piece::Param(arg) => {
+            let name = &names[*arg];
+
+            quote! {
+                query_sql.push_str("?");
+                query_args.add(#name);
+            }
+        }
+
+        Piece::List(arg) => {
+            let name = &names[*arg];
+
+            quote! {
+                if #name.is_empty() {
+                    query_sql.push_str(#empty_list);
+                }
+
+                for (i, value) in #name.iter().enumerate() {
+                    if i > 0 {
+                        query_sql.push_str(", ");
+                    }
+
+                    query_sql.push_str("?");
+                    query_args.add(value);
+                }
+            }
+        }
+
+        Piece::Rows { pieces, lists } => {
+            let first = &names[lists[0]];
+            let rest = lists[1..].iter().map(|&arg| &names[arg]);
+            let statements = pieces
+                .iter()
+                .map(|piece| quote_piece(input, piece, empty_list));
+
+            // if the lengths differ, the query returns an error instead of being executed
+            // (see `quote_row_checks()`)
+            quote! {
+                let rows = #first.len()#(.min(#rest.len()))*;
+
+                for row in 0..rows {
+                    if row > 0 {
+                        query_sql.push_str(", ");
+                    }
+
+                    #(#statements)*
+                }
+            }
+        }
+
+        Piece::RowValue(arg) => {
+            let name = &names[*arg];
+
+            quote! {
+                query_sql.push_str("?");
+                query_args.add(&#name[row]);
+            }
+        }
+    }
+}
+
+// A bind parameter in the query
+struct Param {
+    start: usize,
+    end: usize,
+
+    // the index of the argument bound to this parameter
+    arg: usize,
+
+    // the start of the innermost parentheses around this parameter and their end (the index of
+    // the closing parenthesis)
+    group: Option<(usize, usize)>,
+}
+
+// the positions are only read to rewrite the query for Postgres
+#[cfg_attr(not(feature = "postgres"), allow(dead_code))]
+enum ListContext {
+    // `IN (?)` or `NOT IN (?)` where `start` is the start of `IN` or `NOT`
+    In { start: usize, not: bool },
+
+    // `VALUES (.., ?, ..)` where `start` is the start of `VALUES`
+    Values { start: usize },
+}
+
+struct List<'a> {
+    param: &'a Param,
+    context: ListContext,
+}
+
+// Finds the bind parameters in the query, skipping over quoted strings and identifiers
+// and comments
+fn scan(sql: &str, style: &ParamStyle) -> Result<Vec<Param>, String> {
+    let bytes = sql.as_bytes();
+
+    let mut params: Vec<Param> = Vec::new();
+    let mut groups: Vec<(usize, Vec<usize>)> = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ b'\'' | quote @ b'"' | quote @ b'`' => {
+                i = find(sql, i + 1, quote as char)? + 1;
+            }
+
+            b'-' if sql[i..].starts_with("--") => {
+                i = sql[i..].find('\n').map_or(sql.len(), |end| i + end + 1);
+            }
+
+            b'/' if sql[i..].starts_with("/*") => {
+                i = sql[i + 2..]
+                    .find("*/")
+                    .map(|end| i + 2 + end + 2)
+                    .ok_or("unterminated comment in query")?;
+            }
+
+            b'(' => {
+                groups.push((i, Vec::new()));
+                i += 1;
+            }
+
+            b')' => {
+                if let Some((start, members)) = groups.pop() {
+                    for param in members {
+                        params[param].group = Some((start, i));
+                    }
+                }
+
+                i += 1;
+            }
+
+            #[cfg(any(feature = "mysql", feature = "sqlite"))]
+            b'?' if matches!(style, ParamStyle::Question { .. }) => {
+                let end = i + 1 + digits(&sql[i + 1..]);
+
+                if end > i + 1 {
+                    return Err(
+                        "numbered parameters (`?NNN`) cannot be used with list arguments"
+                            .to_string(),
+                    );
+                }
+
+                let arg = params.len();
+
+                push_param(&mut params, &mut groups, i, end, arg);
+                i = end;
+            }
+
+            #[cfg(feature = "postgres")]
+            b'$' if matches!(style, ParamStyle::Dollar) => {
+                let end = i + 1 + digits(&sql[i + 1..]);
+
+                if end > i + 1 {
+                    let arg = sql[i + 1..end].parse::<usize>().unwrap_or(0);
+
+                    if arg == 0 {
+                        return Err(format!("invalid parameter {}", &sql[i..end]));
+                    }
+
+                    push_param(&mut params, &mut groups, i, end, arg - 1);
+                    i = end;
+                } else {
+                    // a dollar-quoted string, `$tag$ .. $tag$`
+                    let tag_end = find(sql, i + 1, '$')?;
+                    let tag = &sql[i..=tag_end];
+
+                    i = sql[tag_end + 1..]
+                        .find(tag)
+                        .map(|end| tag_end + 1 + end + tag.len())
+                        .ok_or("unterminated dollar-quoted string in query")?;
+                }
+            }
+
+            _ => i += 1,
+        }
+    }
+
+    Ok(params)
+}
+
+fn push_param(
+    params: &mut Vec<Param>,
+    groups: &mut Vec<(usize, Vec<usize>)>,
+    start: usize,
+    end: usize,
+    arg: usize,
+) {
+    if let Some((_, members)) = groups.last_mut() {
+        members.push(params.len());
+    }
+
+    params.push(Param {
+        start,
+        end,
+        arg,
+        group: None,
+    });
+}
+
+fn find(sql: &str, from: usize, c: char) -> Result<usize, String> {
+    sql[from..]
+        .find(c)
+        .map(|i| from + i)
+        .ok_or_else(|| format!("unterminated `{}` in query", c))
+}
+
+fn digits(s: &str) -> usize {
+    s.bytes().take_while(u8::is_ascii_digit).count()
+}
+
+// The word that ends at `end` (ignoring whitespace) and where it starts
+fn word_before(sql: &str, end: usize) -> (&str, usize) {
+    let before = sql[..end].trim_end();
+    let start = before
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map_or(0, |i| i + 1);
+
+    (&before[start..], start)
+}
+
+fn find_list<'a>(sql: &str, params: &'a [Param], arg: usize) -> Result<List<'a>, String> {
+    let mut uses = params.iter().filter(|param| param.arg == arg);
+
+    let param = match (uses.next(), uses.next()) {
+        (Some(param), None) => param,
+
+        _ => {
+            return Err(format!(
+                "list argument #{} must be bound to exactly one parameter",
+                arg + 1
+            ))
+        }
+    };
+
+    let context = param.group.and_then(|(start, end)| {
+        let (word, word_start) = word_before(sql, start);
+
+        if word.eq_ignore_ascii_case("values") {
+            return Some(ListContext::Values { start: word_start });
+        }
+
+        let only_element = sql[start + 1..end].trim() == &sql[param.start..param.end];
+
+        if only_element && word.eq_ignore_ascii_case("in") {
+            let (not, not_start) = word_before(sql, word_start);
+
+            return Some(if not.eq_ignore_ascii_case("not") {
+                ListContext::In {
+                    start: not_start,
+                    not: true,
+                }
+            } else {
+                ListContext::In {
+                    start: word_start,
+                    not: false,
+                }
+            });
+        }
+
+        None
+    });
+
+    match context {
+        Some(context) => Ok(List { param, context }),
+
+        None => Err(format!(
+            "list argument #{} must be the only element of `IN (..)` or in the row of `VALUES (..)`",
+            arg + 1
+        )),
+    }
+}
+
+// The list arguments of each `VALUES` row
+#[cfg(feature = "postgres")]
+fn row_lists(lists: &[List]) -> Vec<Vec<usize>> {
+    let mut rows: Vec<(usize, Vec<usize>)> = Vec::new();
+
+    for list in lists {
+        if let ListContext::Values { start } = list.context {
+            match rows.iter_mut().find(|(row_start, _)| *row_start == start) {
+                Some((_, row)) => row.push(list.param.arg),
+                None => rows.push((start, vec![list.param.arg])),
+            }
+        }
+    }
+
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+// Rewrites the query to bind each list as an array
+#[cfg(feature = "postgres")]
+fn rewrite<DB: DatabaseExt>(
+    sql: &str,
+    params: &[Param],
+    lists: &[List],
+    describe: &Describe<DB>,
+) -> String
+where
+    DB::TypeInfo: Display,
+{
+    // (start, end, replacement)
+    let mut edits: Vec<(usize, usize, String)> = Vec::new();
+
+    for list in lists {
+        let param = &sql[list.param.start..list.param.end];
+        let (_, group_end) = list.param.group.unwrap();
+
+        match list.context {
+            ListContext::In { start, not } => edits.push((
+                start,
+                group_end + 1,
+                if not {
+                    format!("<> ALL({})", param)
+                } else {
+                    format!("= ANY({})", param)
+                },
+            )),
+
+            ListContext::Values { start } => {
+                // every list of the same row is handled by the first
+                if edits.iter().any(|&(edit_start, _, _)| edit_start == start) {
+                    continue;
+                }
+
+                let (group_start, group_end) = list.param.group.unwrap();
+
+                let mut row = String::new();
+                let mut arrays = Vec::new();
+                let mut pos = group_start + 1;
+
+                for param in params
+                    .iter()
+                    .filter(|param| param.start > group_start && param.end <= group_end)
+                {
+                    row.push_str(&sql[pos..param.start]);
+                    pos = param.end;
+
+                    if lists.iter().any(|list| list.param.arg == param.arg) {
+                        let ty = describe.param_types[param.arg]
+                            .as_ref()
+                            .map(|ty| format!("::{}[]", ty))
+                            .unwrap_or_default();
+
+                        row.push_str(&format!("_sqlx_unnest.c{}", arrays.len()));
+                        arrays.push(format!("{}{}", &sql[param.start..param.end], ty));
+                    } else {
+                        row.push_str(&sql[param.start..param.end]);
+                    }
+                }
+
+                row.push_str(&sql[pos..group_end]);
+
+                let columns = (0..arrays.len())
+                    .map(|i| format!("c{}", i))
+                    .collect::<Vec<_>>();
+
+                edits.push((
+                    start,
+                    group_end + 1,
+                    format!(
+                        "SELECT {} FROM UNNEST({}) AS _sqlx_unnest({})",
+                        row.trim(),
+                        arrays.join(", "),
+                        columns.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+
+    edits.sort_by_key(|&(start, _, _)| start);
+
+    let mut out = String::with_capacity(sql.len());
+    let mut pos = 0;
+
+    for (start, end, replacement) in edits {
+        out.push_str(&sql[pos..start]);
+        out.push_str(&replacement);
+        pos = end;
+    }
+
+    out.push_str(&sql[pos..]);
+    out
+}
+
+// Splits the query into the pieces that it is built from
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+fn build_pieces(sql: &str, params: &[Param], lists: &[List]) -> Vec<Piece> {
+    let is_list = |param: &Param| lists.iter().any(|list| list.param.arg == param.arg);
+
+    let rows = |param: &Param| {
+        lists.iter().any(|list| {
+            list.param.group == param.group
+                && match list.context {
+                    ListContext::Values { .. } => true,
+                    ListContext::In { .. } => false,
+                }
+        })
+    };
+
+    let mut pieces = Vec::new();
+    let mut pos = 0;
+
+    for param in params {
+        if param.start < pos {
+            // part of a `VALUES` row that has been handled already
+            continue;
+        }
+
+        if rows(param) {
+            let (group_start, group_end) = param.group.unwrap();
+
+            pieces.push(Piece::Text(sql[pos..group_start].to_owned()));
+
+            let mut row = Vec::new();
+            let mut row_lists = Vec::new();
+            let mut row_pos = group_start;
+
+            for param in params
+                .iter()
+                .filter(|p| p.start > group_start && p.end <= group_end)
+            {
+                row.push(Piece::Text(sql[row_pos..param.start].to_owned()));
+                row_pos = param.end;
+
+                if is_list(param) {
+                    row.push(Piece::RowValue(param.arg));
+                    row_lists.push(param.arg);
+                } else {
+                    row.push(Piece::Param(param.arg));
+                }
+            }
+
+            row.push(Piece::Text(sql[row_pos..=group_end].to_owned()));
+
+            pieces.push(Piece::Rows {
+                pieces: row,
+                lists: row_lists,
+            });
+
+            pos = group_end + 1;
+        } else {
+            pieces.push(Piece::Text(sql[pos..param.start].to_owned()));
+            pieces.push(if is_list(param) {
+                Piece::List(param.arg)
+            } else {
+                Piece::Param(param.arg)
+            });
+
+            pos = param.end;
+        }
+    }
+
+    pieces.push(Piece::Text(sql[pos..].to_owned()));
+    pieces
+}
@@ -1,8 +1,9 @@
 use std::fmt::Display;
 
 use proc_macro2::TokenStream;
-use quote::{format_ident, quote};
+use quote::quote;
 
+pub use file_mod::{expand_query_file_mod, QueryFileModInput};
 pub use input::{QueryAsMacroInput, QueryMacroInput};
 pub use query::expand_query;
 
@@ -12,7 +13,9 @@ use sqlx::connection::Connection;
 use sqlx::database::Database;
 
 mod args;
+mod file_mod;
 mod input;
+mod lists;
 mod output;
 mod query;
 
@@ -37,7 +40,8 @@ where
     C::Database: DatabaseExt + Sized,
     <C::Database as Database>::TypeInfo: Display,
 {
-    let describe = input.query_input.describe_validate(&mut conn).await?;
+    let input = input.expand_named::<C::Database>()?;
+    let (describe, expansion) = lists::describe_expand(&input.query_input, &mut conn).await?;
 
     if describe.result_columns.is_empty() {
         return Err(syn::Error::new(
@@ -47,32 +51,52 @@ where
         .into());
     }
 
-    let args_tokens = args::quote_args(&input.query_input, &describe, checked)?;
+    let args_tokens = args::quote_args(&input.query_input, &describe, &expansion, checked)?;
 
-    let query_args = format_ident!("query_args");
+    let query = lists::quote_query::<C::Database>(&input.query_input, &expansion);
 
     let columns = output::columns_to_rust(&describe)?;
-    let output = output::quote_query_as::<C::Database>(
-        &input.query_input.source,
-        &input.as_ty.path,
-        &query_args,
-        &columns,
-        checked,
-    );
-
-    let arg_names = &input.query_input.arg_names;
-
-    Ok(quote! {
-        macro_rules! macro_result {
-            (#($#arg_names:expr),*) => {{
-                use sqlx::arguments::Arguments as _;
-
-                #args_tokens
-
-                #output
-            }}
-        }
-    })
+    let output =
+        output::quote_query_as::<C::Database>(&query, &input.as_ty.path, &columns, checked);
+
+    Ok(args::quote_macro_result(
+        &input.query_input,
+        quote! {
+            use sqlx::arguments::Arguments as _;
+
+            #args_tokens
+
+            #output
+        },
+    ))
+}
+
+pub async fn expand_query_scalar<C: Connection>(
+    input: QueryMacroInput,
+    mut conn: C,
+) -> crate::Result<TokenStream>
+where
+    C::Database: DatabaseExt + Sized,
+    <C::Database as Database>::TypeInfo: Display,
+{
+    let input = input.expand_named::<C::Database>()?;
+    let (describe, expansion) = lists::describe_expand(&input, &mut conn).await?;
+
+    let args_tokens = args::quote_args(&input, &describe, &expansion, true)?;
+
+    let query = lists::quote_query::<C::Database>(&input, &expansion);
+    let output = output::quote_query_scalar(&query, &describe)?;
+
+    Ok(args::quote_macro_result(
+        &input,
+        quote! {
+            use sqlx::arguments::Arguments as _;
+
+            #args_tokens
+
+            #output
+        },
+    ))
 }
 
 pub async fn expand_query_file_as<C: Connection>(
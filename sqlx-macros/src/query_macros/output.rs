@@ -1,8 +1,8 @@
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::Path;
+use syn::{Path, Type};
 
-use sqlx::describe::Describe;
+use sqlx::describe::{Column, Describe};
 
 use crate::database::DatabaseExt;
 
@@ -11,6 +11,50 @@ use std::fmt::{self, Display, Formatter};
 pub struct RustColumn {
     pub(super) ident: Ident,
     pub(super) type_: TokenStream,
+
+    // the type was given in the column name so it is checked against the column at runtime
+    pub(super) type_override: bool,
+}
+
+// Overrides parsed from a column name of the form `name`, `name!`, `name?` or `name: Type`
+// (or `name!: Type` and `name?: Type`)
+struct ColumnOverride<'a> {
+    name: &'a str,
+    nullability: Option<ColumnNullabilityOverride>,
+    type_: Option<&'a str>,
+}
+
+#[derive(PartialEq)]
+enum ColumnNullabilityOverride {
+    NonNull,
+    Nullable,
+}
+
+impl<'a> ColumnOverride<'a> {
+    fn parse(name: &'a str) -> Self {
+        let (name, type_) = match name.find(':') {
+            Some(i) => (name[..i].trim_end(), Some(name[i + 1..].trim())),
+            None => (name, None),
+        };
+
+        let nullability = if name.ends_with('!') {
+            Some(ColumnNullabilityOverride::NonNull)
+        } else if name.ends_with('?') {
+            Some(ColumnNullabilityOverride::Nullable)
+        } else {
+            None
+        };
+
+        ColumnOverride {
+            name: if nullability.is_some() {
+                &name[..name.len() - 1]
+            } else {
+                name
+            },
+            nullability,
+            type_,
+        }
+    }
 }
 
 struct DisplayColumn<'a> {
@@ -42,43 +86,34 @@ pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Resul
                 .as_deref()
                 .ok_or_else(|| format!("column at position {} must have a name", i))?;
 
-            let ident = parse_ident(name)?;
-
-            let mut type_ = if let Some(type_info) = &column.type_info {
-                <DB as DatabaseExt>::return_type_for_id(&type_info).map_or_else(
-                    || {
-                        let message = if let Some(feature_gate) =
-                            <DB as DatabaseExt>::get_feature_gate(&type_info)
-                        {
-                            format!(
-                                "optional feature `{feat}` required for type {ty} of {col}",
-                                ty = &type_info,
-                                feat = feature_gate,
-                                col = DisplayColumn {
-                                    idx: i,
-                                    name: column.name.as_deref()
-                                }
-                            )
-                        } else {
-                            format!(
-                                "unsupported type {ty} of {col}",
-                                ty = type_info,
-                                col = DisplayColumn {
-                                    idx: i,
-                                    name: column.name.as_deref()
-                                }
-                            )
-                        };
-                        syn::Error::new(Span::call_site(), message).to_compile_error()
-                    },
-                    |t| t.parse().unwrap(),
-                )
-            } else {
+            let column_override = ColumnOverride::parse(name);
+            let ident = parse_ident(column_override.name)?;
+            let type_ = column_to_rust_type::<DB>(i, column, &column_override);
+
+            Ok(RustColumn {
+                ident,
+                type_,
+                type_override: column_override.type_.is_some(),
+            })
+        })
+        .collect::<crate::Result<Vec<_>>>()
+}
+
+// The Rust type of a column, which is `Option<T>` if the column may be `NULL`
+fn column_to_rust_type<DB: DatabaseExt>(
+    i: usize,
+    column: &Column<DB>,
+    column_override: &ColumnOverride,
+) -> TokenStream {
+    let mut type_ = if let Some(type_) = column_override.type_ {
+        syn::parse_str::<Type>(type_).map_or_else(
+            |e| {
                 syn::Error::new(
                     Span::call_site(),
                     format!(
-                        "database couldn't tell us the type of {col}; \
-                     this can happen for columns that are the result of an expression",
+                        "invalid type override {:?} of {col}: {}",
+                        type_,
+                        e,
                         col = DisplayColumn {
                             idx: i,
                             name: column.name.as_deref()
@@ -86,21 +121,68 @@ pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Resul
                     ),
                 )
                 .to_compile_error()
-            };
+            },
+            |type_| quote!(#type_),
+        )
+    } else if let Some(type_info) = &column.type_info {
+        <DB as DatabaseExt>::return_type_for_id(&type_info).map_or_else(
+            || {
+                let message =
+                    if let Some(feature_gate) = <DB as DatabaseExt>::get_feature_gate(&type_info) {
+                        format!(
+                            "optional feature `{feat}` required for type {ty} of {col}",
+                            ty = &type_info,
+                            feat = feature_gate,
+                            col = DisplayColumn {
+                                idx: i,
+                                name: column.name.as_deref()
+                            }
+                        )
+                    } else {
+                        format!(
+                            "unsupported type {ty} of {col}",
+                            ty = type_info,
+                            col = DisplayColumn {
+                                idx: i,
+                                name: column.name.as_deref()
+                            }
+                        )
+                    };
+                syn::Error::new(Span::call_site(), message).to_compile_error()
+            },
+            |t| t.parse().unwrap(),
+        )
+    } else {
+        syn::Error::new(
+            Span::call_site(),
+            format!(
+                "database couldn't tell us the type of {col}; \
+             this can happen for columns that are the result of an expression",
+                col = DisplayColumn {
+                    idx: i,
+                    name: column.name.as_deref()
+                }
+            ),
+        )
+        .to_compile_error()
+    };
 
-            if !column.non_null.unwrap_or(false) {
-                type_ = quote! { Option<#type_> };
-            }
+    let non_null = match column_override.nullability {
+        Some(ColumnNullabilityOverride::NonNull) => true,
+        Some(ColumnNullabilityOverride::Nullable) => false,
+        None => column.non_null.unwrap_or(false),
+    };
 
-            Ok(RustColumn { ident, type_ })
-        })
-        .collect::<crate::Result<Vec<_>>>()
+    if !non_null {
+        type_ = quote! { Option<#type_> };
+    }
+
+    type_
 }
 
 pub fn quote_query_as<DB: DatabaseExt>(
-    sql: &str,
+    query: &TokenStream,
     out_ty: &Path,
-    bind_args: &Ident,
     columns: &[RustColumn],
     checked: bool,
 ) -> TokenStream {
@@ -110,13 +192,16 @@ pub fn quote_query_as<DB: DatabaseExt>(
             &RustColumn {
                 ref ident,
                 ref type_,
-                ..
+                type_override,
             },
         )| {
             // For "checked" queries, the macro checks these at compile time and using "try_get"
-            // would also perform pointless runtime checks
+            // would also perform pointless runtime checks; a type given in the column name
+            // is not checked at compile time so it is checked when decoding instead
 
-            if checked {
+            if checked && type_override {
+                quote!( #ident: row.try_get::<#type_, _>(#i).try_unwrap_optional()? )
+            } else if checked {
                 quote!( #ident: row.try_get_unchecked::<#type_, _>(#i).try_unwrap_optional()? )
             } else {
                 quote!( #ident: row.try_get_unchecked(#i)? )
@@ -124,11 +209,10 @@ pub fn quote_query_as<DB: DatabaseExt>(
         },
     );
 
-    let db_path = DB::db_path();
     let row_path = DB::row_path();
 
     quote! {
-        sqlx::query::<#db_path>(#sql).bind_all(#bind_args).try_map(|row: #row_path| {
+        #query.try_map(|row: #row_path| {
             use sqlx::Row as _;
             use sqlx::result_ext::ResultExt as _;
 
@@ -137,6 +221,44 @@ pub fn quote_query_as<DB: DatabaseExt>(
     }
 }
 
+pub fn quote_query_scalar<DB: DatabaseExt>(
+    query: &TokenStream,
+    describe: &Describe<DB>,
+) -> crate::Result<TokenStream> {
+    let columns = describe.result_columns.len();
+
+    if columns != 1 {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            format!("expected exactly 1 column, got {}", columns),
+        )
+        .into());
+    }
+
+    // the name of the column is only used for the overrides as there is no field to name
+    let column = &describe.result_columns[0];
+    let column_override = ColumnOverride::parse(column.name.as_deref().unwrap_or(""));
+    let type_ = column_to_rust_type::<DB>(0, column, &column_override);
+
+    let row_path = DB::row_path();
+
+    // see `quote_query_as()` for why an overridden type is checked when decoding
+    let get = if column_override.type_.is_some() {
+        quote!(try_get)
+    } else {
+        quote!(try_get_unchecked)
+    };
+
+    Ok(quote! {
+        #query.try_map(|row: #row_path| -> sqlx::Result<#type_> {
+            use sqlx::Row as _;
+            use sqlx::result_ext::ResultExt as _;
+
+            row.#get::<#type_, _>(0).try_unwrap_optional()
+        })
+    })
+}
+
 fn parse_ident(name: &str) -> crate::Result<Ident> {
     // workaround for the following issue (it's semi-fixed but still spits out extra diagnostics)
     // https://github.com/dtolnay/syn/issues/749#issuecomment-575451318
@@ -4,10 +4,10 @@ use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use syn::{Ident, Path};
 
-use quote::{format_ident, quote};
+use quote::quote;
 use sqlx::{connection::Connection, database::Database};
 
-use super::{args, output, QueryMacroInput};
+use super::{args, lists, output, QueryMacroInput};
 use crate::database::DatabaseExt;
 
 /// Given an input like `query!("SELECT * FROM accounts WHERE account_id > ?", account_id)`,
@@ -21,26 +21,24 @@ where
     C::Database: DatabaseExt + Sized,
     <C::Database as Database>::TypeInfo: Display,
 {
-    let describe = input.describe_validate(&mut conn).await?;
-    let sql = &input.source;
+    let input = input.expand_named::<C::Database>()?;
+    let (describe, expansion) = lists::describe_expand(&input, &mut conn).await?;
 
-    let args = args::quote_args(&input, &describe, checked)?;
+    let args = args::quote_args(&input, &describe, &expansion, checked)?;
 
-    let arg_names = &input.arg_names;
-    let db_path = <C::Database as DatabaseExt>::db_path();
+    let query = lists::quote_query::<C::Database>(&input, &expansion);
 
     if describe.result_columns.is_empty() {
-        return Ok(quote! {
-            macro_rules! macro_result {
-                (#($#arg_names:expr),*) => {{
-                    use sqlx::arguments::Arguments as _;
+        return Ok(args::quote_macro_result(
+            &input,
+            quote! {
+                use sqlx::arguments::Arguments as _;
 
-                    #args
+                #args
 
-                    sqlx::query::<#db_path>(#sql).bind_all(query_args)
-                }
-            }}
-        });
+                #query
+            },
+        ));
     }
 
     let columns = output::columns_to_rust(&describe)?;
@@ -53,33 +51,31 @@ where
             |&output::RustColumn {
                  ref ident,
                  ref type_,
+                 ..
              }| quote!(#ident: #type_,),
         )
         .collect::<TokenStream>();
 
-    let query_args = format_ident!("query_args");
     let output = output::quote_query_as::<C::Database>(
-        sql,
+        &query,
         &record_type,
-        &query_args,
         if checked { &columns } else { &[] },
         checked,
     );
 
-    Ok(quote! {
-        macro_rules! macro_result {
-            (#($#arg_names:expr),*) => {{
-                use sqlx::arguments::Arguments as _;
+    Ok(args::quote_macro_result(
+        &input,
+        quote! {
+            use sqlx::arguments::Arguments as _;
 
-                #[derive(Debug)]
-                struct #record_type {
-                    #record_fields
-                }
+            #[derive(Debug)]
+            struct #record_type {
+                #record_fields
+            }
 
-                #args
+            #args
 
-                #output
-            }
-        }}
-    })
+            #output
+        },
+    ))
 }
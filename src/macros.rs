@@ -64,6 +64,78 @@
 /// * Postgres: `$N` where `N` is the 1-based positional argument index
 /// * MySQL: `?` which matches arguments in order that it appears in the query
 ///
+/// ## Named Parameters
+/// Arguments may instead be named, `name = expr`, and written in the query as `:name` or
+/// `@name` for any database. The query is rewritten to the bind parameters of the database
+/// before it is checked, so a name may be used more than once. Every name must be used and
+/// named and positional arguments cannot be mixed.
+///
+/// ```rust,ignore
+/// sqlx::query!(
+///         "SELECT * FROM users WHERE org_id = :org_id AND (name = :name OR :name IS NULL)",
+///         org_id = org.id,
+///         name = filter.name,
+///     )
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+///
+/// ## Type Overrides: Bind Parameters
+/// The type of a bind parameter is checked against the type the database infers for it, which
+/// rejects a newtype or a `#[derive(sqlx::Type)]` enumeration. A cast with `as` overrides this
+/// check for one argument while the others are still checked:
+///
+/// * `id as _`: the type of the argument is not checked.
+/// * `status as Status`: the argument is checked to be a `Status` (or `Option<Status>`) instead
+/// of the inferred type.
+///
+/// The cast is not performed; the expression is bound as it is. Only Postgres infers the types
+/// of bind parameters so the override has no effect on the checks for other databases.
+///
+/// ```rust,ignore
+/// sqlx::query!(
+///         "UPDATE users SET status = $1 WHERE id = $2",
+///         status as Status,
+///         user_id as _
+///     )
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+///
+/// ## List Arguments
+/// An argument prefixed with `..` is a list (anything that derefs to a slice, such as a `Vec<T>`
+/// or an array) which is expanded into one bind parameter per element. A list argument may be
+/// used in exactly one of two places:
+///
+/// * as the only element of an `IN (...)` or `NOT IN (...)` list, which is checked against the
+/// type of a single element;
+/// * in the row of a `VALUES (...)` clause, which inserts one row per element. All list
+/// arguments in the row must have the same length and any other arguments are repeated for
+/// every row.
+///
+/// ```rust,ignore
+/// let users = sqlx::query!("SELECT id, name FROM users WHERE id IN ($1)", ..user_ids)
+///     .fetch_all(&mut conn)
+///     .await?;
+///
+/// sqlx::query!(
+///         "INSERT INTO users (name, email, active) VALUES ($1, $2, $3)",
+///         ..names,
+///         ..emails,
+///         true
+///     )
+///     .execute(&mut conn)
+///     .await?;
+/// ```
+///
+/// Postgres binds each list as a single array parameter: `IN ($1)` is rewritten to `= ANY($1)`
+/// and the `VALUES` row selects from `UNNEST(...)`, so the query is prepared only once and the
+/// arguments must be owned types matching the array (e.g. `&[String]`, not `&[&str]`).
+/// MySQL and SQLite build the query string with the number of parameters at runtime.
+///
+/// An empty list in `IN (...)` matches no rows (and `NOT IN (...)` matches every row). An empty
+/// list in `VALUES (...)` inserts nothing on Postgres but is a syntax error on MySQL and SQLite.
+///
 /// ## Nullability: Bind Parameters
 /// For a given expected type `T`, both `T` and `Option<T>` are allowed (as well as either
 /// behind references). `Option::None` will be bound as `NULL`, so if binding a type behind `Option`
@@ -83,6 +155,10 @@
 /// has a `NOT NULL` constraint. Columns that do not have a `NOT NULL` constraint or are the result
 /// of an expression are assumed to be nullable and so `Option<T>` is used instead of `T`.
 ///
+/// For Postgres, the query plan (from `EXPLAIN (VERBOSE)`) is also consulted: columns from the
+/// nullable side of an outer join are nullable even if they have a `NOT NULL` constraint, while
+/// literals, `count(..)` and `COALESCE(..)` with a non-null argument are never `NULL`.
+///
 /// For MySQL, the implementation looks at [the `NOT_NULL` flag](https://dev.mysql.com/doc/dev/mysql-server/8.0.12/group__group__cs__column__definition__flags.html#ga50377f5ca5b3e92f3931a81fe7b44043)
 /// of [the `ColumnDefinition` structure in `COM_QUERY_OK`](https://dev.mysql.com/doc/internals/en/com-query-response.html#column-definition):
 /// if it is set, `T` is used; if it is not set, `Option<T>` is used.
@@ -92,7 +168,37 @@
 /// `NULL` which then depends on the semantics of what functions are used. Consult the MySQL
 /// manual for the functions you are using to find the cases in which they return `NULL`.
 ///
-/// To override the nullability of an output column, use [query_as!].
+/// To override the nullability of an output column, see the next section.
+///
+/// ## Overrides: Output Columns
+/// The nullability and Rust type of an output column can be overridden with a column alias.
+/// As these are not valid identifiers they must be quoted (`"` in Postgres and SQLite,
+/// `` ` `` in MySQL):
+///
+/// * `SELECT id as "id!"`: the column is never `NULL` and is `T` instead of `Option<T>`; a
+/// `NULL` in the column is an `UnexpectedNullError` when the row is decoded.
+/// * `SELECT name as "name?"`: the column may be `NULL` and is `Option<T>` instead of `T`,
+/// as for a column from the nullable side of an outer join.
+/// * `SELECT status as "status: Status"`: the column is decoded as the given Rust type,
+/// such as a `#[derive(sqlx::Type)]` enumeration or a `Json<T>`. The nullability is still
+/// inferred and may be combined with an override (`"status!: Status"`).
+///
+/// The field of the output struct is named after the alias without the override. A type given
+/// this way cannot be checked at compile time; it is checked to be compatible with the type
+/// of the column (with [`TypeInfo::compatible`]) when the row is decoded.
+///
+/// ```rust,ignore
+/// let rec = sqlx::query!(
+///         r#"SELECT id as "id!: UserId", name as "name?" FROM users LEFT JOIN ..."#
+///     )
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// let id: UserId = rec.id;
+/// let name: Option<String> = rec.name;
+/// ```
+///
+/// [`TypeInfo::compatible`]: crate::types::TypeInfo::compatible
 ///
 /// ## Requirements
 /// * The `DATABASE_URL` environment variable must be set at build-time to point to a database
@@ -126,12 +232,12 @@ macro_rules! query (
         }
         macro_result!()
     });
-    ($query:literal, $($args:expr),*$(,)?) => ({
+    ($query:literal, $($args:tt)*) => ({
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query!($query, $($args),*);
+            $crate::sqlx_macros::query!($query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
 
@@ -149,12 +255,12 @@ macro_rules! query_unchecked (
         }
         macro_result!()
     });
-    ($query:literal, $($args:expr),*$(,)?) => ({
+    ($query:literal, $($args:tt)*) => ({
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query_unchecked!($query, $($args),*);
+            $crate::sqlx_macros::query_unchecked!($query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
 
@@ -210,12 +316,12 @@ macro_rules! query_file (
         }
         macro_result!()
     });
-    ($query:literal, $($args:expr),*$(,)?) => (#[allow(dead_code)]{
+    ($query:literal, $($args:tt)*) => (#[allow(dead_code)]{
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query_file!($query, $($args),*);
+            $crate::sqlx_macros::query_file!($query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
 
@@ -231,15 +337,63 @@ macro_rules! query_file_unchecked (
         }
         macro_result!()
     });
-    ($query:literal, $($args:expr),*$(,)?) => (#[allow(dead_code)]{
+    ($query:literal, $($args:tt)*) => (#[allow(dead_code)]{
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query_file_unchecked!($query, $($args),*);
+            $crate::sqlx_macros::query_file_unchecked!($query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
 
+/// Generates a module with a function for each of the named queries in a SQL file.
+///
+/// A query starts with a `-- name: <function>` line and ends at the next one. Each query is
+/// checked against the database like [query!] and its function returns the query with the
+/// arguments bound to it, ready to be executed. If the query returns rows, they are mapped to
+/// a struct named after the query (`find_user` returns `FindUser` rows) or to the type given
+/// after `->`, like [query_as!].
+///
+/// The parameters of the function are the named parameters (`:name` or `@name`) of the query.
+/// The comment lines directly after `-- name:` are the doc comment of the function, except for
+/// `-- param: <name>[: <Type>][ - <description>]` lines which declare the type of a parameter
+/// and document it. Postgres infers the types of parameters (strings and arrays are taken
+/// by reference); for MySQL and SQLite, the type of every parameter must be declared.
+///
+/// The file path is relative to the project root, as for [query_file!]. Paths in the file are
+/// resolved in the generated module, which imports everything from the module around it.
+///
+/// -----
+///
+/// `queries/users.sql`:
+/// ```text
+/// -- name: find_user -> User
+/// -- Find a user by their id.
+/// -- param: id: i64 - the id of the user
+/// SELECT id, name FROM users WHERE id = :id;
+///
+/// -- name: rename_user
+/// -- param: id: i64
+/// -- param: name: &str
+/// UPDATE users SET name = :name WHERE id = :id;
+/// ```
+///
+/// `src/users.rs`:
+/// ```rust,ignore
+/// sqlx::query_file_mod!(pub mod queries = "queries/users.sql");
+///
+/// let user = queries::find_user(1).fetch_one(&mut conn).await?;
+///
+/// queries::rename_user(user.id, "Herp Derpinson").execute(&mut conn).await?;
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_file_mod (
+    ($($input:tt)*) => (
+        $crate::sqlx_macros::query_file_mod!($($input)*);
+    )
+);
+
 /// A variant of [query!] which takes a path to an explicitly defined struct as the output type.
 ///
 /// This lets you return the struct from a function or add your own trait implementations.
@@ -302,12 +456,12 @@ macro_rules! query_as (
         }
         macro_result!()
     });
-    ($out_struct:path, $query:literal, $($args:expr),*$(,)?) => (#[allow(dead_code)] {
+    ($out_struct:path, $query:literal, $($args:tt)*) => (#[allow(dead_code)] {
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query_as!($out_struct, $query, $($args),*);
+            $crate::sqlx_macros::query_as!($out_struct, $query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
 
@@ -354,12 +508,12 @@ macro_rules! query_file_as (
         }
         macro_result!()
     });
-    ($out_struct:path, $query:literal, $($args:tt),*$(,)?) => (#[allow(dead_code)] {
+    ($out_struct:path, $query:literal, $($args:tt)*) => (#[allow(dead_code)] {
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query_file_as!($out_struct, $query, $($args),*);
+            $crate::sqlx_macros::query_file_as!($out_struct, $query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
 
@@ -376,12 +530,12 @@ macro_rules! query_as_unchecked (
         macro_result!()
     });
 
-    ($out_struct:path, $query:literal, $($args:expr),*$(,)?) => (#[allow(dead_code)] {
+    ($out_struct:path, $query:literal, $($args:tt)*) => (#[allow(dead_code)] {
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query_as_unchecked!($out_struct, $query, $($args),*);
+            $crate::sqlx_macros::query_as_unchecked!($out_struct, $query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
 
@@ -399,11 +553,49 @@ macro_rules! query_file_as_unchecked (
         macro_result!()
     });
 
-    ($out_struct:path, $query:literal, $($args:tt),*$(,)?) => (#[allow(dead_code)] {
+    ($out_struct:path, $query:literal, $($args:tt)*) => (#[allow(dead_code)] {
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::query_file_as_unchecked!($out_struct, $query, $($args)*);
+        }
+        macro_result!($($args)*)
+    })
+);
+
+/// A variant of [query!] which expects a single column from the query and evaluates to the
+/// value of that column instead of an anonymous record.
+///
+/// The type of the value is inferred as for a field of [query!], including the nullability
+/// and any [override](macro.query.html#overrides-output-columns) given in the name of the
+/// column. It is an error for the query to return more or less than one column.
+///
+/// ```rust,ignore
+/// let count: i64 = sqlx::query_scalar!("SELECT count(*) FROM accounts WHERE active = $1", true)
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// let names: Vec<String> = sqlx::query_scalar!(r#"SELECT name as "name!" FROM accounts"#)
+///     .fetch_all(&mut conn)
+///     .await?;
+/// ```
+///
+/// See also [query_scalar][crate::query_scalar()] for queries that are not checked at
+/// compile time.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! query_scalar (
+    ($query:literal) => ({
+        #[macro_use]
+        mod _macro_result {
+            $crate::sqlx_macros::query_scalar!($query);
+        }
+        macro_result!()
+    });
+    ($query:literal, $($args:tt)*) => ({
         #[macro_use]
         mod _macro_result {
-            $crate::sqlx_macros::query_file_as_unchecked!($out_struct, $query, $($args),*);
+            $crate::sqlx_macros::query_scalar!($query, $($args)*);
         }
-        macro_result!($($args),*)
+        macro_result!($($args)*)
     })
 );
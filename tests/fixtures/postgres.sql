@@ -12,3 +12,12 @@ CREATE TYPE inventory_item AS (
     supplier_id     INT,
     price           BIGINT
 );
+
+CREATE TYPE inventory_shelf AS (
+    label           TEXT,
+    tags            TEXT[],
+    featured        inventory_item,
+    items           inventory_item[]
+);
+
+CREATE DOMAIN positive_int AS INT4 CHECK (VALUE > 0);
@@ -1,5 +1,6 @@
-use sqlx::MySql;
-use sqlx_test::test_type;
+use sqlx::mysql::MySqlQueryAs;
+use sqlx::{Executor, MySql};
+use sqlx_test::{new, test_type};
 use std::fmt::Debug;
 
 // Transparent types are rust-side wrappers over DB types
@@ -41,3 +42,25 @@ test_type!(weak_enum(
 ));
 
 test_type!(strong_color_enum(MySql, Color, "'green'" == Color::Green));
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_enum_column() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE colors (color ENUM('red', 'green', 'blue') NOT NULL)")
+        .await?;
+
+    sqlx::query("INSERT INTO colors (color) VALUES (?), ('blue')")
+        .bind(Color::Green)
+        .execute(&mut conn)
+        .await?;
+
+    let colors: Vec<(Color,)> = sqlx::query_as("SELECT color FROM colors")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(colors, vec![(Color::Green,), (Color::Blue,)]);
+
+    Ok(())
+}
@@ -97,3 +97,25 @@ async fn test_query_bytes() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_insert_list_args_of_different_lengths() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let names = ["Derp Herpinson", "Herp Herpinson"];
+    let scores = [1.5f64];
+
+    let res = sqlx::query!(
+        "insert into accounts (name, score, is_active) values (?, ?, ?)",
+        ..names,
+        ..scores,
+        true
+    )
+    .execute(&mut conn)
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Argument(_))));
+
+    Ok(())
+}
@@ -1,7 +1,10 @@
 extern crate time_ as time;
 
-use sqlx::MySql;
-use sqlx_test::test_type;
+use std::collections::HashSet;
+
+use sqlx::mysql::MySqlQueryAs;
+use sqlx::{Executor, MySql};
+use sqlx_test::{new, test_type};
 
 test_type!(null(
     MySql,
@@ -52,6 +55,48 @@ test_type!(bytes(
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_bit_and_set() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // BIT and SET are only ever the type of a column
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE bit_and_set (
+    flags BIT(12) NOT NULL,
+    tags SET('red', 'green', 'blue') NOT NULL
+)
+        "#,
+    )
+    .await?;
+
+    let tags: HashSet<String> = vec!["red".to_owned(), "blue".to_owned()]
+        .into_iter()
+        .collect();
+
+    sqlx::query("INSERT INTO bit_and_set (flags, tags) VALUES (?, ?), (b'0', '')")
+        .bind(0b1010_0000_0101_u64)
+        .bind(&tags)
+        .execute(&mut conn)
+        .await?;
+
+    let rows: Vec<(u64, Vec<u8>, HashSet<String>, String)> =
+        sqlx::query_as("SELECT flags, flags, tags, tags FROM bit_and_set")
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(rows[0].0, 0b1010_0000_0101);
+    assert_eq!(rows[0].1, vec![0b1010, 0b0000_0101]);
+    assert_eq!(rows[0].2, tags);
+    assert_eq!(rows[0].3, "red,blue");
+
+    assert_eq!(rows[1].0, 0);
+    assert!(rows[1].2.is_empty());
+
+    Ok(())
+}
+
 #[cfg(feature = "chrono")]
 mod chrono {
     use super::*;
@@ -124,6 +169,49 @@ mod time_tests {
     ));
 }
 
+#[cfg(feature = "uuid")]
+mod uuid {
+    use super::*;
+    use sqlx::types::Uuid;
+
+    test_type!(uuid(
+        MySql,
+        Uuid,
+        "X'b731678f636f4135bc6f19440c13bd19'"
+            == Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap(),
+        "X'00000000000000000000000000000000'"
+            == Uuid::parse_str("00000000-0000-0000-0000-000000000000").unwrap()
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn test_uuid_columns() -> anyhow::Result<()> {
+        let mut conn = new::<MySql>().await?;
+
+        conn.execute(
+            "CREATE TEMPORARY TABLE uuids (bin BINARY(16) NOT NULL, text CHAR(36) NOT NULL)",
+        )
+        .await?;
+
+        let uuid = Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19")?;
+
+        sqlx::query("INSERT INTO uuids (bin, text) VALUES (?, ?)")
+            .bind(uuid)
+            .bind(uuid.to_hyphenated())
+            .execute(&mut conn)
+            .await?;
+
+        let (bin, text): (Uuid, Uuid) = sqlx::query_as("SELECT bin, text FROM uuids")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(bin, uuid);
+        assert_eq!(text, uuid);
+
+        Ok(())
+    }
+}
+
 #[cfg(feature = "bigdecimal")]
 test_type!(decimal(
     MySql,
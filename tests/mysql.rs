@@ -232,3 +232,38 @@ async fn test_fetch_one_and_ping() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_evicts_and_clears_cached_statements() -> anyhow::Result<()> {
+    use sqlx::{Connect, MySqlConnection};
+
+    let url = dotenv::var("DATABASE_URL")?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+
+    let mut conn =
+        MySqlConnection::connect(&*format!("{}{}statement_cache_capacity=2", url, separator))
+            .await?;
+
+    for _ in 0..2 {
+        for i in 0..3_i32 {
+            let (value,): (i64,) = sqlx::query_as(&format!("SELECT ? + {}", i))
+                .bind(1_i32)
+                .fetch_one(&mut conn)
+                .await?;
+
+            assert_eq!(value, 1 + i as i64);
+        }
+    }
+
+    conn.clear_cached_statements().await?;
+
+    let (value,): (i64,) = sqlx::query_as("SELECT ? + 1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use sqlx::{postgres::PgQueryAs, Connection, Cursor, Executor, FromRow, Postgres};
+use sqlx::{postgres::PgQueryAs, Connection, Cursor, Executor, FromRow, Postgres, Row};
 use sqlx_test::{new, test_type};
 use std::fmt::Debug;
 
@@ -16,6 +16,18 @@ enum Weak {
     Three = 4,
 }
 
+// Types without a Postgres array type opt out of deriving one
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(transparent, no_pg_array)]
+struct TransparentNoArray(u64);
+
+#[derive(PartialEq, Copy, Clone, Debug, sqlx::Type)]
+#[sqlx(no_pg_array)]
+#[repr(u8)]
+enum WeakNoArray {
+    One = 1,
+}
+
 // "Strong" enums can map to TEXT (25)
 #[derive(PartialEq, Debug, sqlx::Type)]
 #[sqlx(rename = "text")]
@@ -48,6 +60,16 @@ struct InventoryItem {
     price: Option<i64>,
 }
 
+// Records may contain arrays, nullable fields and other records
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(rename = "inventory_shelf")]
+struct InventoryShelf {
+    label: String,
+    tags: Vec<String>,
+    featured: Option<InventoryItem>,
+    items: Vec<InventoryItem>,
+}
+
 test_type!(transparent(
     Postgres,
     Transparent,
@@ -55,6 +77,12 @@ test_type!(transparent(
     "23523" == Transparent(23523)
 ));
 
+test_type!(transparent_array(
+    Postgres,
+    Vec<Transparent>,
+    "'{0,23523}'::int4[]" == vec![Transparent(0), Transparent(23523)]
+));
+
 test_type!(weak_enum(
     Postgres,
     Weak,
@@ -63,6 +91,12 @@ test_type!(weak_enum(
     "4::int4" == Weak::Three
 ));
 
+test_type!(weak_enum_array(
+    Postgres,
+    Vec<Weak>,
+    "'{0,4}'::int4[]" == vec![Weak::One, Weak::Three]
+));
+
 test_type!(strong_enum(
     Postgres,
     Strong,
@@ -159,6 +193,62 @@ SELECT $1 = 'happy'::mood, $1
     assert!(rec.0);
     assert_eq!(rec.1, Mood::Happy);
 
+    // Arrays of enums are resolved by name as `mood[]`
+
+    let moods = vec![Mood::Ok, Mood::Sad];
+
+    let rec: (bool, Vec<Mood>) = sqlx::query_as(
+        "
+SELECT $1 = '{ok,sad}'::mood[], $1
+        ",
+    )
+    .bind(&moods)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, moods);
+
+    let (count,): (i64,) = sqlx::query_as(
+        "
+SELECT COUNT(*) FROM people WHERE id = $1 AND mood = ANY($2)
+        ",
+    )
+    .bind(people_id)
+    .bind(&moods)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_domain_array_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // A domain is sent and received as its base type
+    let values = vec![Transparent(1), Transparent(23523)];
+
+    let rec: (Vec<Transparent>,) = sqlx::query_as(
+        "
+        SELECT $1::positive_int[]
+        ",
+    )
+    .bind(&values)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(rec.0, values);
+
+    let mut cursor = conn.fetch("SELECT '{1,23523}'::positive_int[]");
+    let row = cursor.next().await?.unwrap();
+    let rec = row.try_get::<Vec<Transparent>, _>(0)?;
+
+    assert_eq!(rec, values);
+
     Ok(())
 }
 
@@ -205,6 +295,140 @@ END $$;
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_nested_record_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+DO $$ BEGIN
+
+CREATE TYPE inventory_item AS (
+    name            text,
+    supplier_id     int,
+    price           bigint
+);
+
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+
+DO $$ BEGIN
+
+CREATE TYPE inventory_shelf AS (
+    label           text,
+    tags            text[],
+    featured        inventory_item,
+    items           inventory_item[]
+);
+
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+    "#,
+    )
+    .await?;
+
+    let value = InventoryShelf {
+        label: "top".to_owned(),
+        tags: vec!["games".to_owned(), "novelty".to_owned()],
+        featured: None,
+        items: vec![
+            InventoryItem {
+                name: "fuzzy dice".to_owned(),
+                supplier_id: Some(42),
+                price: Some(199),
+            },
+            InventoryItem {
+                name: "rubber duck".to_owned(),
+                supplier_id: None,
+                price: None,
+            },
+        ],
+    };
+
+    let rec: (bool, InventoryShelf) = sqlx::query_as(
+        "
+        SELECT $1 = ROW(
+            'top',
+            ARRAY['games', 'novelty'],
+            NULL,
+            ARRAY[ROW('fuzzy dice', 42, 199), ROW('rubber duck', NULL, NULL)]::inventory_item[]
+        )::inventory_shelf, $1
+        ",
+    )
+    .bind(&value)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, value);
+
+    // the same record decoded from the TEXT format of a simple query
+    let mut cursor = conn.fetch(
+        "
+        SELECT ROW(
+            'top',
+            ARRAY['games', 'novelty'],
+            NULL,
+            ARRAY[ROW('fuzzy dice', 42, 199), ROW('rubber duck', NULL, NULL)]::inventory_item[]
+        )::inventory_shelf
+        ",
+    );
+
+    let row = cursor.next().await?.unwrap();
+    let rec = row.try_get::<InventoryShelf, _>(0)?;
+
+    assert_eq!(rec, value);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_record_array_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let items = vec![
+        InventoryItem {
+            name: "fuzzy dice".to_owned(),
+            supplier_id: Some(42),
+            price: Some(199),
+        },
+        InventoryItem {
+            name: "rubber duck".to_owned(),
+            supplier_id: Some(7),
+            price: None,
+        },
+    ];
+
+    let rec: (i32, Vec<InventoryItem>) = sqlx::query_as(
+        "
+        SELECT cardinality($1), $1
+        ",
+    )
+    .bind(&items)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(rec.0, 2);
+    assert_eq!(rec.1, items);
+
+    let (found,): (bool,) = sqlx::query_as(
+        "
+        SELECT ROW('rubber duck', 7, NULL)::inventory_item = ANY($1)
+        ",
+    )
+    .bind(&items)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(found);
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -325,3 +549,134 @@ async fn test_from_row_with_rename() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "macros")]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_rename_all() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    #[sqlx(rename_all = "camelCase")]
+    struct Account {
+        user_id: i32,
+        display_name: String,
+
+        #[sqlx(rename = "is_active")]
+        active: bool,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: Account = sqlx::query_as(
+        r#"SELECT * from (VALUES (1, 'Herp Derpinson', true)) accounts("userId", "displayName", is_active)"#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.user_id);
+    assert_eq!("Herp Derpinson", account.display_name);
+    assert!(account.active);
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_with_field_attributes() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+    use std::convert::TryFrom;
+
+    #[derive(Debug, PartialEq)]
+    struct Score(u8);
+
+    impl TryFrom<i32> for Score {
+        type Error = std::num::TryFromIntError;
+
+        fn try_from(value: i32) -> Result<Self, Self::Error> {
+            u8::try_from(value).map(Score)
+        }
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Parent {
+        parent_id: i32,
+        parent_name: String,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account {
+        id: i32,
+
+        #[sqlx(try_from = "i32")]
+        score: Score,
+
+        #[sqlx(default)]
+        nickname: Option<String>,
+
+        #[sqlx(flatten)]
+        parent: Parent,
+
+        #[sqlx(skip)]
+        posts: Vec<String>,
+    }
+
+    let mut conn = new::<Postgres>().await?;
+
+    let account: Account = sqlx::query_as(
+        "SELECT * from (VALUES (1, 80, 2, 'Derp Herpinson')) accounts(id, score, parent_id, parent_name)"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(Score(80), account.score);
+    assert_eq!(None, account.nickname);
+    assert_eq!(2, account.parent.parent_id);
+    assert_eq!("Derp Herpinson", account.parent.parent_name);
+    assert!(account.posts.is_empty());
+
+    // a column which is present must still decode
+    let res: sqlx::Result<Account> = sqlx::query_as(
+        "SELECT * from (VALUES (1, 80, 2, 'Derp Herpinson', 1)) accounts(id, score, parent_id, parent_name, nickname)"
+    )
+    .fetch_one(&mut conn)
+    .await;
+
+    assert!(res.is_err());
+
+    // a value that does not convert is an error
+    let res: sqlx::Result<Account> = sqlx::query_as(
+        "SELECT * from (VALUES (1, 300, 2, 'Derp Herpinson')) accounts(id, score, parent_id, parent_name)"
+    )
+    .fetch_one(&mut conn)
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Decode(_))));
+
+    Ok(())
+}
+
+#[cfg(feature = "macros")]
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_from_row_tuple_struct() -> anyhow::Result<()> {
+    use sqlx::prelude::*;
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account(i32, String, Option<String>);
+
+    let mut conn = new::<Postgres>().await?;
+
+    // the names of the columns are not used
+    let account: Account = sqlx::query_as("SELECT 1 as a, 'Herp Derpinson' as b, null::text as c")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(1, account.0);
+    assert_eq!("Herp Derpinson", account.1);
+    assert_eq!(None, account.2);
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use sqlx::Postgres;
+use sqlx::{Connection, Postgres};
 use sqlx_test::new;
 
 use futures::TryStreamExt;
@@ -38,12 +38,12 @@ async fn test_text_var_char_char_n() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
 
     // TEXT
-    // we cannot infer nullability from an expression
+    // a literal is inferred to be non-null from the query plan
     let rec = sqlx::query!("SELECT 'Hello'::text as greeting")
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(rec.greeting.as_deref(), Some("Hello"));
+    assert_eq!(rec.greeting, "Hello");
 
     // VARCHAR(N)
 
@@ -51,7 +51,7 @@ async fn test_text_var_char_char_n() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(rec.greeting.as_deref(), Some("Hello"));
+    assert_eq!(rec.greeting, "Hello");
 
     // CHAR(N)
 
@@ -59,7 +59,7 @@ async fn test_text_var_char_char_n() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(rec.greeting.as_deref(), Some("Hello"));
+    assert_eq!(rec.greeting, "Hello");
 
     Ok(())
 }
@@ -167,7 +167,7 @@ async fn query_by_string() -> anyhow::Result<()> {
     .fetch_one(&mut conn)
     .await?;
 
-    assert_eq!(result.string, Some(string));
+    assert_eq!(result.string, string);
 
     Ok(())
 }
@@ -200,6 +200,117 @@ async fn test_nullable_err() -> anyhow::Result<()> {
     panic!("expected `UnexpectedNullError`, got {}", err)
 }
 
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(transparent)]
+struct AccountId(i32);
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_column_override() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the columns of a VALUES list of more than one row are assumed to be nullable
+    let account = sqlx::query!(
+        r#"SELECT id as "id!: AccountId", name as "name!", id as "parent_id?", name as "alias: String"
+        from (VALUES (1, 'Herp Derpinson'), (2, 'Derp Herpinson')) accounts(id, name)"#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, AccountId(1));
+    assert_eq!(account.name, "Herp Derpinson");
+    assert_eq!(account.parent_id, Some(1));
+    assert_eq!(account.alias.as_deref(), Some("Herp Derpinson"));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_column_override_mismatched_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let err = sqlx::query!(r#"SELECT 'Herp Derpinson' as "id!: AccountId""#)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    if let sqlx::Error::Decode(_) = err {
+        return Ok(());
+    }
+
+    panic!("expected a mismatched type error, got {}", err)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_column_override_nullable_err() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let err = sqlx::query!(r#"SELECT null::int as "id!""#)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    if let sqlx::Error::Decode(err) = &err {
+        if let Some(sqlx::error::UnexpectedNullError) = err.downcast_ref() {
+            return Ok(());
+        }
+    }
+
+    panic!("expected `UnexpectedNullError`, got {}", err)
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_arg_override() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let id = AccountId(1);
+    let parent_id = Some(AccountId(2));
+    let name = String::from("Herp Derpinson");
+
+    let account = sqlx::query!(
+        r#"SELECT $1::int4 as "id!: AccountId", $2::int4 as "parent_id: AccountId", $3::text as "name!""#,
+        id as AccountId,
+        parent_id as AccountId,
+        &name as _,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(account.id, id);
+    assert_eq!(account.parent_id, parent_id);
+    assert_eq!(account.name, name);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_arg_override_of_any_expression() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let ids = [1_i64, 2];
+
+    let row = sqlx::query!(
+        "SELECT $1::int4 as max, $2::int8 as sum, $3::int8 as id, $4::int4 as small",
+        std::i32::MAX as _,
+        (ids[0] + ids[1]) as _,
+        ids[1] as i64,
+        1_i8 as i8 as _,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.max, Some(std::i32::MAX));
+    assert_eq!(row.sum, Some(3));
+    assert_eq!(row.id, Some(2));
+    assert_eq!(row.small, Some(1));
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn test_many_args() -> anyhow::Result<()> {
@@ -240,7 +351,7 @@ async fn test_array_from_slice() -> anyhow::Result<()> {
         .fetch_one(&mut conn)
         .await?;
 
-    assert_eq!(account.my_array, Some(vec![4, 3, 2, 1]));
+    assert_eq!(account.my_array, vec![4, 3, 2, 1]);
 
     println!("account ID: {:?}", account.my_array);
 
@@ -264,3 +375,254 @@ async fn fetch_is_usable_issue_224() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_nullability_from_plan() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let rec = sqlx::query!(
+        "SELECT count(*) as count, 1 as one, coalesce(null::int, 0) as zero, max(id) as max_id FROM accounts"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(rec.count, 0);
+    assert_eq!(rec.one, 1);
+    assert_eq!(rec.zero, 0);
+    assert_eq!(rec.max_id, None);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_nullability_outer_join() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query!("INSERT INTO accounts (name) VALUES ('Herp Derpinson')")
+        .execute(&mut tx)
+        .await?;
+
+    // `accounts.name` is `NOT NULL` but not on the nullable side of a `LEFT JOIN`
+    let rec = sqlx::query!(
+        r#"SELECT a.name, b.name as parent_name, coalesce(b.name, 'none') as parent_or_none
+        FROM accounts a
+        LEFT JOIN accounts b ON b.id = a.id + 1"#
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    assert_eq!(rec.name, "Herp Derpinson");
+    assert_eq!(rec.parent_name, None);
+    assert_eq!(rec.parent_or_none, "none");
+
+    let rec = sqlx::query!(
+        "INSERT INTO accounts (name) VALUES ('Herp Derpinson') RETURNING id, name, score"
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    let _: i64 = rec.id;
+
+    assert_eq!(rec.name, "Herp Derpinson");
+    assert_eq!(rec.score, None);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_query_scalar() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let count: i64 = sqlx::query_scalar!("SELECT count(*) FROM generate_series(1, $1)", 5i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 5);
+
+    let nums: Vec<Option<i32>> = sqlx::query_scalar!("SELECT * FROM generate_series(1, 3)")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(nums, vec![Some(1), Some(2), Some(3)]);
+
+    let id: Option<AccountId> = sqlx::query_scalar!(r#"SELECT 1::int4 as "id?: AccountId""#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, Some(AccountId(1)));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_list_args() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let ids = vec![1i32, 3];
+
+    let rows = sqlx::query!(
+        "SELECT id FROM (VALUES (1), (2), (3)) accounts(id) WHERE id IN ($1) ORDER BY id",
+        ..ids
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(
+        rows.iter().map(|row| row.id).collect::<Vec<_>>(),
+        [Some(1), Some(3)]
+    );
+
+    let count = sqlx::query_scalar!(
+        "SELECT count(*) FROM (VALUES (1), (2), (3)) accounts(id) WHERE id NOT IN ($1) AND id > $2",
+        ..[3i32],
+        1i32
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(count, 1);
+
+    // an empty list matches nothing
+    let empty: &[i32] = &[];
+
+    let count = sqlx::query_scalar!(
+        "SELECT count(*) FROM (VALUES (1), (2), (3)) accounts(id) WHERE id IN ($1)",
+        ..empty
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_list_args_values() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    let names = vec!["Herp Derpinson".to_owned(), "Derp Herpinson".to_owned()];
+    let scores = vec![1.5f64, 2.5];
+
+    let ids = sqlx::query_scalar!(
+        "INSERT INTO accounts (name, score, is_active) VALUES ($1, $2, $3) RETURNING id",
+        ..names,
+        ..scores,
+        true
+    )
+    .fetch_all(&mut tx)
+    .await?;
+
+    assert_eq!(ids.len(), 2);
+
+    let rows = sqlx::query!(
+        "SELECT name, score, is_active FROM accounts WHERE id IN ($1) ORDER BY id",
+        ..ids
+    )
+    .fetch_all(&mut tx)
+    .await?;
+
+    assert_eq!(rows[0].name, "Herp Derpinson");
+    assert_eq!(rows[0].score, Some(1.5));
+    assert_eq!(rows[1].name, "Derp Herpinson");
+    assert_eq!(rows[1].is_active, Some(true));
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_list_args_values_of_different_lengths() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let names = vec!["Herp Derpinson".to_owned(), "Derp Herpinson".to_owned()];
+    let scores = vec![1.5f64];
+
+    let res = sqlx::query_scalar!(
+        "INSERT INTO accounts (name, score, is_active) VALUES ($1, $2, $3) RETURNING id",
+        ..names,
+        ..scores,
+        true
+    )
+    .fetch_all(&mut conn)
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Argument(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_named_args() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let min = 2i32;
+
+    let ids = sqlx::query_scalar!(
+        "SELECT id FROM generate_series(1, 10) AS s(id) WHERE id > :min AND id < @max AND id <> :min + 1",
+        max = 6i32,
+        min = min,
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(ids, [Some(4), Some(5)]);
+
+    let rows = sqlx::query!(
+        "SELECT id FROM (VALUES (1), (2), (3)) accounts(id) WHERE id IN (:ids)",
+        ids = ..[1i32, 3],
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(rows.len(), 2);
+
+    Ok(())
+}
+
+sqlx::query_file_mod!(mod queries = "tests/test-queries.sql");
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_query_file_mod() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let account: queries::FindAccount = queries::find_account(2).fetch_one(&mut conn).await?;
+
+    assert_eq!(account.id, Some(2));
+    assert_eq!(account.name.as_deref(), Some("Derp Herpinson"));
+
+    let accounts: Vec<Account> = queries::find_accounts_after(0).fetch_all(&mut conn).await?;
+
+    assert_eq!(accounts.len(), 2);
+    assert_eq!(accounts[1].id, 2);
+
+    let greeting = queries::greet("world").fetch_one(&mut conn).await?.greeting;
+
+    assert_eq!(greeting, "Hello, world");
+
+    let mut tx = conn.begin().await?;
+
+    let rows = queries::rename_account("Herp Derpinson", -1)
+        .execute(&mut tx)
+        .await?;
+
+    assert_eq!(rows, 0);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
@@ -3,6 +3,7 @@ extern crate time_ as time;
 use sqlx::decode::Decode;
 use sqlx::encode::Encode;
 use sqlx::postgres::types::raw::{PgNumeric, PgNumericSign, PgRecordDecoder, PgRecordEncoder};
+use sqlx::postgres::types::{PgArray, PgArrayDimension};
 use sqlx::postgres::{PgQueryAs, PgRawBuffer, PgTypeInfo, PgValue};
 use sqlx::{Cursor, Executor, Postgres, Row, Type};
 use sqlx_test::{new, test_prepared_type, test_type};
@@ -23,9 +24,9 @@ macro_rules! array_macro_test {
                     let v: &[$type] = $value;
                     let res = sqlx::query!($sql, v).fetch_one(&mut conn).await?;
 
-                    // these must be unwrapped in query! because postgres infers expressions
-                    // to be potentially NULL at all times even if it's impossible to be NULL
-                    assert_eq!(res.value.unwrap(), v);
+                    // a literal is known to be non-null from the query plan but a bound
+                    // parameter may always be NULL
+                    assert_eq!(res.value, v);
                     assert_eq!(res.out.unwrap(), v);
                 )+
 
@@ -386,6 +387,115 @@ test_type!(string_vec(Postgres, Vec<String>,
         == vec!["Hello, World".to_string(), "".to_string(), "Goodbye".to_string()],
 ));
 
+test_type!(i32_vec_vec(Postgres, Vec<Vec<i32>>,
+    "'{{1,2,3},{4,5,6}}'::int4[]" == vec![vec![1_i32, 2, 3], vec![4, 5, 6]],
+    "'{}'::int4[]" == Vec::<Vec<i32>>::new(),
+));
+
+test_type!(i32_opt_vec_vec(Postgres, Vec<Vec<Option<i32>>>,
+    "'{{1},{NULL}}'::int4[]" == vec![vec![Some(1_i32)], vec![None]],
+));
+
+test_type!(i32_vec_opt_vec(Postgres, Vec<Option<Vec<i32>>>,
+    "'{{1,2},{3,4}}'::int4[]" == vec![Some(vec![1_i32, 2]), Some(vec![3, 4])],
+));
+
+test_type!(string_vec_vec_vec(Postgres, Vec<Vec<Vec<String>>>,
+    "'{{{a,\"b,c\"}},{{\"{d}\",\"\"}}}'::text[]"
+        == vec![
+            vec![vec!["a".to_string(), "b,c".to_string()]],
+            vec![vec!["{d}".to_string(), "".to_string()]],
+        ],
+));
+
+test_type!(bytea_vec(Postgres, Vec<Vec<u8>>,
+    "'{\"\\\\x0102\",\"\\\\x\"}'::bytea[]" == vec![vec![1_u8, 2], vec![]],
+));
+
+test_type!(i32_array(Postgres, PgArray<i32>,
+    "'{1,2,3}'::int4[]" == PgArray::new(vec![1_i32, 2, 3]),
+    "'{}'::int4[]" == PgArray::<i32>::new(vec![]),
+    "'[0:1][-1:1]={{1,2,3},{4,5,6}}'::int4[]" == PgArray::with_dimensions(
+        vec![
+            PgArrayDimension { len: 2, lower_bound: 0 },
+            PgArrayDimension { len: 3, lower_bound: -1 },
+        ],
+        vec![1_i32, 2, 3, 4, 5, 6],
+    ),
+));
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_array_bounds() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let array = PgArray::with_dimensions(
+        vec![
+            PgArrayDimension {
+                len: 2,
+                lower_bound: 5,
+            },
+            PgArrayDimension {
+                len: 2,
+                lower_bound: 1,
+            },
+        ],
+        vec![1_i32, 2, 3, 4],
+    );
+
+    let rec: (String, i32, i32) = sqlx::query_as("SELECT array_dims($1), $1[6][1], $1[5][2]")
+        .bind(&array)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(rec, ("[5:6][1:2]".to_string(), 3, 2));
+    assert_eq!(array.get(&[6, 1]), Some(&3));
+
+    // `Vec` only supports arrays starting at one
+    let res: Result<(Vec<Vec<i32>>,), _> = sqlx::query_as("SELECT $1")
+        .bind(&array)
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    // and of the same number of dimensions
+    let res: Result<(Vec<i32>,), _> = sqlx::query_as("SELECT '{{1,2},{3,4}}'::int4[]")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn test_ragged_array_is_an_error() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let res = sqlx::query("SELECT $1")
+        .bind(vec![vec![1_i32, 2], vec![3]])
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Argument(_))));
+
+    let res = sqlx::query("SELECT $1")
+        .bind(vec![Some(vec![1_i32, 2]), None])
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Argument(_))));
+
+    // the connection is still usable
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
 //
 // These require some annoyingly different tests as anonymous records cannot be read from the
 // database. If someone enterprising comes along and wants to try and just the macro to handle
@@ -705,8 +815,8 @@ mod json {
         .fetch_one(&mut conn)
         .await?;
 
-        assert_eq!(Some(v), res._1);
-        assert_eq!(res._1, res._2);
+        assert_eq!(v, res._1);
+        assert_eq!(Some(res._1), res._2);
 
         Ok(())
     }
@@ -1,6 +1,6 @@
 use futures::TryStreamExt;
 use sqlx::postgres::{PgPool, PgQueryAs, PgRow};
-use sqlx::{Connection, Cursor, Executor, Postgres, Row};
+use sqlx::{Connection, Cursor, Executor, Postgres, QueryBuilder, Row};
 use sqlx_test::new;
 use std::time::Duration;
 
@@ -323,6 +323,182 @@ async fn test_invalid_query() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_query_as_tuples() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let (id, name): (i32, String) = sqlx::query_as("SELECT 1, 'Herp Derpinson'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 1);
+    assert_eq!(name, "Herp Derpinson");
+
+    let row: (
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        i32,
+        String,
+    ) = sqlx::query_as("SELECT 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 'sixteen'")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(row.0, 1);
+    assert_eq!(row.14, 15);
+    assert_eq!(row.15, "sixteen");
+
+    // more elements than columns is an error
+    let res: sqlx::Result<(i32, i32)> = sqlx::query_as("SELECT 1").fetch_one(&mut conn).await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_query_scalar() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT count(*) FROM generate_series(1, $1)")
+        .bind(5_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 5);
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT * FROM generate_series(1, 3)")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(ids, vec![1, 2, 3]);
+
+    let name: Option<Option<String>> = sqlx::query_scalar("SELECT NULL::text")
+        .fetch_optional(&mut conn)
+        .await?;
+
+    assert_eq!(name, Some(None));
+
+    // the value is checked against the type of the column
+    let res = sqlx::query_scalar::<_, String>("SELECT 1::int4")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_bind_named_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let ids: Vec<i32> = sqlx::query_scalar(
+        "SELECT id FROM generate_series(1, 10) AS s(id) WHERE id > :min AND id < @max AND id <> :min + 1",
+    )
+    .bind_named("max", 6_i32)
+    .bind_named("min", 2_i32)
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(ids, vec![4, 5]);
+
+    let (text,): (String,) = sqlx::query_as("SELECT ':not_a_param' || :value::text")
+        .bind_named("value", "!")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(text, ":not_a_param!");
+
+    // the bounds of an array slice are not named parameters
+    let (slice,): (Vec<i32>,) = sqlx::query_as(
+        "SELECT (ARRAY[1, 2, 3, 4])[lower:upper] FROM (SELECT :lower AS lower, 3 AS upper) AS t",
+    )
+    .bind_named("lower", 2_i32)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(slice, vec![2, 3]);
+
+    let res = sqlx::query("SELECT :a::int4")
+        .bind_named("b", 1_i32)
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Argument(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_build_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut builder =
+        QueryBuilder::<Postgres>::new("SELECT id FROM generate_series(1, 10) AS s(id)");
+
+    builder.push(" WHERE id > ").push_bind(2_i32);
+    builder.push(" AND id IN (");
+
+    let mut separated = builder.separated(", ");
+
+    for id in &[1_i32, 3, 5, 7] {
+        separated.push_bind(*id);
+    }
+
+    builder.push(")");
+
+    assert_eq!(
+        builder.sql(),
+        "SELECT id FROM generate_series(1, 10) AS s(id) WHERE id > $1 AND id IN ($2, $3, $4, $5)"
+    );
+
+    let ids: Vec<i32> = builder.build_query_scalar().fetch_all(&mut conn).await?;
+
+    assert_eq!(ids, vec![3, 5, 7]);
+
+    builder.reset();
+    builder.push(" ORDER BY id DESC LIMIT ").push_bind(1_i64);
+
+    let (id,): (i32,) = builder.build_query_as().fetch_one(&mut conn).await?;
+
+    assert_eq!(id, 10);
+
+    let mut builder = QueryBuilder::<Postgres>::new("SELECT * FROM (");
+
+    builder.push_values(&[(1_i32, "foo"), (2, "bar")], |mut b, (id, name)| {
+        b.push_bind(*id).push_bind(*name);
+    });
+
+    builder.push(") AS t(id, name)");
+
+    assert_eq!(
+        builder.sql(),
+        "SELECT * FROM (VALUES ($1, $2), ($3, $4)) AS t(id, name)"
+    );
+
+    let rows: Vec<(i32, String)> = builder.build_query_as().fetch_all(&mut conn).await?;
+
+    assert_eq!(rows, vec![(1, "foo".to_owned()), (2, "bar".to_owned())]);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn test_describe() -> anyhow::Result<()> {
@@ -371,7 +547,8 @@ async fn test_describe() -> anyhow::Result<()> {
             .to_string(),
         "BYTEA"
     );
-    assert_eq!(describe.result_columns[3].non_null, None);
+    // a literal is inferred to be non-null from the query plan
+    assert_eq!(describe.result_columns[3].non_null, Some(true));
     assert_eq!(
         describe.result_columns[3]
             .type_info
@@ -383,3 +560,76 @@ async fn test_describe() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_does_not_infer_non_null_through_set_operations() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    for query in &[
+        "SELECT 1 AS x UNION SELECT NULL::int",
+        "SELECT x FROM (SELECT 1 AS x UNION ALL SELECT NULL::int) s",
+        "SELECT * FROM (SELECT 1 AS x UNION SELECT NULL::int) s",
+        "WITH t AS MATERIALIZED (SELECT 1 AS x UNION SELECT NULL::int) SELECT x FROM t",
+    ] {
+        let describe = conn.describe(*query).await?;
+
+        assert_ne!(describe.result_columns[0].non_null, Some(true), "{}", query);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_describes_in_a_transaction_without_aborting_it() -> anyhow::Result<()> {
+    let mut tx = new::<Postgres>().await?.begin().await?;
+
+    // `SHOW` cannot be explained and planning `1 / 0` fails
+    tx.describe("SHOW search_path").await?;
+    tx.describe("SELECT 1 / 0 AS x").await?;
+
+    let (value,): (i32,) = sqlx::query_as("SELECT 1").fetch_one(&mut tx).await?;
+
+    assert_eq!(value, 1);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_evicts_and_clears_cached_statements() -> anyhow::Result<()> {
+    use sqlx::{Connect, PgConnection};
+
+    let url = dotenv::var("DATABASE_URL")?;
+    let separator = if url.contains('?') { '&' } else { '?' };
+
+    let mut conn =
+        PgConnection::connect(&*format!("{}{}statement_cache_capacity=2", url, separator)).await?;
+
+    for _ in 0..2 {
+        for i in 0..3_i32 {
+            let (value,): (i32,) = sqlx::query_as(&format!("SELECT $1 + {}", i))
+                .bind(1_i32)
+                .fetch_one(&mut conn)
+                .await?;
+
+            assert_eq!(value, 1 + i);
+        }
+    }
+
+    let count = "SELECT COUNT(*) FROM pg_prepared_statements";
+
+    // the least recently used statements were closed to make room for this one
+    let (prepared,): (i64,) = sqlx::query_as(count).fetch_one(&mut conn).await?;
+    assert_eq!(prepared, 2);
+
+    conn.clear_cached_statements().await?;
+
+    let (prepared,): (i64,) = sqlx::query_as(count).fetch_one(&mut conn).await?;
+    assert_eq!(prepared, 1);
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use sqlx::Connection;
 use sqlx::Sqlite;
 use sqlx_test::new;
 
@@ -75,3 +76,175 @@ async fn macro_select_from_view() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_select_column_override() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = sqlx::query!(
+        r#"select id as "id: i64", name as "name?", is_active as "is_active: bool", upper(name) as "upper!: String"
+        from accounts where id = 1"#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!(Some("Herp Derpinson"), account.name.as_deref());
+    assert_eq!(None, account.is_active);
+    assert_eq!("HERP DERPINSON", account.upper);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_select_scalar() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let name: String = sqlx::query_scalar!("select name from accounts where id = ?", 1i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(name, "Herp Derpinson");
+
+    let count: i32 = sqlx::query_scalar!(r#"select count(*) as "count!: i32" from accounts"#)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(count > 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_select_list_args() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let ids = vec![1i32, 2, 3];
+
+    let names: Vec<String> =
+        sqlx::query_scalar!("select name from accounts where id in (?)", ..ids)
+            .fetch_all(&mut conn)
+            .await?;
+
+    assert_eq!(names, ["Herp Derpinson"]);
+
+    // an empty list matches nothing
+    let count: i32 = sqlx::query_scalar!(
+        r#"select count(*) as "count!: i32" from accounts where id in (?)"#,
+        ..Vec::<i32>::new()
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_insert_list_args() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let mut tx = conn.begin().await?;
+
+    let names = ["Derp Herpinson", "Herp Herpinson"];
+    let scores = [1.5f64, 2.5];
+
+    sqlx::query!(
+        "insert into accounts (name, score, is_active) values (?, ?, ?)",
+        ..names,
+        ..scores,
+        true
+    )
+    .execute(&mut tx)
+    .await?;
+
+    let count: i32 = sqlx::query_scalar!(
+        r#"select count(*) as "count!: i32" from accounts where is_active and name in (?)"#,
+        ..names
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    assert_eq!(count, 2);
+
+    tx.rollback().await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_insert_list_args_of_different_lengths() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let names = ["Derp Herpinson", "Herp Herpinson"];
+    let scores = [1.5f64];
+
+    let res = sqlx::query!(
+        "insert into accounts (name, score, is_active) values (?, ?, ?)",
+        ..names,
+        ..scores,
+        true
+    )
+    .execute(&mut conn)
+    .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Argument(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_select_named_args() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = sqlx::query!(
+        "select id, name from accounts where id = :id and (name = :name or :name is null)",
+        name = "Herp Derpinson",
+        id = 1i32,
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(1, account.id);
+    assert_eq!("Herp Derpinson", account.name);
+
+    let names: Vec<String> = sqlx::query_scalar!(
+        "select name from accounts where id in (@ids) and id <> @excluded",
+        excluded = 2i32,
+        ids = ..[1i32, 2],
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(names, ["Herp Derpinson"]);
+
+    Ok(())
+}
+
+sqlx::query_file_mod!(mod queries = "tests/test-queries-sqlite.sql");
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn macro_query_file_mod() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let account = queries::find_account(1).fetch_one(&mut conn).await?;
+
+    assert_eq!(account.id, 1);
+    assert_eq!(account.name, "Herp Derpinson");
+
+    let count = queries::count_named("Herp Derpinson")
+        .fetch_one(&mut conn)
+        .await?
+        .count;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
@@ -1,5 +1,8 @@
+extern crate time_ as time;
+
+use sqlx::sqlite::SqliteQueryAs;
 use sqlx::Sqlite;
-use sqlx_test::test_type;
+use sqlx_test::{new, test_type};
 
 test_type!(null(
     Sqlite,
@@ -44,3 +47,211 @@ test_type!(bytes(
     "X'0000000052'"
         == vec![0_u8, 0, 0, 0, 0x52]
 ));
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use super::*;
+    use sqlx::types::chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+    test_type!(chrono_date(
+        Sqlite,
+        NaiveDate,
+        "'2001-01-05'" == NaiveDate::from_ymd(2001, 1, 5),
+        "'2050-11-23'" == NaiveDate::from_ymd(2050, 11, 23)
+    ));
+
+    test_type!(chrono_time(
+        Sqlite,
+        NaiveTime,
+        "'05:10:20.115'" == NaiveTime::from_hms_milli(5, 10, 20, 115)
+    ));
+
+    test_type!(chrono_date_time(
+        Sqlite,
+        NaiveDateTime,
+        "'2019-01-02 05:10:20'" == NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20),
+        "'2019-01-02 05:10:20.115'"
+            == NaiveDate::from_ymd(2019, 1, 2).and_hms_milli(5, 10, 20, 115)
+    ));
+
+    test_type!(chrono_date_time_tz(
+        Sqlite,
+        DateTime::<Utc>,
+        "'2019-01-02 05:10:20.115+00:00'"
+            == DateTime::<Utc>::from_utc(
+                NaiveDate::from_ymd(2019, 1, 2).and_hms_milli(5, 10, 20, 115),
+                Utc,
+            )
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn test_chrono_storage_classes() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        let expected = NaiveDate::from_ymd(2019, 1, 2).and_hms_milli(5, 10, 20, 115);
+
+        // INTEGER as Unix time, REAL as a Julian day number and TEXT with an offset
+        let (unix, julian, text): (NaiveDateTime, DateTime<Utc>, NaiveDateTime) = sqlx::query_as(
+            "SELECT 1546405820, julianday('2019-01-02 05:10:20.115'), '2019-01-02T07:10:20.115+02:00'",
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+        assert_eq!(unix, expected.date().and_hms(5, 10, 20));
+        assert_eq!(julian.naive_utc(), expected);
+        assert_eq!(text, expected);
+
+        // The encoded text is understood by the SQLite date and time functions
+        let (datetime,): (String,) = sqlx::query_as("SELECT datetime(?)")
+            .bind(DateTime::<FixedOffset>::from_utc(
+                expected,
+                FixedOffset::east(2 * 3600),
+            ))
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(datetime, "2019-01-02 05:10:20");
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_tests {
+    use super::*;
+    use sqlx::types::time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+    use time::{date, time};
+
+    test_type!(time_date(
+        Sqlite,
+        Date,
+        "'2001-01-05'" == date!(2001 - 1 - 5),
+        "'2050-11-23'" == date!(2050 - 11 - 23)
+    ));
+
+    test_type!(time_time(
+        Sqlite,
+        Time,
+        "'05:10:20.115'" == time!(5:10:20.115)
+    ));
+
+    test_type!(time_date_time(
+        Sqlite,
+        PrimitiveDateTime,
+        "'2019-01-02 05:10:20'" == date!(2019 - 1 - 2).with_time(time!(5:10:20)),
+        "'2019-01-02 05:10:20.115'" == date!(2019 - 1 - 2).with_time(time!(5:10:20.115))
+    ));
+
+    test_type!(time_date_time_tz(
+        Sqlite,
+        OffsetDateTime,
+        "'2019-01-02 05:10:20.115+02:00'"
+            == date!(2019 - 1 - 2)
+                .with_time(time!(3:10:20.115))
+                .assume_utc()
+                .to_offset(UtcOffset::hours(2))
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn test_time_storage_classes() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        let (unix, julian): (OffsetDateTime, PrimitiveDateTime) =
+            sqlx::query_as("SELECT 1546405820, julianday('2019-01-02 05:10:20.115')")
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(
+            unix,
+            date!(2019 - 1 - 2).with_time(time!(5:10:20)).assume_utc()
+        );
+        assert_eq!(julian, date!(2019 - 1 - 2).with_time(time!(5:10:20.115)));
+
+        // the first and last second of the dates of `time` can be decoded
+        let (min, max): (OffsetDateTime, OffsetDateTime) =
+            sqlx::query_as("SELECT -377705116800, 253402300799")
+                .fetch_one(&mut conn)
+                .await?;
+
+        assert_eq!(min, date!(-9999 - 1 - 1).midnight().assume_utc());
+        assert_eq!(
+            max,
+            date!(9999 - 12 - 31)
+                .with_time(time!(23:59:59))
+                .assume_utc()
+        );
+
+        // a Unix time out of range is an error instead of a panic
+        for unix in &[9223372036854775807_i64, 253402300800, -377705116801] {
+            let result = sqlx::query_as::<_, (OffsetDateTime,)>("SELECT ?")
+                .bind(unix)
+                .fetch_one(&mut conn)
+                .await;
+
+            assert!(matches!(result, Err(sqlx::Error::Decode(_))), "{}", unix);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "uuid")]
+mod uuid {
+    use super::*;
+    use sqlx::types::Uuid;
+
+    test_type!(uuid(
+        Sqlite,
+        Uuid,
+        "x'b731678f636f4135bc6f19440c13bd19'"
+            == Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19").unwrap(),
+        "x'00000000000000000000000000000000'"
+            == Uuid::parse_str("00000000-0000-0000-0000-000000000000").unwrap()
+    ));
+
+    #[cfg_attr(feature = "runtime-async-std", async_std::test)]
+    #[cfg_attr(feature = "runtime-tokio", tokio::test)]
+    async fn test_uuid_from_text() -> anyhow::Result<()> {
+        let mut conn = new::<Sqlite>().await?;
+
+        let (uuid,): (Uuid,) = sqlx::query_as("SELECT 'b731678f-636f-4135-bc6f-19440c13bd19'")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(
+            uuid,
+            Uuid::parse_str("b731678f-636f-4135-bc6f-19440c13bd19")?
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use super::*;
+    use serde_json::{json, Value as JsonValue};
+    use sqlx::types::Json;
+
+    test_type!(json(
+        Sqlite,
+        JsonValue,
+        "'\"Hello, World\"'" == json!("Hello, World"),
+        "'\"😎\"'" == json!("😎"),
+        "'[\"Hello\",\"World!\"]'" == json!(["Hello", "World!"])
+    ));
+
+    #[derive(serde::Deserialize, serde::Serialize, Debug, PartialEq)]
+    struct Friend {
+        name: String,
+        age: u32,
+    }
+
+    test_type!(json_struct(
+        Sqlite,
+        Json<Friend>,
+        "'{\"name\":\"Joe\",\"age\":33}'" == Json(Friend { name: "Joe".to_string(), age: 33 })
+    ));
+}
@@ -1,6 +1,10 @@
 use futures::TryStreamExt;
-use sqlx::{sqlite::SqliteQueryAs, Connect, Connection, Executor, Sqlite, SqliteConnection};
+use sqlx::{
+    sqlite::{SqliteAggregate, SqliteEvent, SqliteFunctionArgs, SqliteOperation, SqliteQueryAs},
+    Connect, Connection, Executor, QueryBuilder, Sqlite, SqliteConnection, SqlitePool,
+};
 use sqlx_test::new;
+use std::time::Duration;
 
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
@@ -114,6 +118,70 @@ SELECT id, other FROM users WHERE id = last_insert_rowid();
     Ok(())
 }
 
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_bind_named_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    let (sum, text): (i32, String) = sqlx::query_as("SELECT :a + @b + :a, $text")
+        .bind_named("text", "hello")
+        .bind_named("b", 2_i32)
+        .bind_named("a", 1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(sum, 4);
+    assert_eq!(text, "hello");
+
+    let res = sqlx::query("SELECT :a")
+        .bind_named("b", 1_i32)
+        .execute(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::Argument(_))));
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_can_build_queries() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let mut builder = QueryBuilder::<Sqlite>::new("INSERT INTO users (id, name) ");
+
+    builder.push_values(
+        vec![(1_i32, "foo"), (2, "bar"), (3, "baz")],
+        |mut b, (id, name)| {
+            b.push_bind(id).push_bind(name);
+        },
+    );
+
+    assert_eq!(
+        builder.sql(),
+        "INSERT INTO users (id, name) VALUES (?, ?), (?, ?), (?, ?)"
+    );
+
+    assert_eq!(builder.build().execute(&mut conn).await?, 3);
+
+    let mut builder = QueryBuilder::<Sqlite>::new("SELECT name FROM users WHERE ");
+    let mut separated = builder.separated(" OR ");
+
+    separated.push("id = ").push_bind_unseparated(1_i32);
+    separated.push("name = ").push_bind_unseparated("baz");
+
+    builder.push(" ORDER BY id");
+
+    let names: Vec<String> = builder.build_query_scalar().fetch_all(&mut conn).await?;
+
+    assert_eq!(names, vec!["foo", "baz"]);
+
+    Ok(())
+}
+
 #[cfg_attr(feature = "runtime-async-std", async_std::test)]
 #[cfg_attr(feature = "runtime-tokio", tokio::test)]
 async fn it_describes() -> anyhow::Result<()> {
@@ -210,3 +278,757 @@ CREATE TEMPORARY TABLE describe_test (
 
     Ok(())
 }
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_opens_read_only() -> anyhow::Result<()> {
+    let mut conn =
+        SqliteConnection::connect("sqlite://tests/fixtures/sqlite.sqlite?mode=ro").await?;
+
+    let count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM accounts")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(count.0 > 0);
+
+    let res = conn
+        .execute("INSERT INTO accounts (name, is_active) VALUES ('Herp Derp', TRUE)")
+        .await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_opens_named_in_memory_databases() -> anyhow::Result<()> {
+    let mut conn1 =
+        SqliteConnection::connect("sqlite://it_opens_named_in_memory?mode=memory").await?;
+    let mut conn2 =
+        SqliteConnection::connect("sqlite://it_opens_named_in_memory?mode=memory").await?;
+
+    conn1
+        .execute("CREATE TABLE shared (id INTEGER PRIMARY KEY); INSERT INTO shared VALUES (1)")
+        .await?;
+
+    let id: (i32,) = sqlx::query_as("SELECT id FROM shared")
+        .fetch_one(&mut conn2)
+        .await?;
+
+    assert_eq!(id.0, 1);
+
+    // a private in-memory database is not shared
+    let mut conn3 =
+        SqliteConnection::connect("sqlite://it_opens_named_in_memory?mode=memory&cache=private")
+            .await?;
+
+    assert!(conn3.execute("SELECT id FROM shared").await.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fails_to_connect_with_invalid_options() -> anyhow::Result<()> {
+    assert!(SqliteConnection::connect("sqlite://data.db?mode=readonly")
+        .await
+        .is_err());
+
+    // a NUL byte in the path is an error instead of a panic
+    assert!(SqliteConnection::connect("sqlite://foo%00bar")
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_sets_pragmas_on_connect() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:?foreign_keys=true").await?;
+
+    let (foreign_keys,): (i32,) = sqlx::query_as("PRAGMA foreign_keys")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let (busy_timeout,): (i32,) = sqlx::query_as("PRAGMA busy_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(foreign_keys, 1);
+    assert_eq!(busy_timeout, 5000);
+
+    conn.execute(
+        r#"
+CREATE TABLE parents (id INTEGER PRIMARY KEY);
+CREATE TABLE children (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES parents (id));
+        "#,
+    )
+    .await?;
+
+    assert!(conn
+        .execute("INSERT INTO children (parent_id) VALUES (1)")
+        .await
+        .is_err());
+
+    let mut conn =
+        SqliteConnection::connect("sqlite::memory:?foreign_keys=false&busy_timeout=250").await?;
+
+    let (foreign_keys,): (i32,) = sqlx::query_as("PRAGMA foreign_keys")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let (busy_timeout,): (i32,) = sqlx::query_as("PRAGMA busy_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(foreign_keys, 0);
+    assert_eq!(busy_timeout, 250);
+
+    // the journal mode of a database file is left as it is unless it is given
+    let path = std::env::temp_dir().join(format!("sqlx-journal-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut conn = SqliteConnection::connect(&*format!("sqlite://{}", path.display())).await?;
+
+    let (journal_mode,): (String,) = sqlx::query_as("PRAGMA journal_mode")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(journal_mode, "delete");
+
+    drop(conn);
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_calls_user_defined_functions() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_function("add_one", 1, true, |args| Ok(args.try_get::<i64>(0)? + 1))
+        .await?;
+
+    conn.create_function("greet", -1, false, |args| {
+        if args.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(format!("Hello, {}!", args.try_get::<String>(0)?)))
+    })
+    .await?;
+
+    let (value, greeting, none): (i64, String, Option<String>) =
+        sqlx::query_as("SELECT add_one(41), greet(?), greet()")
+            .bind("world")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(value, 42);
+    assert_eq!(greeting, "Hello, world!");
+    assert_eq!(none, None);
+
+    conn.create_function("checked_sqrt", 1, true, |args| {
+        let value = args.try_get::<f64>(0)?;
+
+        if value < 0.0 {
+            return Err(sqlx::Error::Decode(
+                "cannot take the root of a negative number".into(),
+            ));
+        }
+
+        Ok(value.sqrt())
+    })
+    .await?;
+
+    // an error of the function is an error of the statement
+    let err = sqlx::query_as::<_, (f64,)>("SELECT checked_sqrt(-1.0)")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert_eq!(err.to_string(), "cannot take the root of a negative number");
+
+    Ok(())
+}
+
+struct Product(i64);
+
+impl SqliteAggregate for Product {
+    type Output = i64;
+
+    fn step(&mut self, args: &SqliteFunctionArgs<'_>) -> sqlx::Result<()> {
+        self.0 *= args.try_get::<i64>(0)?;
+
+        Ok(())
+    }
+
+    fn finalize(self) -> sqlx::Result<i64> {
+        Ok(self.0)
+    }
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_calls_user_defined_aggregates_and_collations() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.create_aggregate("product", 1, true, || Product(1))
+        .await?;
+
+    conn.create_collation("reverse", |a, b| b.cmp(a)).await?;
+
+    let (product,): (i64,) = sqlx::query_as(
+        "SELECT product(value) FROM (SELECT 2 AS value UNION ALL SELECT 3 UNION ALL SELECT 7)",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(product, 42);
+
+    // an aggregate of no rows is the initial value
+    let (product,): (i64,) = sqlx::query_as("SELECT product(1) WHERE FALSE")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(product, 1);
+
+    let names: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM (SELECT 'a' AS name UNION ALL SELECT 'c' UNION ALL SELECT 'b') ORDER BY name COLLATE reverse",
+    )
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(
+        names,
+        vec![("c".to_owned(),), ("b".to_owned(),), ("a".to_owned(),)]
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_runs_after_connect_on_pool_connections() -> anyhow::Result<()> {
+    let pool = SqlitePool::builder()
+        .max_size(2)
+        .after_connect(|conn| {
+            Box::pin(async move {
+                conn.create_function("add_one", 1, true, |args| Ok(args.try_get::<i64>(0)? + 1))
+                    .await
+            })
+        })
+        .build(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn1 = pool.acquire().await?;
+    let mut conn2 = pool.acquire().await?;
+
+    for conn in vec![&mut conn1, &mut conn2] {
+        let (value,): (i64,) = sqlx::query_as("SELECT add_one(1)").fetch_one(conn).await?;
+
+        assert_eq!(value, 2);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_backs_up_to_a_file() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("sqlx-backup-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut conn = new::<Sqlite>().await?;
+    let mut steps = Vec::new();
+
+    conn.backup_to(path.as_path())
+        .pages_per_step(1)
+        .progress(|progress| steps.push(progress))
+        .run()
+        .await?;
+
+    assert!(!steps.is_empty());
+    assert_eq!(steps.last().unwrap().remaining, 0);
+
+    let (expected,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM accounts")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let mut copy = SqliteConnection::connect(&*format!("sqlite://{}", path.display())).await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM accounts")
+        .fetch_one(&mut copy)
+        .await?;
+
+    assert_eq!(count, expected);
+
+    drop(copy);
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_backs_up_to_a_connection() -> anyhow::Result<()> {
+    let mut source = SqliteConnection::connect("sqlite::memory:").await?;
+
+    source
+        .execute("CREATE TABLE items (id INTEGER PRIMARY KEY); INSERT INTO items VALUES (1), (2)")
+        .await?;
+
+    let mut target = SqliteConnection::connect("sqlite::memory:").await?;
+
+    source.backup_to(&mut target).run().await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM items")
+        .fetch_one(&mut target)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_uses_both_connections_after_a_backup_is_dropped() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::task::sleep;
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::time::delay_for as sleep;
+
+    let path = std::env::temp_dir().join(format!("sqlx-backup-drop-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let url = format!("sqlite://{}?busy_timeout=0", path.display());
+    let mut source = SqliteConnection::connect(&*url).await?;
+
+    source
+        .execute("CREATE TABLE items (id INTEGER PRIMARY KEY); INSERT INTO items VALUES (1), (2)")
+        .await?;
+
+    // another connection locks the source database, so the backup waits between its steps
+    let mut locker = SqliteConnection::connect(&*url).await?;
+    locker.execute("BEGIN EXCLUSIVE").await?;
+
+    let mut target = SqliteConnection::connect("sqlite::memory:").await?;
+
+    {
+        let mut backup = Box::pin(
+            source
+                .backup_to(&mut target)
+                .busy_delay(Duration::from_secs(3600))
+                .run(),
+        );
+
+        // the backup starts and then waits for the lock
+        for _ in 0..10 {
+            assert!(futures::poll!(&mut backup).is_pending());
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        // and is dropped without waiting for it to finish
+    }
+
+    locker.execute("ROLLBACK").await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM items")
+        .fetch_one(&mut source)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    target.execute("CREATE TABLE other (id INTEGER)").await?;
+
+    drop(source);
+    drop(locker);
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_vacuums_into_a_file() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join(format!("sqlx-vacuum-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut conn = new::<Sqlite>().await?;
+
+    conn.vacuum_into(&path).await?;
+
+    let mut copy =
+        SqliteConnection::connect(&*format!("sqlite://{}?mode=ro", path.display())).await?;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM accounts")
+        .fetch_one(&mut copy)
+        .await?;
+
+    assert!(count > 0);
+
+    // the file must not exist
+    assert!(conn.vacuum_into(&path).await.is_err());
+
+    drop(copy);
+    std::fs::remove_file(&path)?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_reads_and_writes_blobs_incrementally() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-async-std")]
+    use futures::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+    use std::io::SeekFrom;
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute("CREATE TABLE files (id INTEGER PRIMARY KEY, data BLOB NOT NULL)")
+        .await?;
+
+    conn.execute("INSERT INTO files (id, data) VALUES (1, zeroblob(100000))")
+        .await?;
+
+    let data: Vec<u8> = (0..100_000).map(|i| (i % 251) as u8).collect();
+
+    {
+        let mut blob = conn.open_blob("files", "data", 1, true).await?;
+
+        assert_eq!(blob.len(), 100_000);
+
+        blob.write_all(&data).await?;
+
+        // a BLOB cannot grow
+        assert_eq!(blob.write(b"more").await?, 0);
+
+        blob.seek(SeekFrom::Start(0)).await?;
+
+        let mut read = Vec::new();
+        blob.read_to_end(&mut read).await?;
+
+        assert!(read == data);
+
+        assert_eq!(blob.seek(SeekFrom::End(-10)).await?, 99_990);
+
+        let mut tail = [0; 10];
+        blob.read_exact(&mut tail).await?;
+
+        assert_eq!(&tail[..], &data[99_990..]);
+        assert!(blob.seek(SeekFrom::Current(-100_001)).await.is_err());
+    }
+
+    let (stored,): (Vec<u8>,) = sqlx::query_as("SELECT data FROM files WHERE id = 1")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(stored == data);
+
+    // a read-only BLOB cannot be written to
+    let mut blob = conn.open_blob("files", "data", 1, false).await?;
+
+    assert!(blob.write_all(b"data").await.is_err());
+    drop(blob);
+
+    assert!(conn.open_blob("files", "data", 2, false).await.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_runs_a_query_after_a_blob_is_dropped_mid_read() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-async-std")]
+    use futures::AsyncReadExt;
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::io::AsyncReadExt;
+
+    use futures::FutureExt;
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute("CREATE TABLE files (id INTEGER PRIMARY KEY, data BLOB NOT NULL)")
+        .await?;
+
+    conn.execute("INSERT INTO files (id, data) VALUES (1, zeroblob(1000000))")
+        .await?;
+
+    for _ in 0..10 {
+        let mut blob = conn.open_blob("files", "data", 1, false).await?;
+        let mut buf = vec![0; 1_000_000];
+
+        // the read is queued on the worker thread and is still pending when the blob is dropped
+        let _ = blob.read(&mut buf).now_or_never();
+        drop(blob);
+
+        let (len,): (i32,) = sqlx::query_as("SELECT length(data) FROM files WHERE id = 1")
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(len, 1_000_000);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_streams_update_commit_and_rollback_events() -> anyhow::Result<()> {
+    use futures::StreamExt;
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let mut events = conn.events().await;
+
+    conn.execute("INSERT INTO items (id, name) VALUES (1, 'one')")
+        .await?;
+
+    conn.execute("BEGIN").await?;
+    conn.execute("UPDATE items SET name = 'uno' WHERE id = 1")
+        .await?;
+    conn.execute("DELETE FROM items WHERE id = 1").await?;
+    conn.execute("ROLLBACK").await?;
+
+    let update = |operation| SqliteEvent::Update {
+        operation,
+        database: "main".into(),
+        table: "items".into(),
+        row_id: 1,
+    };
+
+    assert_eq!(events.next().await, Some(update(SqliteOperation::Insert)));
+    assert_eq!(events.next().await, Some(SqliteEvent::Commit));
+    assert_eq!(events.next().await, Some(update(SqliteOperation::Update)));
+    assert_eq!(events.next().await, Some(update(SqliteOperation::Delete)));
+    assert_eq!(events.next().await, Some(SqliteEvent::Rollback));
+
+    // every stream receives the events
+    let mut other = conn.events().await;
+
+    conn.execute("INSERT INTO items (id, name) VALUES (2, 'two')")
+        .await?;
+
+    assert!(matches!(
+        events.next().await,
+        Some(SqliteEvent::Update { row_id: 2, .. })
+    ));
+    assert!(matches!(
+        other.next().await,
+        Some(SqliteEvent::Update { row_id: 2, .. })
+    ));
+
+    // the streams end when the connection is dropped
+    drop(conn);
+
+    assert_eq!(events.next().await, Some(SqliteEvent::Commit));
+    assert_eq!(events.next().await, None);
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_fails_to_load_a_missing_extension() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await?;
+
+    let err = conn
+        .load_extension("sqlx_missing_extension", None)
+        .await
+        .unwrap_err();
+
+    assert!(
+        err.to_string().contains("sqlx_missing_extension"),
+        "{}",
+        err
+    );
+
+    // loading extensions from SQL stays disabled
+    assert!(conn
+        .execute("SELECT load_extension('sqlx_missing_extension')")
+        .await
+        .is_err());
+
+    assert!(
+        SqliteConnection::connect("sqlite::memory:?extension=sqlx_missing_extension")
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_evicts_and_clears_cached_statements() -> anyhow::Result<()> {
+    let mut conn = SqliteConnection::connect("sqlite::memory:?statement_cache_capacity=2").await?;
+
+    for _ in 0..2 {
+        for i in 0..3_i32 {
+            let (value,): (i32,) = sqlx::query_as(&format!("SELECT ? + {}", i))
+                .bind(1_i32)
+                .fetch_one(&mut conn)
+                .await?;
+
+            assert_eq!(value, 1 + i);
+        }
+    }
+
+    // a query of several statements is prepared one statement at a time
+    for _ in 0..3 {
+        let (value,): (i32,) = sqlx::query_as("SELECT ?; SELECT ?;  ")
+            .bind(5_i32)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, 5);
+    }
+
+    conn.clear_cached_statements().await?;
+
+    let (value,): (i32,) = sqlx::query_as("SELECT ? + 1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 2);
+
+    assert!(
+        SqliteConnection::connect("sqlite::memory:?statement_cache_capacity=0")
+            .await
+            .is_err()
+    );
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_runs_connections_on_a_shared_worker_pool() -> anyhow::Result<()> {
+    let mut conns = Vec::new();
+
+    for _ in 0..16 {
+        conns.push(
+            SqliteConnection::connect("sqlite::memory:?worker=shared&worker_threads=2").await?,
+        );
+    }
+
+    // the statements of each connection still run in order
+    let results =
+        futures::future::try_join_all(conns.iter_mut().enumerate().map(|(i, conn)| async move {
+            conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY, value INTEGER NOT NULL)")
+                .await?;
+
+            for value in 0..50_i64 {
+                sqlx::query("INSERT INTO items (value) VALUES (?)")
+                    .bind(value * i as i64)
+                    .execute(&mut *conn)
+                    .await?;
+            }
+
+            let (sum,): (i64,) = sqlx::query_as("SELECT SUM(value) FROM items")
+                .fetch_one(&mut *conn)
+                .await?;
+
+            Ok::<_, sqlx::Error>((i as i64, sum))
+        }))
+        .await?;
+
+    for (i, sum) in results {
+        assert_eq!(sum, (0..50).sum::<i64>() * i);
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_keeps_a_shared_in_memory_database_alive_in_a_pool() -> anyhow::Result<()> {
+    #[cfg(feature = "runtime-tokio")]
+    use tokio::time::delay_for as sleep;
+
+    #[cfg(feature = "runtime-async-std")]
+    use async_std::task::sleep;
+
+    let url = "sqlite:file:it_keeps_alive?mode=memory&cache=shared";
+
+    let pool = SqlitePool::builder()
+        .max_size(2)
+        .max_lifetime(Duration::from_millis(10))
+        .idle_timeout(Duration::from_millis(10))
+        .build(url)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    conn.execute("CREATE TABLE items (id INTEGER PRIMARY KEY); INSERT INTO items VALUES (1)")
+        .await?;
+
+    drop(conn);
+
+    // the connection that created the table is reaped and replaced
+    sleep(Duration::from_millis(50)).await;
+
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM items")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    // the database is destroyed once the pool is closed
+    pool.close().await;
+
+    let mut conn = SqliteConnection::connect(url).await?;
+
+    assert!(conn.execute("SELECT id FROM items").await.is_err());
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "runtime-async-std", async_std::test)]
+#[cfg_attr(feature = "runtime-tokio", tokio::test)]
+async fn it_does_not_count_the_keep_alive_connection_in_the_pool_size() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let connects = Arc::new(AtomicUsize::new(0));
+    let after_connect = connects.clone();
+
+    let pool = SqlitePool::builder()
+        .max_size(2)
+        .after_connect(move |_| {
+            after_connect.fetch_add(1, Ordering::SeqCst);
+
+            Box::pin(async { Ok(()) })
+        })
+        .build("sqlite://it_counts_the_pool_size?mode=memory")
+        .await?;
+
+    assert_eq!(pool.size(), 0);
+    assert_eq!(pool.max_size(), 2);
+
+    let conn1 = pool.acquire().await?;
+    let conn2 = pool.acquire().await?;
+
+    // the keep-alive connection is not one of the `max_size` connections
+    assert_eq!(pool.size(), 2);
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+    assert!(pool.try_acquire().is_none());
+
+    drop(conn1);
+    drop(conn2);
+
+    pool.close().await;
+
+    Ok(())
+}
@@ -0,0 +1,8 @@
+-- name: find_account
+-- Find an account by its id.
+-- param: id: i32 - the id of the account
+select id, name from accounts where id = :id;
+
+-- name: count_named
+-- param: name: &str
+select count(*) as "count!: i32" from accounts where name = :name or name = upper(:name);
@@ -0,0 +1,17 @@
+-- name: find_account
+-- Find an account by its id.
+-- param: id - the id of the account
+SELECT * FROM (VALUES (1, 'Herp Derpinson'), (2, 'Derp Herpinson')) accounts(id, name)
+WHERE id = :id;
+
+-- name: find_accounts_after -> super::Account
+-- param: min_id: i32
+SELECT id AS "id!", name FROM (VALUES (1, 'Herp Derpinson'), (2, 'Derp Herpinson')) accounts(id, name)
+WHERE id > :min_id
+ORDER BY id;
+
+-- name: greet
+SELECT 'Hello, ' || :name::text AS "greeting!";
+
+-- name: rename_account
+UPDATE accounts SET name = :name WHERE id = :id